@@ -2,6 +2,7 @@
 import argparse
 import os
 import subprocess
+import shlex
 from time import sleep
 DIR=os.path.dirname(os.path.realpath(__file__))
 os.chdir(DIR)
@@ -16,7 +17,7 @@ def parse_file(filepath):
         exit(2)
     param=dict()
     for i in lines:
-        line=i.split(':')
+        line=i.split(':',1)
         if len(line)!=2:
             break
         param[line[0].strip()]=line[1].strip()
@@ -94,11 +95,36 @@ def main(filepath,outputpath,skip_target,skip_learner,inference_id,restart_serve
     if not skip_learner :
         learner_docker(param)
     log_output=os.path.realpath(os.path.dirname(__file__))+"/"+outputpath
+    #without NodeIdType the node id is given with the OPC UA syntax (ns=1;s=the.answer or nsu=uri;s=the.answer)
+    if "NodeIdType" in param:
+        node_id_options=f"""--ns {param["Namespace"]} --idtype {param["NodeIdType"]}"""
+    else:
+        node_id_options=""
+    #optional fields of the DataValue written by write_req_status, write_req_source, write_req_server...
+    value_fields_options="".join(f" {option} {shlex.quote(param[key])}" for key,option in (("ValueStatus","--value-status"),("SourceTimestamp","--source-timestamp"),("ServerTimestamp","--server-timestamp")) if key in param)
+    #attributes of read_items and write_items, one per ReadItem<n> and WriteItem<n> line
+    items_options="".join(f" {option} {shlex.quote(value)}" for key,value in param.items() for prefix,option in (("ReadItem","--read-item"),("WriteItem","--write-item")) if key.startswith(prefix))
+    output_option=f" --output {shlex.quote(param['Output'])}" if "Output" in param else ""
+    if "ReturnDiagnostics" in param:
+        output_option+=f" --return-diagnostics {shlex.quote(param['ReturnDiagnostics'])}"
+    if "MaxClockSkew" in param:
+        output_option+=f" --max-clock-skew {shlex.quote(param['MaxClockSkew'])}"
+    if param.get("Scan","no").lower() in ("yes","true","1"):
+        output_option+=" --scan"
+    if param.get("CertReport","no").lower() in ("yes","true","1"):
+        output_option+=" --cert-report"
+    if "ClientCertificates" in param:
+        output_option+=f" --client-certificates {shlex.quote(param['ClientCertificates'])}"
+    #the PKI directory is mounted in the learner container
+    trust_list_volume=""
+    if "TrustList" in param:
+        trust_list_volume=f" -v {shlex.quote(os.path.realpath(param['TrustList']))}:/learner/pki:ro"
+        output_option+=" --trust-list pki"
     if restart_server:
         restart_server_option="--restart-server"
     else:
         restart_server_option=""
-    os.system(f""" nohup docker run --name learner_{inference_id} -v $(pwd)/result:/learner/result --network inference_network_{inference_id} --ip 192.123.{inference_id}.250 "learner:OPCUA"  ./OpcUaLeaner.py  -o {outputpath} --voc {param["Vocabulary"]} -m {param["Mode"]} --nb_target {param["NbContainers"]} --node {param["NodeId"]} --value {param["Value"]} --ns {param["Namespace"]} --idtype {param["NodeIdType"]} --valtype {param["ValueType"]} -t {param["Timeout"]} {restart_server_option} --inference-id {inference_id} > {log_output}/learner_ongoing 2>&1 & """)
+    os.system(f""" nohup docker run --name learner_{inference_id} -v $(pwd)/result:/learner/result{trust_list_volume} --network inference_network_{inference_id} --ip 192.123.{inference_id}.250 "learner:OPCUA"  ./OpcUaLeaner.py  -o {outputpath} --voc {param["Vocabulary"]} -m {param["Mode"]} --nb_target {param["NbContainers"]} --node {shlex.quote(param["NodeId"])} --value {shlex.quote(param["Value"])} {node_id_options} --valtype {param["ValueType"]}{value_fields_options}{items_options}{output_option} -t {param["Timeout"]} --liveness {param.get("Liveness","none")} {restart_server_option} --inference-id {inference_id} > {log_output}/learner_ongoing 2>&1 & """)
     while not os.path.exists(outputpath+"/automata.dot"):
         if detect_failure(log_output+"/learner_ongoing"):
             break
@@ -136,11 +162,37 @@ if __name__=="__main__":
         print("NodeId: nodeid (for read and write request)")
         print("NodeIdType: type of node id (NodeIdNumeric, NodeIdString,NodeIdGuid or NodeIdByteString)")
         print("Namespace: the namespace of the node")
+        print("  NodeIdType and Namespace can be omitted when NodeId uses the OPC UA syntax: ns=1;s=the.answer, i=2258,")
+        print("  or nsu=<namespace uri>;s=the.answer to find the namespace index in the NamespaceArray of the server")
         print("Value: value to write")
-        print("ValueType: type of the value (int8,uint8,int16,uint16,int32,uint32,float,double)")
+        print("ValueType: type of the value (bool,int8,uint8,int16,uint16,int32,uint32,int64,uint64,float,double,string,datetime,guid,bytestring,xmlelement,nodeid,expandednodeid,statuscode,qualifiedname,localizedtext)")
+        print("  datetime: ISO 8601 (2024-03-01T12:30:00Z), nodeid: ns=2;s=Foo, expandednodeid: nsu=uri;i=5, bytestring: base64 or 0x..., statuscode: name or value, qualifiedname: 2:Name, localizedtext: en|Text")
         print("NbContainers: number of targets container")
         print("Timeout:timeout in ms (socket timeout)")
         print("Mode: encryption mode (1:nothing,2:signature,3:signature+encryption)")
+        print("Liveness (optional): check the server after each word (none,hello,getendpoints)")
+        print("ValueStatus (optional): status code written with the value by write_req_status (name or value, Good by default)")
+        print("SourceTimestamp, ServerTimestamp (optional): ISO 8601 timestamps written by write_req_source and write_req_server (current time by default)")
+        print("  the fields can be combined in the vocabulary: write_req_status_source_server")
+        print("ReadItem1, ReadItem2... (optional): attributes read by read_items, node@attribute (ns=1;s=the.answer@UserAccessLevel)")
+        print("WriteItem1, WriteItem2... (optional): attributes written by write_items, node@attribute:type=value (ns=1;s=the.answer@DisplayName:21=en|Answer)")
+        print("Output (optional): abstraction of the responses, coarse (OK/NOK, default), class (Good/Uncertain/Bad) or name (name of the status code)")
+        print("  predicates on the fields of the responses can be added: name:server_nonce,server_certificate,server_signature,endpoints,diagnostics,string_table,additional_header,reason,trusted_certificate (needs TrustList)")
+        print("ReturnDiagnostics (optional): return_diagnostic mask of the request headers, 0x1f for the service diagnostics, 0x3ff for all (0 by default)")
+        print("  the diagnostics and the reason of the Error messages are printed with the outputs of each word")
+        print("MaxClockSkew (optional): difference in seconds between the timestamp of a response and the local time reported by the conformance oracle (300 by default)")
+        print("  the findings of the conformance oracle (request handle, timestamp, nonce length, timeouts, endpoints) are printed with the outputs of each word")
+        print("Scan (optional): yes to try each endpoint and user token policy of the target before the inference (no by default)")
+        print("  the reports of accepted and rejected configurations and weaknesses are written in scan_<n>.json and scan_<n>.txt, <n> being the index of the target (0 for the first one)")
+        print("TrustList (optional): PKI directory (trusted/certs, trusted/crl, issuers/certs, issuers/crl, rejected/certs) validating the server certificates")
+        print("  for the trusted_certificate predicate of Output and the Scan reports")
+        print("ClientCertificates (optional): generate the certificates of the mapper, the false ones with the defects (comma separated):")
+        print("  expired,not_yet_valid,wrong_uri,missing_key_usage,sha1_signature,weak_key,untrusted_chain,revoked")
+        print("  the generated CA is written in client_ca.der and client_ca.crl of the output, the intermediate CA in client_intermediate_ca.der and client_intermediate_ca.crl")
+        print("  the Vocabulary can use opn_cert_<defect> and activate_user_cert_<defect> for each defect (opn_cert_expired, activate_user_cert_revoked)")
+        print("  and opn_chain_<chain> and create_session_chain_<chain> for the chains complete,missing_intermediate,wrong_order,duplicate,truncated")
+        print("CertReport (optional): yes to analyze the certificates of the target before the inference (no by default)")
+        print("  key, signature, validity, application uri, key usages and chain are written in cert_<n>.json and cert_<n>.txt, <n> being the index of the target (0 for the first one)")
         exit(0)
     if args.o:
         outputpath=args.o
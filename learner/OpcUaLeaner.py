@@ -26,11 +26,36 @@ def fill_answer_with(prefix,value,size):
         return Word(prefix)
 class OpcUAKnowledgeBase(ActiveKnowledgeBase):
     shift=0
-    def __init__(self,timeout,nb_target,namespace,node_id,idtype,value,valtype,mode,restart_server):
+    def __init__(self,timeout,nb_target,namespace,node_id,idtype,value,valtype,mode,restart_server,liveness=None,findings=None,value_fields=(None,None,None),read_items=None,write_items=None,output=None,return_diagnostics=0,max_clock_skew=None,trust_list=None,credentials=None):
         super(OpcUAKnowledgeBase, self).__init__()
-        self.mapper=Map.Mapper(("crypto/uaexpert_key.der","crypto/key_wrong.der","crypto/uaexpert.der","crypto/cert_wrong.der","crypto/user_cert_true.der","crypto/user_cert_wrong.der",mode))
-        self.mapper.set_target_node(idtype,namespace,node_id)
+        #keys and certificates: paths or DER content (key, false key, certificate, false certificate, user certificate, user false certificate)
+        if credentials is None:
+            credentials=("crypto/uaexpert_key.der","crypto/key_wrong.der","crypto/uaexpert.der","crypto/cert_wrong.der","crypto/user_cert_true.der","crypto/user_cert_wrong.der")
+        self.mapper=Map.Mapper(tuple(credentials)+(mode,))
+        if idtype is None:
+            self.mapper.set_target_node_id(node_id)
+        else:
+            self.mapper.set_target_node(idtype,namespace,node_id)
         self.mapper.set_target_node_value(valtype,value)
+        #status code, source and server timestamps written by write_req_status, write_req_source, write_req_server...
+        self.mapper.set_target_node_value_fields(*value_fields)
+        #attributes of read_items and write_items
+        self.mapper.set_read_items(read_items or [])
+        self.mapper.set_write_items(write_items or [])
+        #PKI directory validating the server certificates, needed by the trusted_certificate predicate
+        if trust_list is not None:
+            self.mapper.set_trust_list(trust_list)
+        #abstraction of the responses: coarse (OK/NOK), class or name of the status code, with predicates on fields
+        if output is not None:
+            self.mapper.set_output_abstraction(output)
+        #diagnostics asked to the server in the request headers (return_diagnostic mask)
+        self.mapper.set_return_diagnostics(return_diagnostics)
+        #conformance oracle: tolerated difference between the timestamps of the responses and the local time
+        if max_clock_skew is not None:
+            self.mapper.set_max_clock_skew(max_clock_skew)
+        #liveness check after each word: None, "hello" or "getendpoints"
+        if liveness is not None and liveness!="none":
+            self.mapper.set_liveness_check(liveness=="getendpoints",timeout,findings)
         self.restart_server=restart_server
         for i in range(nb_target):
             dest=f"192.123.{inference_id}.{10+i}:{4840}"
@@ -95,6 +120,15 @@ class OpcUAKnowledgeBase(ActiveKnowledgeBase):
         
         res=self.mapper.submit_word(dest,word_,OpcUAKnowledgeBase.shift,timeout,expected,KnownNoResp)
         print("##\n   ",word_,"-->",res)
+        for index,error in self.mapper.parse_errors():
+            print("    parse error",word_[index],":",error)
+        liveness=self.mapper.liveness()
+        if liveness is not None and liveness[1]!="Alive":
+            print("   ",liveness[1],"after",word_[liveness[0]])
+        for index,diagnostic in self.mapper.diagnostics():
+            print("   ",word_[index],":",diagnostic)
+        for index,rule,description in self.mapper.conformance_findings():
+            print("    conformance",word_[index],":",rule,description)
         if self.restart_server:
             sock=socket.create_connection((f"192.123.{inference_id}.{10+OpcUAKnowledgeBase.shift}",5555))
             sock.close()
@@ -105,13 +139,58 @@ class OpcUAKnowledgeBase(ActiveKnowledgeBase):
         return Word(letters=ret)
 
 
-def main(outputdir,timeout,nb_target,namespace,node_id,idtype,value,valtype,mode,input_vocabulary,restart_server=False):              
+def main(outputdir,timeout,nb_target,namespace,node_id,idtype,value,valtype,mode,input_vocabulary,restart_server=False,liveness=None,value_fields=(None,None,None),read_items=None,write_items=None,output=None,return_diagnostics=0,max_clock_skew=None,scan=False,cert_report=False,trust_list=None,client_certificates=None):              
     input_letter=[Letter(i) for i in input_vocabulary]
     if outputdir[-1]=="/":
         outputdir=outputdir[:-1]
     print(f"{len(input_letter)} Letter")
     print([Letter(symbol) for symbol in input_vocabulary])
-    ServerBase = OpcUAKnowledgeBase(timeout,nb_target,namespace,node_id,idtype,value,valtype,mode,restart_server)
+    #certificates issued by a generated CA, the false ones with the defects; the CA and its revocation list are written for the targets
+    #the certificates of each defect (generated[8]) are given to the mapper for the opn_cert_<defect> and activate_user_cert_<defect> symbols,
+    #and the chains issued by the intermediate CA (generated[9]) for the opn_chain_<chain> and create_session_chain_<chain> symbols
+    credentials=None
+    if client_certificates is not None:
+        hostname=socket.gethostname()
+        generated=Map.generate_certificates(f"urn:{hostname}:mapper",hostname,client_certificates)
+        credentials=list(generated[:6])+[generated[8],generated[9]]
+        with open(f"{outputdir}/client_ca.der","wb") as fd:
+            fd.write(generated[6])
+        with open(f"{outputdir}/client_ca.crl","wb") as fd:
+            fd.write(generated[7])
+        with open(f"{outputdir}/client_intermediate_ca.der","wb") as fd:
+            fd.write(generated[10])
+        with open(f"{outputdir}/client_intermediate_ca.crl","wb") as fd:
+            fd.write(generated[11])
+    ServerBase = OpcUAKnowledgeBase(timeout,nb_target,namespace,node_id,idtype,value,valtype,mode,restart_server,liveness,outputdir+"/findings",value_fields,read_items,write_items,output,return_diagnostics,max_clock_skew,trust_list,credentials)
+    #symbols that cannot be sent (invalid additional parameters...) are rejected before learning
+    for symbol in input_vocabulary:
+        try:
+            ServerBase.mapper.check_symbol(symbol)
+        except ValueError as e:
+            print(f"invalid symbol {symbol}: {e}")
+            exit(2)
+    #report of the endpoints and security configurations of each target
+    if scan:
+        for i in range(nb_target):
+            try:
+                report_json,report_text=ServerBase.mapper.scan_endpoints(f"192.123.{inference_id}.{10+i}:{4840}",i,timeout)
+            except ValueError as e:
+                print(f"scan of target {i} failed: {e}")
+                exit(2)
+            with open(f"{outputdir}/scan_{i}.json","w") as fd:
+                fd.write(report_json)
+            with open(f"{outputdir}/scan_{i}.txt","w") as fd:
+                fd.write(report_text)
+            print(report_text)
+    #analysis of the certificates of each target
+    if cert_report:
+        for i in range(nb_target):
+            report_json,report_text=ServerBase.mapper.analyze_server_certificates(i)
+            with open(f"{outputdir}/cert_{i}.json","w") as fd:
+                fd.write(report_json)
+            with open(f"{outputdir}/cert_{i}.txt","w") as fd:
+                fd.write(report_text)
+            print(report_text)
     try:
         ServerBase.start_target()
         store=StoreHypothesis(ServerBase,input_vocabulary,outputdir,BDistMethod(ServerBase,input_letter,3))
@@ -141,23 +220,36 @@ if __name__ == "__main__":
     
     parser=argparse.ArgumentParser(description="create docker compose file")
     parser.add_argument('-o', nargs='?',type=str,help="name of ouput directory")
-    parser.add_argument('--voc',help="vocabulary as msg1,msg2...")
+    parser.add_argument('--voc',help="vocabulary as msg1,msg2..., the additional parameters of a request are given as msg[key=value;key:type=value]")
     parser.add_argument('--nb_target',metavar="nb target",type=int,help='number of container running at the same time')
-    parser.add_argument('--node',metavar="node_id",help="node id to write or read")
-    parser.add_argument('--value',metavar="value",help="value to write for the node")
+    parser.add_argument('--node',metavar="node_id",help="node id to write or read, with the OPC UA syntax (ns=1;s=name, nsu=uri;i=5) when --idtype is not given")
+    parser.add_argument('--value',metavar="value",help="value to write for the node, arrays between brackets ([1,2], [[1,2],[3,4]]), a leading backslash for a string starting with a bracket")
     parser.add_argument('--ns',metavar="namespace",help="namespace the node",type=int)
     parser.add_argument('--idtype',metavar="IdType",help="type of identifier for the node")
     parser.add_argument('--valtype',metavar="ValType",help="type of value in the node")
     parser.add_argument('-t',metavar="timeout in ms",help="timeout in ms")    
     parser.add_argument('-m',metavar="mode",help="1 nothing, 2 signature, 3 signature + encryption")    
     parser.add_argument('--restart-server',action="store_true",help="restart the target server only available on rust target")  
+    parser.add_argument('--value-status',metavar="status code",help="status code written by the write_req_status symbols (name or value, Good by default)")
+    parser.add_argument('--source-timestamp',metavar="ISO 8601 date",help="source timestamp written by the write_req_source symbols (current time by default)")
+    parser.add_argument('--server-timestamp',metavar="ISO 8601 date",help="server timestamp written by the write_req_server symbols (current time by default)")
+    parser.add_argument('--read-item',action="append",metavar="node@attribute",help="attribute read by read_items: ns=1;s=the.answer@UserAccessLevel or nsu=<uri>;s=the.answer@UserAccessLevel (can be repeated)")
+    parser.add_argument('--write-item',action="append",metavar="node@attribute:type=value",help="attribute written by write_items: ns=1;s=the.answer@DisplayName:21=en|Answer (can be repeated)")
+    parser.add_argument('--output',metavar="abstraction",help="abstraction of the responses: coarse (OK/NOK, default), class (Good/Uncertain/Bad) or name (status code name), optionally with predicates: name:server_nonce,endpoints")
+    parser.add_argument('--return-diagnostics',metavar="mask",type=lambda x:int(x,0),default=0,help="return_diagnostic mask of the request headers: 0x1f for all the service diagnostics, 0x3ff for all (0 by default)")
+    parser.add_argument('--max-clock-skew',metavar="seconds",type=int,help="difference between the timestamp of a response and the local time reported by the conformance oracle (300 by default)")
+    parser.add_argument('--scan',action="store_true",help="try each endpoint and user token policy of the targets before the inference, the reports are written in scan_<n>.json and scan_<n>.txt, <n> being the index of the target (0 for the first one)")
+    parser.add_argument('--trust-list',metavar="directory",help="PKI directory (trusted/certs, trusted/crl, issuers/certs, issuers/crl, rejected/certs) validating the server certificates for the trusted_certificate output predicate and the scan")
+    parser.add_argument('--client-certificates',metavar="defects",help="generate the certificates of the mapper instead of using crypto/, the false certificates have the defects: expired,not_yet_valid,wrong_uri,missing_key_usage,sha1_signature,weak_key,untrusted_chain,revoked (an empty string for none); the CA is written in client_ca.der and client_ca.crl and the intermediate CA in client_intermediate_ca.der and client_intermediate_ca.crl; the certificates of each defect are used by opn_cert_<defect> and activate_user_cert_<defect>, the chains complete,missing_intermediate,wrong_order,duplicate,truncated by opn_chain_<chain> and create_session_chain_<chain>")
+    parser.add_argument('--cert-report',action="store_true",help="analyze the certificates of the targets before the inference (key, signature, validity, application uri, key usages and chain), the reports are written in cert_<n>.json and cert_<n>.txt, <n> being the index of the target (0 for the first one)")
+    parser.add_argument('--liveness',choices=["none","hello","getendpoints"],default="none",help="check if the server is still alive after each word (crash and hang are saved in the findings directory of the output)")
     parser.add_argument('--inference-id',metavar="inference id",help="id of the inference. Used for parallel case. default value to 0. If it is not use in parallel use 0.",type=int,default=0)    
     args=parser.parse_args()
     inference_id=args.inference_id 
-    if args.node!=None and args.value!=None and args.idtype!=None and args.valtype!=None and args.voc!=None and args.o!=None and args.t!=None and args.nb_target!=None and args.ns!=None and args.m!=None:
+    if args.node!=None and args.value!=None and args.valtype!=None and args.voc!=None and args.o!=None and args.t!=None and args.nb_target!=None and args.m!=None and (args.idtype==None or args.ns!=None):
         node_id=args.node
         value=args.value
-        NodeIdType=eval("Map."+args.idtype)
+        NodeIdType=eval("Map."+args.idtype) if args.idtype!=None else None
         ValType=eval("Map."+args.valtype)
         timeout=int(args.t)
         input_vocabulary=[i for i in args.voc.split(",")]
@@ -168,6 +260,6 @@ if __name__ == "__main__":
     else:
         parser.print_help()
         exit(1)
-    main(outputdir,timeout,nb_target,namespace,node_id,NodeIdType,value,ValType,mode,input_vocabulary,args.restart_server)
+    main(outputdir,timeout,nb_target,namespace,node_id,NodeIdType,value,ValType,mode,input_vocabulary,args.restart_server,args.liveness,(args.value_status,args.source_timestamp,args.server_timestamp),args.read_item,args.write_item,args.output,args.return_diagnostics,args.max_clock_skew,args.scan,args.cert_report,args.trust_list,args.client_certificates)
 
 
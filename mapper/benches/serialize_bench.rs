@@ -0,0 +1,73 @@
+//! Allocations and time of the serialization of the requests, with `serialize` and with a buffer reused by `serialize_into`:
+//!   cargo bench --bench serialize_bench --features bench
+//! `serialize` allocates its buffer once and `serialize_into` does not allocate, the bench fails otherwise.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::time::Instant;
+
+use mapper::msg::bench;
+
+//counts the allocations of the current thread
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count() {
+    let _ = ALLOCATIONS.try_with(|a| a.set(a.get() + 1));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count();
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count();
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations<F: FnMut()>(mut f: F) -> usize {
+    let before = ALLOCATIONS.with(|a| a.get());
+    f();
+    ALLOCATIONS.with(|a| a.get()) - before
+}
+
+fn main() {
+    const ITERATIONS: usize = 100000;
+    let mut buf = Vec::with_capacity(4096);
+    //warm up the thread local counter
+    allocations(|| {});
+    for sample in bench::samples() {
+        let per_message = allocations(|| {
+            sample.serialize();
+        });
+        let reused = allocations(|| sample.serialize_into(&mut buf));
+        buf.clear();
+        assert_eq!(per_message, 1, "{}", sample.name);
+        assert_eq!(reused, 0, "{}", sample.name);
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            std::hint::black_box(sample.serialize());
+        }
+        let serialize = start.elapsed().as_nanos() / ITERATIONS as u128;
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            buf.clear();
+            sample.serialize_into(&mut buf);
+            std::hint::black_box(&buf);
+        }
+        let serialize_into = start.elapsed().as_nanos() / ITERATIONS as u128;
+        buf.clear();
+        println!("{:<16} serialize: {:>3} allocations, {:>6} ns | serialize_into: {} allocations, {:>6} ns", sample.name, per_message, serialize, reused, serialize_into);
+    }
+}
@@ -0,0 +1,499 @@
+//! Generation of the OPC UA definitions from the schema files of the OPC Foundation,
+//! vendored in `schema/` so that no network access is needed. The vendored files contain the definitions
+//! of the services of Part 4:
+//! - `StatusCode.csv`: constants and names of `StatusCode`
+//! - `NodeIds.csv`: identifiers of namespace 0, grouped by node class (`ObjectIds`, `DataTypeIds`...)
+//! - `Opc.Ua.Types.bsd`: structures and enumerations of the binary encoding
+//!
+//! The generated files are included from `uatypes::status_code`, `uatypes::node_ids` and `uatypes::generated`.
+//! The service messages of the schema (structures starting with a request or a response header) are framed with
+//! the headers of the secure channel and included from `msg::services`, the `Msg` enumeration of all the messages
+//! with their accessors and their decoding is included from `msg`. The fuzz targets decode data with each of them.
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SCHEMA_DIR: &str = "schema";
+const BINARY_SCHEMA_NS: &str = "http://opcfoundation.org/BinarySchema/";
+
+//types written by hand in uatypes and msg: the schema definitions are not generated, the fields
+//of generated structures use the hand-written types. The boolean tells if the type implements Mutate.
+const HAND_WRITTEN: &[(&str, bool)] = &[
+    ("ApplicationDescription", true),
+    ("ApplicationType", true),
+    ("EndpointDescription", false),
+    ("MessageSecurityMode", true),
+    ("UserTokenPolicy", false),
+    ("UserTokenType", true),
+    ("UserNameIdentityToken", false),
+    ("X509IdentityToken", false),
+    ("AnonymousIdentityToken", false),
+    ("SignatureData", true),
+    ("ReadValueId", true),
+    ("WriteValue", true),
+    ("RequestHeader", true),
+    ("ResponseHeader", false),
+    ("ServiceFault", false),
+];
+
+//messages written by hand in msg which are not service messages of the schema (Part 6 messages and crafted
+//requests): name, Mutate implementation and whether it has a request header
+const HAND_WRITTEN_MESSAGES: &[(&str, bool, bool)] = &[
+    ("HelloMessage", true, false),
+    ("AckowledgeMessage", false, false),
+    ("RevHelloMessage", false, false),
+    ("ErrorMessage", false, false),
+    ("NullSize", true, true),
+];
+
+//service messages sent with an asymmetric security header, the others use the symmetric one
+const ASYMMETRIC_MESSAGES: &[&str] = &["OpenSecureChannelRequest", "OpenSecureChannelResponse"];
+
+//built-in types of the schema: rust type and Mutate implementation
+const BUILT_IN: &[(&str, &str, bool)] = &[
+    ("opc:Boolean", "bool", true),
+    ("opc:SByte", "i8", true),
+    ("opc:Byte", "u8", true),
+    ("opc:Int16", "i16", true),
+    ("opc:UInt16", "u16", true),
+    ("opc:Int32", "i32", true),
+    ("opc:UInt32", "u32", true),
+    ("opc:Int64", "i64", true),
+    ("opc:UInt64", "u64", true),
+    ("opc:Float", "f32", true),
+    ("opc:Double", "f64", true),
+    ("opc:String", "UaString", true),
+    ("opc:CharArray", "UaString", true),
+    ("opc:DateTime", "DateTime", true),
+    ("opc:Guid", "Guid", true),
+    ("opc:ByteString", "ByteString", true),
+    ("ua:XmlElement", "XmlElement", false),
+    ("ua:NodeId", "NodeId", true),
+    ("ua:ExpandedNodeId", "ExpandedNodeId", false),
+    //status codes are kept as u32 (as in the hand-written messages)
+    ("ua:StatusCode", "u32", true),
+    ("ua:QualifiedName", "QualifiedName", true),
+    ("ua:LocalizedText", "LocalizedText", true),
+    ("ua:ExtensionObject", "ExtensionObject", true),
+    ("ua:DataValue", "DataValue", true),
+    ("ua:Variant", "Variant", true),
+    ("ua:DiagnosticInfo", "DiagnosticInfo", false),
+];
+
+const KEYWORDS: &[&str] = &["type", "match", "ref", "self", "struct", "enum", "mod", "use", "loop", "move", "where", "impl", "fn", "in", "as", "const", "static", "trait", "box"];
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", SCHEMA_DIR);
+    println!("cargo:rerun-if-changed=build.rs");
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    let schema = Path::new(SCHEMA_DIR);
+    write(&out_dir.join("status_codes.rs"), &status_codes(&read(&schema.join("StatusCode.csv"))));
+    let node_ids_csv = read(&schema.join("NodeIds.csv"));
+    write(&out_dir.join("node_ids.rs"), &node_ids(&node_ids_csv));
+    let node_names: Vec<String> = csv_lines(&node_ids_csv).map(|f| f[0].clone()).collect();
+    let generated = types(&read(&schema.join("Opc.Ua.Types.bsd")), &node_names);
+    write(&out_dir.join("types.rs"), &generated.types);
+    write(&out_dir.join("services.rs"), &generated.services);
+    write(&out_dir.join("messages.rs"), &generated.messages);
+    write(&out_dir.join("fuzz_targets.rs"), &generated.fuzz_targets);
+}
+
+fn read(path: &Path) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| panic!("cannot read {}: {}", path.display(), e))
+}
+
+fn write(path: &Path, content: &str) {
+    fs::write(path, content).unwrap_or_else(|e| panic!("cannot write {}: {}", path.display(), e));
+}
+
+//fields of a line of the csv files, descriptions are quoted and may contain commas
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+fn csv_lines(content: &str) -> impl Iterator<Item = Vec<String>> + '_ {
+    content.lines().map(str::trim).filter(|l| !l.is_empty()).map(csv_fields)
+}
+
+fn status_codes(csv: &str) -> String {
+    let codes: Vec<(String, u32, String)> = csv_lines(csv)
+        .map(|f| {
+            let value = u32::from_str_radix(f[1].trim_start_matches("0x"), 16).unwrap_or_else(|_| panic!("invalid status code {}", f[1]));
+            (f[0].clone(), value, f.get(2).cloned().unwrap_or_default())
+        })
+        .collect();
+    let mut out = String::from("//generated by build.rs from schema/StatusCode.csv\n\n");
+    out.push_str("//we conserve the same syntax as provided in the documentation\n#[allow(non_upper_case_globals)]\nimpl StatusCode {\n");
+    for (name, value, description) in &codes {
+        if !description.is_empty() {
+            writeln!(out, "    ///{}", description).unwrap();
+        }
+        writeln!(out, "    pub const {}: u32 = 0x{:08X};", name, value).unwrap();
+    }
+    out.push_str("\n    ///Name of a status code of the specification\n    pub fn name(code: u32) -> Option<&'static str> {\n        match code {\n");
+    for (name, _, _) in &codes {
+        writeln!(out, "            StatusCode::{} => Some(\"{}\"),", name, name).unwrap();
+    }
+    out.push_str("            _ => None,\n        }\n    }\n");
+    out.push_str("\n    ///Status code of the specification with this name\n    pub fn from_name(name: &str) -> Option<u32> {\n        match name {\n");
+    for (name, _, _) in &codes {
+        writeln!(out, "            \"{}\" => Some(StatusCode::{}),", name, name).unwrap();
+    }
+    out.push_str("            _ => None,\n        }\n    }\n}\n");
+    out
+}
+
+fn node_ids(csv: &str) -> String {
+    //node classes in order of appearance
+    let mut classes: Vec<(String, Vec<(String, u32)>)> = Vec::new();
+    for f in csv_lines(csv) {
+        let id: u32 = f[1].parse().unwrap_or_else(|_| panic!("invalid node id {}", f[1]));
+        let class = f.get(2).cloned().unwrap_or_default();
+        match classes.iter_mut().find(|(c, _)| *c == class) {
+            Some((_, ids)) => ids.push((f[0].clone(), id)),
+            None => classes.push((class, vec![(f[0].clone(), id)])),
+        }
+    }
+    let mut out = String::from("//generated by build.rs from schema/NodeIds.csv\n");
+    for (class, ids) in &classes {
+        writeln!(out, "\n///Numeric identifiers of the {} nodes of namespace 0\npub struct {}Ids;\n", class, class).unwrap();
+        writeln!(out, "#[allow(non_upper_case_globals)]\nimpl {}Ids {{", class).unwrap();
+        for (name, id) in ids {
+            writeln!(out, "    pub const {}: u32 = {};", name, id).unwrap();
+        }
+        out.push_str("}\n");
+    }
+    out
+}
+
+//ApplicationUri -> application_uri, ServerURI -> server_uri
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut result = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).map(|n| n.is_lowercase()).unwrap_or(false);
+            if previous.is_lowercase() || previous.is_ascii_digit() || (previous.is_uppercase() && next_is_lower) {
+                result.push('_');
+            }
+        }
+        result.extend(c.to_lowercase());
+    }
+    if KEYWORDS.contains(&result.as_str()) {
+        result.insert_str(0, "r#");
+    }
+    result
+}
+
+struct Field {
+    name: String,
+    type_name: String,
+    array: bool,
+}
+
+struct Structure {
+    name: String,
+    documentation: Option<String>,
+    fields: Vec<Field>,
+}
+
+struct Enumeration {
+    name: String,
+    documentation: Option<String>,
+    values: Vec<(String, i64)>,
+}
+
+//service message of the schema: its name, whether it is a request and whether it is written by hand
+struct Service {
+    name: String,
+    request: bool,
+    hand_written: bool,
+}
+
+struct Generated {
+    types: String,
+    services: String,
+    messages: String,
+    fuzz_targets: String,
+}
+
+fn documentation(node: roxmltree::Node) -> Option<String> {
+    node.children()
+        .find(|c| c.has_tag_name((BINARY_SCHEMA_NS, "Documentation")))
+        .and_then(|c| c.text())
+        .map(|t| t.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+fn types(bsd: &str, node_names: &[String]) -> Generated {
+    let document = roxmltree::Document::parse(bsd).expect("invalid Opc.Ua.Types.bsd");
+    let hand_written: HashMap<&str, bool> = HAND_WRITTEN.iter().copied().collect();
+    let mut structures = Vec::new();
+    let mut enumerations = Vec::new();
+    let mut skipped = Vec::new();
+    let mut unknown_types = Vec::new();
+    let mut empty = Vec::new();
+    let mut services = Vec::new();
+    //enumerations of the schema, the hand-written ones included
+    let mut enumeration_names = Vec::new();
+    for node in document.root_element().children().filter(|n| n.is_element()) {
+        let name = match node.attribute("Name") {
+            Some(n) => n.to_string(),
+            None => continue,
+        };
+        let first_field = node.children().find(|c| c.has_tag_name((BINARY_SCHEMA_NS, "Field"))).and_then(|f| f.attribute("TypeName"));
+        if let Some(header @ ("tns:RequestHeader" | "tns:ResponseHeader")) = first_field {
+            services.push(Service { name: name.clone(), request: header == "tns:RequestHeader", hand_written: hand_written.contains_key(name.as_str()) });
+        }
+        if node.has_tag_name((BINARY_SCHEMA_NS, "EnumeratedType")) {
+            enumeration_names.push(name.clone());
+        }
+        if hand_written.contains_key(name.as_str()) {
+            continue;
+        }
+        if node.has_tag_name((BINARY_SCHEMA_NS, "EnumeratedType")) {
+            let values = node
+                .children()
+                .filter(|c| c.has_tag_name((BINARY_SCHEMA_NS, "EnumeratedValue")))
+                .map(|c| (c.attribute("Name").unwrap_or_default().to_string(), c.attribute("Value").and_then(|v| v.parse().ok()).unwrap_or_default()))
+                .collect();
+            enumerations.push(Enumeration { name, documentation: documentation(node), values });
+        } else if node.has_tag_name((BINARY_SCHEMA_NS, "StructuredType")) {
+            let fields: Vec<roxmltree::Node> = node.children().filter(|c| c.has_tag_name((BINARY_SCHEMA_NS, "Field"))).collect();
+            //optional fields and unions are not supported by the generator
+            if fields.iter().any(|f| f.attribute("SwitchField").is_some() || f.attribute("TypeName") == Some("opc:Bit")) {
+                skipped.push(name);
+                continue;
+            }
+            //abstract base types of the ExtensionObjects, their subtypes are generated
+            if fields.is_empty() {
+                empty.push(name);
+                continue;
+            }
+            let length_fields: Vec<&str> = fields.iter().filter_map(|f| f.attribute("LengthField")).collect();
+            let fields = fields
+                .iter()
+                .filter(|f| !length_fields.contains(&f.attribute("Name").unwrap_or_default()))
+                .map(|f| Field {
+                    name: f.attribute("Name").unwrap_or_default().to_string(),
+                    type_name: f.attribute("TypeName").unwrap_or_default().to_string(),
+                    array: f.attribute("LengthField").is_some(),
+                })
+                .collect();
+            structures.push(Structure { name, documentation: documentation(node), fields });
+        }
+    }
+
+    //rust type of a field and whether it implements Mutate (structures are resolved afterwards),
+    //None for the types the generator does not know
+    let field_type = |type_name: &str, structure_names: &[String]| -> Option<(String, Option<bool>)> {
+        if let Some((_, rust, mutate)) = BUILT_IN.iter().find(|(t, _, _)| *t == type_name) {
+            return Some((rust.to_string(), Some(*mutate)));
+        }
+        let local = type_name.trim_start_matches("tns:").trim_start_matches("ua:");
+        //enumerations are encoded as Int32, kept as u32 as in the hand-written structures
+        if enumeration_names.iter().any(|e| e == local) {
+            return Some((String::from("u32"), Some(true)));
+        }
+        if let Some(mutate) = hand_written.get(local) {
+            return Some((local.to_string(), Some(*mutate)));
+        }
+        if structure_names.iter().any(|s| s == local) {
+            return Some((local.to_string(), None));
+        }
+        None
+    };
+
+    //structures with a field of unknown type are skipped, as well as the structures using them
+    loop {
+        let structure_names: Vec<String> = structures.iter().map(|s| s.name.clone()).collect();
+        let (kept, unknown): (Vec<Structure>, Vec<Structure>) = structures
+            .into_iter()
+            .partition(|s| s.fields.iter().all(|f| field_type(&f.type_name, &structure_names).is_some()));
+        structures = kept;
+        if unknown.is_empty() {
+            break;
+        }
+        unknown_types.extend(unknown.into_iter().map(|s| s.name));
+    }
+    services.retain(|s| s.hand_written || structures.iter().any(|t| t.name == s.name));
+    let structure_names: Vec<String> = structures.iter().map(|s| s.name.clone()).collect();
+    let field_type = |type_name: &str| field_type(type_name, &structure_names).expect("unknown types are skipped");
+
+    //a structure implements Mutate when all its fields do
+    let mut mutable: HashMap<&str, bool> = HashMap::new();
+    loop {
+        let mut changed = false;
+        for structure in &structures {
+            if mutable.contains_key(structure.name.as_str()) {
+                continue;
+            }
+            let mut result = Some(true);
+            for field in &structure.fields {
+                let known = match field_type(&field.type_name) {
+                    (_, Some(m)) => Some(m),
+                    (rust, None) => mutable.get(rust.as_str()).copied(),
+                };
+                result = match (result, known) {
+                    (_, Some(false)) | (Some(false), _) => Some(false),
+                    (Some(true), Some(true)) => Some(true),
+                    _ => None,
+                };
+            }
+            if let Some(m) = result {
+                mutable.insert(&structure.name, m);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    let is_service = |name: &str| services.iter().any(|s| s.name == name);
+    let mut out = String::from("//generated by build.rs from schema/Opc.Ua.Types.bsd\n");
+    if !skipped.is_empty() {
+        writeln!(out, "//not generated (optional fields): {}", skipped.join(", ")).unwrap();
+    }
+    if !unknown_types.is_empty() {
+        writeln!(out, "//not generated (unsupported field types): {}", unknown_types.join(", ")).unwrap();
+    }
+    if !empty.is_empty() {
+        writeln!(out, "//not generated (no fields): {}", empty.join(", ")).unwrap();
+    }
+    for enumeration in &enumerations {
+        out.push('\n');
+        if let Some(d) = &enumeration.documentation {
+            writeln!(out, "///{}", d).unwrap();
+        }
+        writeln!(out, "pub struct {};\nimpl {} {{", enumeration.name, enumeration.name).unwrap();
+        for (name, value) in &enumeration.values {
+            writeln!(out, "    pub const {}: u32 = {};", snake_case(name).trim_start_matches("r#").to_uppercase(), value).unwrap();
+        }
+        out.push_str("}\n");
+    }
+    let mut services_out = String::from("//generated by build.rs from schema/Opc.Ua.Types.bsd\n");
+    for structure in &structures {
+        let service = is_service(&structure.name);
+        let out = if service { &mut services_out } else { &mut out };
+        out.push('\n');
+        if let Some(d) = &structure.documentation {
+            writeln!(out, "///{}", d).unwrap();
+        }
+        let derive_mutate = mutable.get(structure.name.as_str()).copied().unwrap_or(false);
+        writeln!(out, "#[derive(Debug, Serialize, Deserialize{})]", if derive_mutate { ", Mutate" } else { "" }).unwrap();
+        writeln!(out, "pub struct {} {{", structure.name).unwrap();
+        //service messages are sent in a chunk of the secure channel, the body is preceded by its encoding id
+        if service {
+            let security_header = if ASYMMETRIC_MESSAGES.contains(&structure.name.as_str()) { "AsymmetricSecurityHeader" } else { "SymmetricSecurityHeader" };
+            writeln!(out, "    pub(crate) message_header: MessageHeader,\n    pub(crate) security_header: {},", security_header).unwrap();
+            out.push_str("    pub(crate) sequence_header: SequenceHeader,\n    pub(crate) node_id: NodeId,\n");
+        }
+        for field in &structure.fields {
+            let (rust, _) = field_type(&field.type_name);
+            let rust = if field.array { format!("UaArray<{}>", rust) } else { rust };
+            writeln!(out, "    pub(crate) {}: {},", snake_case(&field.name), rust).unwrap();
+        }
+        out.push_str("}\n");
+    }
+
+    //structures with a binary encoding object can be decoded from ExtensionObjects
+    let encoded: Vec<&str> = structures
+        .iter()
+        .map(|s| s.name.as_str())
+        .filter(|name| !is_service(name) && node_names.iter().any(|n| *n == format!("{}_Encoding_DefaultBinary", name)))
+        .collect();
+    for name in &encoded {
+        writeln!(out, "\nimpl BinaryEncodingId for {} {{\n    const ENCODING_ID: u32 = ObjectIds::{}_Encoding_DefaultBinary;\n}}", name, name).unwrap();
+    }
+    out.push_str("\n//decoders of the generated structures, looked up by TypeRegistry\npub(crate) fn registration(encoding_id: u32) -> Option<Registration> {\n    match encoding_id {\n");
+    for name in &encoded {
+        writeln!(out, "        {}::ENCODING_ID => Some(Registration::of::<{}>()),", name, name).unwrap();
+    }
+    out.push_str("        _ => None,\n    }\n}\n");
+    let service_mutate = |s: &Service| if s.hand_written { hand_written[s.name.as_str()] } else { mutable.get(s.name.as_str()).copied().unwrap_or(false) };
+    let structures: Vec<&str> = structures.iter().map(|s| s.name.as_str()).filter(|name| !is_service(name)).collect();
+    Generated { types: out, services: services_out, messages: messages(&services, service_mutate), fuzz_targets: fuzz_targets(&structures, &services) }
+}
+
+//decoding of data with each generated structure and each message, included from fuzz::targets
+fn fuzz_targets(structures: &[&str], services: &[Service]) -> String {
+    let mut out = String::from("//generated by build.rs from schema/Opc.Ua.Types.bsd\n\n");
+    out.push_str("//decode data with each generated structure\nfn generated(data: &[u8]) {\n");
+    for name in structures {
+        writeln!(out, "    decode::<{}>(data);", name).unwrap();
+    }
+    out.push_str("}\n\n//decode data with each message, whatever its message type and encoding id\nfn messages(data: &[u8]) {\n");
+    for name in HAND_WRITTEN_MESSAGES.iter().map(|(n, _, _)| *n).chain(services.iter().map(|s| s.name.as_str())) {
+        writeln!(out, "    let _ = {}::deserialize(data);", name).unwrap();
+    }
+    out.push_str("}\n");
+    out
+}
+
+//the Msg enumeration of the hand-written messages and of the service messages
+fn messages(services: &[Service], mutate: impl Fn(&Service) -> bool) -> String {
+    let mut out = String::from("//generated by build.rs from schema/Opc.Ua.Types.bsd\n\n");
+    out.push_str("///List of supported message.\n#[derive(Debug,DebugOutputEnum,Serialize)]\npub (crate) enum Msg {\n");
+    for (name, _, _) in HAND_WRITTEN_MESSAGES {
+        writeln!(out, "    {}({}),", name, name).unwrap();
+    }
+    for service in services {
+        writeln!(out, "    {}({}),", service.name, service.name).unwrap();
+    }
+    out.push_str("}\n\nimpl Msg {\n");
+    out.push_str("    ///name of the message type\n    pub(crate) fn name(&self) -> &'static str {\n        match self {\n");
+    for name in HAND_WRITTEN_MESSAGES.iter().map(|(n, _, _)| *n).chain(services.iter().map(|s| s.name.as_str())) {
+        writeln!(out, "            Msg::{}(_) => \"{}\",", name, name).unwrap();
+    }
+    out.push_str("        }\n    }\n");
+    let requests: Vec<&str> = services
+        .iter()
+        .filter(|s| s.request)
+        .map(|s| s.name.as_str())
+        .chain(HAND_WRITTEN_MESSAGES.iter().filter(|(_, _, request)| *request).map(|(n, _, _)| *n))
+        .collect();
+    for (accessor, reference) in [("request_header", "&"), ("request_header_mut", "&mut ")] {
+        writeln!(out, "\n    ///request header of the requests, None for the other messages\n    pub(crate) fn {}({}self) -> Option<{}RequestHeader> {{\n        match self {{", accessor, reference, reference).unwrap();
+        for name in &requests {
+            writeln!(out, "            Msg::{}(m) => Some({}m.request_header),", name, reference).unwrap();
+        }
+        out.push_str("            _ => None,\n        }\n    }\n");
+    }
+    out.push_str("\n    ///response header of the responses and service faults, None for the other messages\n    pub(crate) fn response_header(&self) -> Option<&ResponseHeader> {\n        match self {\n");
+    for service in services.iter().filter(|s| !s.request) {
+        writeln!(out, "            Msg::{}(m) => Some(&m.response_header),", service.name).unwrap();
+    }
+    out.push_str("            _ => None,\n        }\n    }\n");
+    out.push_str("\n    ///decoding of a service message from the encoding id of its body\n    pub(crate) fn deserialize_service(encoding_id: u32, data: &[u8]) -> MapperResult<Msg> {\n        let msg = match encoding_id {\n");
+    for service in services {
+        writeln!(out, "            ObjectIds::{}_Encoding_DefaultBinary => Msg::{}({}::deserialize(data)?.1),", service.name, service.name, service.name).unwrap();
+    }
+    out.push_str("            _ => return Err(MapperError::new(MapperErrorKind::ParsingError, \"bad unknown type or not implemented yet\")),\n        };\n        Ok(msg)\n    }\n}\n");
+    out.push_str("\n//only the client requests are mutated, they are the messages sent by the mapper\nimpl Mutate for Msg {\n    fn mutations(&self) -> Vec<Mutation> {\n        match self {\n");
+    let mutable = HAND_WRITTEN_MESSAGES.iter().filter(|(_, m, _)| *m).map(|(n, _, _)| *n).chain(services.iter().filter(|s| s.request && mutate(s)).map(|s| s.name.as_str()));
+    for name in mutable {
+        writeln!(out, "            Msg::{}(m) => m.mutations(),", name).unwrap();
+    }
+    out.push_str("            _ => vec![],\n        }\n    }\n}\n");
+    out
+}
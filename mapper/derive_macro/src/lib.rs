@@ -1,9 +1,13 @@
 use proc_macro::TokenStream;
-use syn::{DataStruct,Data,Fields, DeriveInput, DataEnum, Field, token::{Comma}, Ident, Variant, GenericParam};
-use quote::quote;
+use syn::{DataStruct,Data,Fields, DeriveInput, DataEnum, Field, token::{Comma}, Ident, Variant, GenericParam, Attribute, Expr, Type, PathArguments, GenericArgument};
+use syn::parse::ParseStream;
+use quote::{quote, ToTokens};
 
-///derive the Serialize trait
-#[proc_macro_derive(Serialize)]
+///derive the Serialize trait. Fields are written in order into the caller's buffer,
+///`serialize` and `encoded_len` come from the trait.
+///Structures with `#[ua(mask_bit = ...)]` fields and enumerations with `#[ua(tag = ...)]` variants
+///are encoded as described in `ua_options`.
+#[proc_macro_derive(Serialize, attributes(ua))]
  pub fn derive_serialize(input: TokenStream) -> TokenStream { 
    let ast = syn::parse_macro_input!(input as DeriveInput);
     impl_serialize_macro(&ast)
@@ -11,6 +15,12 @@ use quote::quote;
  }
 
 fn impl_serialize_macro(ast: &syn::DeriveInput)-> TokenStream{
+   if has_ua_options(ast) {
+      return match impl_serialize_ua(ast) {
+         Ok(expanded)=>expanded,
+         Err(e)=>TokenStream::from(e.to_compile_error()),
+      };
+   }
    let generic= &ast.generics.params.first();
    let name = &ast.ident;
    match &ast.data {
@@ -27,12 +37,10 @@ fn impl_serialize_macro(ast: &syn::DeriveInput)-> TokenStream{
        Some(GenericParam::Lifetime(_))=>{
          let expanded = quote! {
             impl<'a> Serialize for #struct_name<'a>{
-                  fn serialize(&self)->Vec<u8>{
-                     let mut result: Vec<u8> =Vec::new();
+                  fn serialize_into(&self,buf:&mut impl crate::BufMut){
                      #(
-                        result.extend_from_slice(&self.#field_name.serialize());
+                        self.#field_name.serialize_into(buf);
                      )*
-                     result
                   }
                }
 
@@ -43,12 +51,10 @@ fn impl_serialize_macro(ast: &syn::DeriveInput)-> TokenStream{
        Some(GenericParam::Type(_))=>{
          let expanded = quote! {
             impl<T:Serialize+Deserialize> Serialize for #struct_name<T>{
-                  fn serialize(&self)->Vec<u8>{
-                     let mut result: Vec<u8> =Vec::new();
+                  fn serialize_into(&self,buf:&mut impl crate::BufMut){
                      #(
-                        result.extend_from_slice(&self.#field_name.serialize());
+                        self.#field_name.serialize_into(buf);
                      )*
-                     result
                   }
                }
 
@@ -59,12 +65,10 @@ fn impl_serialize_macro(ast: &syn::DeriveInput)-> TokenStream{
        _=>{
          let expanded = quote! {
        impl Serialize for #struct_name{
-             fn serialize(&self)->Vec<u8>{
-                let mut result: Vec<u8> =Vec::new();
+             fn serialize_into(&self,buf:&mut impl crate::BufMut){
                 #(
-                   result.extend_from_slice(&self.#field_name.serialize());
+                   self.#field_name.serialize_into(buf);
                 )*
-                result
              }
           }
 
@@ -82,10 +86,10 @@ fn impl_serialize_macro_enum(variants: &syn::punctuated::Punctuated<Variant, Com
       Some(GenericParam::Lifetime(_))=>{
       let expanded = quote! {
          impl<'a> Serialize for #struct_name<'a>{
-            fn serialize(&self)->Vec<u8> {
+            fn serialize_into(&self,buf:&mut impl crate::BufMut) {
                match self {
                   #(
-                     #struct_name::#field_name(s)=>s.serialize(),
+                     #struct_name::#field_name(s)=>s.serialize_into(buf),
                   )*
                   }
             }
@@ -99,10 +103,10 @@ fn impl_serialize_macro_enum(variants: &syn::punctuated::Punctuated<Variant, Com
       _=>{
          let expanded = quote! {
             impl Serialize for #struct_name {
-               fn serialize(&self)->Vec<u8> {
+               fn serialize_into(&self,buf:&mut impl crate::BufMut) {
                   match self {
                      #(
-                        #struct_name::#field_name(s)=>s.serialize(),
+                        #struct_name::#field_name(s)=>s.serialize_into(buf),
                      )*
    
                      }
@@ -118,8 +122,10 @@ fn impl_serialize_macro_enum(variants: &syn::punctuated::Punctuated<Variant, Com
  
 
 
-///derive the Deserialize trait
- #[proc_macro_derive(Deserialize)]
+///derive the Deserialize trait. Fields are decoded with `<Type as Deserialize>`, so generic
+///field types can be written as usual (`UaArray<T>` instead of `UaArray::<T>`).
+///Errors are wrapped with the structure and field names (see `MapperError::in_field`).
+ #[proc_macro_derive(Deserialize, attributes(ua))]
  pub fn derive_deserialize(input: TokenStream) -> TokenStream { 
     let ast = syn::parse_macro_input!(input as syn::DeriveInput);
 
@@ -128,11 +134,19 @@ fn impl_serialize_macro_enum(variants: &syn::punctuated::Punctuated<Variant, Com
  }
 
 fn impl_deserialize_macro(ast: &syn::DeriveInput) -> TokenStream {
+   if has_ua_options(ast) {
+      return match impl_deserialize_ua(ast) {
+         Ok(expanded)=>expanded,
+         Err(e)=>TokenStream::from(e.to_compile_error()),
+      };
+   }
    let fields= match &ast.data {Data::Struct( DataStruct{fields: Fields::Named(fields),..})=>&fields.named,
    _=> panic!("struct need to have fields") };
    let field_name: Vec<_> = fields.iter().map(|field| &field.ident).collect();
    let field_type = fields.iter().map(|field| &field.ty);
+   let field_label: Vec<_> = fields.iter().map(|field| field.ident.as_ref().map(|i| i.to_string()).unwrap_or_default()).collect();
    let struct_name = &ast.ident;
+   let type_name = struct_name.to_string();
    let struct_lifetime=&ast.generics.params.first();
    match struct_lifetime {
    
@@ -141,7 +155,7 @@ fn impl_deserialize_macro(ast: &syn::DeriveInput) -> TokenStream {
          impl<'a> Deserialize for #struct_name<'a>{
             fn deserialize(data:&[u8])-> MapperResult<(&[u8], Self)>{
                   #(
-                  let (data,#field_name)=#field_type::deserialize(data)?;
+                  let (data,#field_name)=<#field_type as Deserialize>::deserialize(data).map_err(|e| e.in_field(#type_name,#field_label,data.len()))?;
                   )*
                   let return_struct=#struct_name{
                      #(
@@ -160,7 +174,7 @@ fn impl_deserialize_macro(ast: &syn::DeriveInput) -> TokenStream {
          impl<T:Deserialize+Serialize> Deserialize for #struct_name<T>{
             fn deserialize(data:&[u8])-> MapperResult<(&[u8], Self)>{
                   #(
-                  let (data,#field_name)=#field_type::deserialize(data)?;
+                  let (data,#field_name)=<#field_type as Deserialize>::deserialize(data).map_err(|e| e.in_field(#type_name,#field_label,data.len()))?;
                   )*
                   let return_struct=#struct_name{
                      #(
@@ -179,7 +193,7 @@ fn impl_deserialize_macro(ast: &syn::DeriveInput) -> TokenStream {
          impl Deserialize for #struct_name{
             fn deserialize(data:&[u8])-> MapperResult<(&[u8], Self)>{
                   #(
-                  let (data,#field_name)=#field_type::deserialize(data)?;
+                  let (data,#field_name)=<#field_type as Deserialize>::deserialize(data).map_err(|e| e.in_field(#type_name,#field_label,data.len()))?;
                   )*
                   let return_struct=#struct_name{
                      #(
@@ -265,4 +279,303 @@ match generic {
    },
    }
     
-}
\ No newline at end of file
+}
+
+///derive the Mutate trait. Each field is mutated in turn while the other fields keep their
+///regular encoding, so a mutant differs from the original message by exactly one field.
+#[proc_macro_derive(Mutate)]
+pub fn derive_mutate(input: TokenStream) -> TokenStream {
+   let ast = syn::parse_macro_input!(input as DeriveInput);
+   impl_mutate_macro(&ast)
+}
+
+fn impl_mutate_macro(ast: &syn::DeriveInput) -> TokenStream {
+   let fields= match &ast.data {Data::Struct( DataStruct{fields: Fields::Named(fields),..})=>&fields.named,
+   _=> panic!("struct need to have named fields") };
+   let field_name: Vec<_> = fields.iter().map(|field| &field.ident).collect();
+   let struct_name = &ast.ident;
+   let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+   let expanded = quote! {
+      impl #impl_generics Mutate for #struct_name #ty_generics #where_clause {
+         fn mutations(&self)->Vec<crate::fuzz::Mutation>{
+            crate::fuzz::combine(vec![
+               #(
+                  (stringify!(#field_name),self.#field_name.serialize(),self.#field_name.mutations()),
+               )*
+            ])
+         }
+      }
+   };
+   TokenStream::from(expanded)
+}
+
+///Options of the `ua` attribute, for encodings that are not a plain sequence of fields:
+/// - `#[ua(mask_bit = EXPR)]` on an `Option<T>` field: the field is encoded only when it is `Some`, which is
+///   recorded by the bit `EXPR` of an encoding mask (one byte). The mask is written before the first of these fields.
+/// - `#[ua(mask)]` on an `u8` field: it receives the decoded mask and is not encoded (the mask is computed from the fields).
+/// - `#[ua(depth_guard)]` on a structure: its decoding counts for the nesting depth (recursive types).
+/// - `#[ua(tag = EXPR)]` on each variant of an enumeration: the variant is encoded as the byte `EXPR` followed by
+///   its value, if any (variants are unit or have a single field).
+#[derive(Default)]
+struct UaOptions{
+   mask:bool,
+   mask_bit:Option<Expr>,
+   depth_guard:bool,
+   tag:Option<Expr>,
+}
+
+fn ua_options(attrs:&[Attribute])->syn::Result<UaOptions>{
+   let mut options=UaOptions::default();
+   for attr in attrs{
+      parse_ua_attribute(attr,Some(&mut options))?;
+   }
+   Ok(options)
+}
+
+//parse the attribute from its tokens (`#[name(...)]`), which are the same for every version of syn.
+//Return if it is a `ua` attribute, its options are added to `options`.
+fn parse_ua_attribute(attr:&Attribute,options:Option<&mut UaOptions>)->syn::Result<bool>{
+   let parser=|input:ParseStream|->syn::Result<bool>{
+      input.parse::<syn::Token![#]>()?;
+      let content;
+      syn::bracketed!(content in input);
+      let name:Ident=content.parse()?;
+      let options=match options{
+         Some(o) if name=="ua"=>o,
+         _=>{
+            //other attributes are ignored
+            content.step(|cursor|{
+               let mut rest=*cursor;
+               while let Some((_,next))=rest.token_tree(){
+                  rest=next;
+               }
+               Ok(((),rest))
+            })?;
+            return Ok(name=="ua");
+         },
+      };
+      let arguments;
+      syn::parenthesized!(arguments in content);
+      while !arguments.is_empty(){
+         let name:Ident=arguments.parse()?;
+         match name.to_string().as_str(){
+            "mask"=>options.mask=true,
+            "depth_guard"=>options.depth_guard=true,
+            "mask_bit"=>{
+               arguments.parse::<syn::Token![=]>()?;
+               options.mask_bit=Some(arguments.parse()?);
+            },
+            "tag"=>{
+               arguments.parse::<syn::Token![=]>()?;
+               options.tag=Some(arguments.parse()?);
+            },
+            _=>return Err(syn::Error::new(name.span(),"unknown ua option")),
+         }
+         if !arguments.is_empty(){
+            arguments.parse::<Comma>()?;
+         }
+      }
+      Ok(true)
+   };
+   syn::parse::Parser::parse2(parser,attr.to_token_stream())
+}
+
+fn has_ua_options(ast:&DeriveInput)->bool{
+   let is_ua=|attrs:&[Attribute]| attrs.iter().any(|a| parse_ua_attribute(a,None).unwrap_or(false));
+   is_ua(&ast.attrs) || match &ast.data{
+      Data::Struct(s)=>s.fields.iter().any(|f| is_ua(&f.attrs)),
+      Data::Enum(e)=>e.variants.iter().any(|v| is_ua(&v.attrs)),
+      _=>false,
+   }
+}
+
+//T of a field of type Option<T>
+fn option_inner(ty:&Type)->Option<&Type>{
+   let segment=match ty{
+      Type::Path(p)=>p.path.segments.last()?,
+      _=>return None,
+   };
+   match &segment.arguments{
+      PathArguments::AngleBracketed(args) if segment.ident=="Option" && args.args.len()==1=>match args.args.first(){
+         Some(GenericArgument::Type(t))=>Some(t),
+         _=>None,
+      },
+      _=>None,
+   }
+}
+
+//field of a structure with an encoding mask
+enum MaskedField<'a>{
+   Plain(&'a Ident,&'a Type),
+   Mask(&'a Ident),
+   Bit(&'a Ident,&'a Type,Expr),
+}
+
+fn masked_fields(ast:&DeriveInput)->syn::Result<Vec<MaskedField<'_>>>{
+   let fields=match &ast.data{
+      Data::Struct(DataStruct{fields:Fields::Named(fields),..})=>&fields.named,
+      _=>return Err(syn::Error::new(ast.ident.span(),"struct need to have named fields")),
+   };
+   let mut result=Vec::new();
+   for field in fields{
+      let options=ua_options(&field.attrs)?;
+      let name=field.ident.as_ref().unwrap();
+      result.push(match (options.mask,options.mask_bit){
+         (true,None)=>MaskedField::Mask(name),
+         (false,Some(bit))=>match option_inner(&field.ty){
+            Some(ty)=>MaskedField::Bit(name,ty,bit),
+            None=>return Err(syn::Error::new(name.span(),"mask_bit fields need to be Option<T>")),
+         },
+         (false,None)=>MaskedField::Plain(name,&field.ty),
+         (true,Some(_))=>return Err(syn::Error::new(name.span(),"a field cannot be both the mask and a masked field")),
+      });
+   }
+   Ok(result)
+}
+
+//variants of an enumeration with tags: tag and type of the value (None for unit variants)
+fn tagged_variants(variants:&syn::punctuated::Punctuated<Variant,Comma>)->syn::Result<Vec<(&Ident,Expr,Option<&Type>)>>{
+   let mut result=Vec::new();
+   for variant in variants{
+      let tag=match ua_options(&variant.attrs)?.tag{
+         Some(t)=>t,
+         None=>return Err(syn::Error::new(variant.ident.span(),"all variants need a tag")),
+      };
+      let ty=match &variant.fields{
+         Fields::Unit=>None,
+         Fields::Unnamed(f) if f.unnamed.len()==1=>Some(&f.unnamed[0].ty),
+         _=>return Err(syn::Error::new(variant.ident.span(),"tagged variants are unit or have a single field")),
+      };
+      result.push((&variant.ident,tag,ty));
+   }
+   Ok(result)
+}
+
+fn impl_serialize_ua(ast:&DeriveInput)->syn::Result<TokenStream>{
+   let name=&ast.ident;
+   let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+   let body=match &ast.data{
+      Data::Enum(DataEnum{variants,..})=>{
+         let arms=tagged_variants(variants)?.into_iter().map(|(variant,tag,ty)| match ty{
+            Some(_)=>quote!{ #name::#variant(v)=>{ crate::BufMut::put_u8(buf,#tag); v.serialize_into(buf); } },
+            None=>quote!{ #name::#variant=>crate::BufMut::put_u8(buf,#tag), },
+         });
+         quote!{ match self { #(#arms)* } }
+      },
+      _=>{
+         let fields=masked_fields(ast)?;
+         let bits=fields.iter().filter_map(|f| match f{
+            MaskedField::Bit(field,_,bit)=>Some(quote!{ if self.#field.is_some() { mask|=#bit; } }),
+            _=>None,
+         });
+         let mut mask_written=false;
+         let mut writes=Vec::new();
+         for field in &fields{
+            match field{
+               MaskedField::Plain(field,_)=>writes.push(quote!{ self.#field.serialize_into(buf); }),
+               MaskedField::Mask(_)=>{},
+               MaskedField::Bit(field,_,_)=>{
+                  if !mask_written{
+                     writes.push(quote!{ crate::BufMut::put_u8(buf,mask); });
+                     mask_written=true;
+                  }
+                  writes.push(quote!{ if let Some(v)=&self.#field { v.serialize_into(buf); } });
+               },
+            }
+         }
+         quote!{
+            let mut mask:u8=0;
+            #(#bits)*
+            #(#writes)*
+         }
+      },
+   };
+   Ok(TokenStream::from(quote!{
+      impl #impl_generics Serialize for #name #ty_generics #where_clause {
+         fn serialize_into(&self,buf:&mut impl crate::BufMut){
+            #body
+         }
+      }
+   }))
+}
+
+fn impl_deserialize_ua(ast:&DeriveInput)->syn::Result<TokenStream>{
+   let name=&ast.ident;
+   let type_name=name.to_string();
+   let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+   let guard=if ua_options(&ast.attrs)?.depth_guard{
+      quote!{ let _depth=crate::uatypes::decode_limits::DepthGuard::enter()?; }
+   }else{
+      quote!{}
+   };
+   let body=match &ast.data{
+      Data::Enum(DataEnum{variants,..})=>{
+         let unknown=format!("unknown {} tag",type_name);
+         let arms=tagged_variants(variants)?.into_iter().map(|(variant,tag,ty)| match ty{
+            Some(ty)=>{
+               let label=variant.to_string();
+               quote!{
+                  if tag==#tag {
+                     let (data,v)=<#ty as Deserialize>::deserialize(data).map_err(|e| e.in_field(#type_name,#label,data.len()))?;
+                     return Ok((data,#name::#variant(v)));
+                  }
+               }
+            },
+            None=>quote!{ if tag==#tag { return Ok((data,#name::#variant)); } },
+         });
+         quote!{
+            let (data,tag)=u8::deserialize(data)?;
+            #(#arms)*
+            Err(crate::error::MapperError::new(crate::error::MapperErrorKind::ParsingError,#unknown))
+         }
+      },
+      _=>{
+         let fields=masked_fields(ast)?;
+         let mask_label=fields.iter().find_map(|f| match f{
+            MaskedField::Mask(field)=>Some(field.to_string()),
+            _=>None,
+         }).unwrap_or_else(|| String::from("encoding_mask"));
+         let mut mask_read=false;
+         let mut reads=Vec::new();
+         let mut values=Vec::new();
+         for field in &fields{
+            match field{
+               MaskedField::Plain(field,ty)=>{
+                  let label=field.to_string();
+                  reads.push(quote!{ let (data,#field)=<#ty as Deserialize>::deserialize(data).map_err(|e| e.in_field(#type_name,#label,data.len()))?; });
+                  values.push(quote!{ #field });
+               },
+               MaskedField::Mask(field)=>values.push(quote!{ #field:mask }),
+               MaskedField::Bit(field,ty,bit)=>{
+                  if !mask_read{
+                     reads.push(quote!{ let (data,mask)=u8::deserialize(data).map_err(|e| e.in_field(#type_name,#mask_label,data.len()))?; });
+                     mask_read=true;
+                  }
+                  let label=field.to_string();
+                  reads.push(quote!{
+                     let (data,#field)=if mask & (#bit) != 0 {
+                        let (data,v)=<#ty as Deserialize>::deserialize(data).map_err(|e| e.in_field(#type_name,#label,data.len()))?;
+                        (data,Some(v))
+                     } else {
+                        (data,None)
+                     };
+                  });
+                  values.push(quote!{ #field });
+               },
+            }
+         }
+         quote!{
+            #(#reads)*
+            Ok((data,#name{ #(#values,)* }))
+         }
+      },
+   };
+   Ok(TokenStream::from(quote!{
+      impl #impl_generics Deserialize for #name #ty_generics #where_clause {
+         fn deserialize(data:&[u8])-> MapperResult<(&[u8], Self)>{
+            #guard
+            #body
+         }
+      }
+   }))
+}
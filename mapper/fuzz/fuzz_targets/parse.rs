@@ -0,0 +1,8 @@
+//! Messages as received from a server: `cargo fuzz run parse`.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    mapper::fuzz::targets::parse(data);
+});
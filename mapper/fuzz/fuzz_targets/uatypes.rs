@@ -0,0 +1,8 @@
+//! Built-in types and structures of the encoding: `cargo fuzz run uatypes`.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    mapper::fuzz::targets::uatypes(data);
+});
@@ -0,0 +1,214 @@
+//! Abstraction of the responses into the outputs of the learner.
+//!
+//! The default [`Coarse`] abstraction gives the outputs of [`Handle::update_response`]: OK or NOK for each
+//! response (`CreSesResNOK,`). Finer levels tell the errors apart, with the severity of the status code
+//! ([`StatusClass`]: `CreSesResBad,`) or its name ([`StatusName`]: `CreSesResBadTooManySessions,`), and
+//! [`FieldPredicates`] adds the value of some fields of the responses to any level (`CreSesResOK[server_nonce|!endpoints],`).
+//! The abstraction of a run is given as text, see [`from_spec`]. The `trusted_certificate` predicate validates the server
+//! certificates with a [`TrustedList`], see [`from_spec_with_trust_list`].
+
+#[cfg(test)]
+mod tests;
+
+use std::sync::Arc;
+
+use crate::crypto::x509::TrustedList;
+use crate::handle::Handle;
+use crate::msg::Msg;
+use crate::result_prelude::*;
+use crate::uatypes::prelude::*;
+use crate::uatypes::status_code::Severity;
+
+/// Abstract output of a response. Outputs end with a comma and must not contain another one,
+/// the learner counts the messages of a symbol with the commas.
+pub(crate) trait OutputAbstraction: Send {
+    fn output(&self, msg: &Msg) -> String;
+}
+
+/// OK or NOK, the outputs of [`Handle::update_response`].
+pub(crate) struct Coarse;
+
+/// Severity of the status code: Good, Uncertain or Bad.
+pub(crate) struct StatusClass;
+
+/// Name of the status code, with its info bits.
+pub(crate) struct StatusName;
+
+/// Output of another abstraction followed by predicates on the fields of the response:
+/// the predicates that hold are listed, the others with a `!`.
+pub(crate) struct FieldPredicates {
+    pub(crate) inner: Box<dyn OutputAbstraction>,
+    pub(crate) predicates: Vec<String>,
+    ///trust list of the trusted_certificate predicate
+    pub(crate) trust_list: Option<Arc<TrustedList>>,
+}
+
+impl OutputAbstraction for Coarse {
+    fn output(&self, msg: &Msg) -> String {
+        Handle::update_response(msg)
+    }
+}
+
+impl OutputAbstraction for StatusClass {
+    fn output(&self, msg: &Msg) -> String {
+        with_status(msg, |status| match status.severity() {
+            Severity::Good => "Good".to_string(),
+            Severity::Uncertain => "Uncertain".to_string(),
+            Severity::Bad => "Bad".to_string(),
+        })
+    }
+}
+
+impl OutputAbstraction for StatusName {
+    fn output(&self, msg: &Msg) -> String {
+        with_status(msg, |status| status.to_string())
+    }
+}
+
+impl OutputAbstraction for FieldPredicates {
+    fn output(&self, msg: &Msg) -> String {
+        let output = self.inner.output(msg);
+        let values: Vec<String> = self
+            .predicates
+            .iter()
+            .filter_map(|name| match predicate(name, msg, self.trust_list.as_deref())? {
+                true => Some(name.clone()),
+                false => Some(format!("!{}", name)),
+            })
+            .collect();
+        if values.is_empty() {
+            return output;
+        }
+        format!("{}[{}],", output.trim_end_matches(','), values.join("|"))
+    }
+}
+
+//output of the responses with a status: the name of the response followed by the detail of its status.
+//The other messages have their coarse output.
+fn with_status(msg: &Msg, detail: impl Fn(StatusCode) -> String) -> String {
+    let name = match msg {
+        Msg::OpenSecureChannelResponse(_) => Handle::R_OPEN_REPOK,
+        Msg::GetEndpointsResponse(_) => Handle::R_GEP_REPOK,
+        Msg::CreateSessionResponse(_) => Handle::R_CREATE_SESS_REPOK,
+        Msg::CloseSessionResponse(_) => Handle::R_CLO_SESS_REPOK,
+        Msg::ActivateSessionResponse(_) => Handle::R_ACTIVE_SESS_REPOK,
+        Msg::ReadResponse(_) => Handle::R_READ_REPOK,
+        Msg::WriteResponse(_) => Handle::R_WRITE_REPOK,
+        Msg::ServiceFault(_) | Msg::ErrorMessage(_) => "",
+        _ => return Handle::update_response(msg),
+    };
+    let mut status = match msg.service_result() {
+        Some(status) => status,
+        None => return Handle::update_response(msg),
+    };
+    //a write rejected by the server is reported with the result of the write
+    if let Msg::WriteResponse(m) = msg {
+        if status.is_good() {
+            status = m.results.iter().map(|r| StatusCode::new(*r)).find(StatusCode::is_bad).unwrap_or(status);
+        }
+    }
+    match name {
+        //Service_fault_BadSessionIdInvalid,
+        "" => format!("{}_{},", Handle::update_response(msg).trim_end_matches(','), detail(status)),
+        _ => format!("{}{},", name.trim_end_matches("OK,"), detail(status)),
+    }
+}
+
+/// Names of the predicates of [`FieldPredicates`].
+pub(crate) struct Predicate;
+impl Predicate {
+    ///the server nonce of OpenSecureChannel, CreateSession and ActivateSession responses is not empty
+    pub const SERVER_NONCE: &'static str = "server_nonce";
+    ///the CreateSession response has a server certificate
+    pub const SERVER_CERTIFICATE: &'static str = "server_certificate";
+    ///the CreateSession response has a server signature
+    pub const SERVER_SIGNATURE: &'static str = "server_signature";
+    ///the GetEndpoints or CreateSession response has endpoints
+    pub const ENDPOINTS: &'static str = "endpoints";
+    ///the response header has a diagnostic info
+    pub const DIAGNOSTICS: &'static str = "diagnostics";
+    ///the response header has a string table
+    pub const STRING_TABLE: &'static str = "string_table";
+    ///the response header has an additional header
+    pub const ADDITIONAL_HEADER: &'static str = "additional_header";
+    ///the Error message has a reason
+    pub const REASON: &'static str = "reason";
+    ///the certificate of the CreateSession response, or each certificate of the GetEndpoints response, is accepted by the trust list
+    pub const TRUSTED_CERTIFICATE: &'static str = "trusted_certificate";
+
+    pub const ALL: [&'static str; 9] = [
+        Predicate::SERVER_NONCE,
+        Predicate::SERVER_CERTIFICATE,
+        Predicate::SERVER_SIGNATURE,
+        Predicate::ENDPOINTS,
+        Predicate::DIAGNOSTICS,
+        Predicate::STRING_TABLE,
+        Predicate::ADDITIONAL_HEADER,
+        Predicate::REASON,
+        Predicate::TRUSTED_CERTIFICATE,
+    ];
+}
+
+//value of the predicate for the message, None if it does not apply to this message
+fn predicate(name: &str, msg: &Msg, trust_list: Option<&TrustedList>) -> Option<bool> {
+    let not_empty = |b: &ByteString| b.value.as_ref().is_some_and(|v| !v.is_empty());
+    match (name, msg) {
+        (Predicate::SERVER_NONCE, Msg::OpenSecureChannelResponse(m)) => Some(not_empty(&m.server_nonce)),
+        (Predicate::SERVER_NONCE, Msg::CreateSessionResponse(m)) => Some(not_empty(&m.server_nonce)),
+        (Predicate::SERVER_NONCE, Msg::ActivateSessionResponse(m)) => Some(not_empty(&m.server_nonce)),
+        (Predicate::SERVER_CERTIFICATE, Msg::CreateSessionResponse(m)) => Some(not_empty(&m.server_certificate)),
+        (Predicate::SERVER_SIGNATURE, Msg::CreateSessionResponse(m)) => Some(not_empty(&m.server_signature.signature)),
+        (Predicate::ENDPOINTS, Msg::GetEndpointsResponse(m)) => Some(!m.endpoints.is_empty()),
+        (Predicate::ENDPOINTS, Msg::CreateSessionResponse(m)) => Some(!m.server_endpoints.is_empty()),
+        (Predicate::DIAGNOSTICS, _) => msg.response_header().map(|h| h.service_diagnostic != DiagnosticInfo::new()),
+        (Predicate::STRING_TABLE, _) => msg.response_header().map(|h| !h.string_table.is_empty()),
+        (Predicate::ADDITIONAL_HEADER, _) => msg.response_header().map(|h| h.additional_header.type_id != NodeId::empty()),
+        (Predicate::REASON, Msg::ErrorMessage(m)) => Some(m.reason.value.as_deref().is_some_and(|r| !r.is_empty())),
+        (Predicate::TRUSTED_CERTIFICATE, Msg::CreateSessionResponse(m)) if not_empty(&m.server_certificate) => {
+            let uri = m.server_endpoints.iter().next().and_then(|e| e.server.application_uri.value().as_deref());
+            Some(trusted(trust_list?, &m.server_certificate, uri))
+        }
+        (Predicate::TRUSTED_CERTIFICATE, Msg::GetEndpointsResponse(m)) => {
+            let mut certificates = m.endpoints.iter().filter(|e| not_empty(&e.server_certificate)).peekable();
+            certificates.peek()?;
+            let trust_list = trust_list?;
+            Some(certificates.all(|e| trusted(trust_list, &e.server_certificate, e.server.application_uri.value().as_deref())))
+        }
+        _ => None,
+    }
+}
+
+fn trusted(trust_list: &TrustedList, certificate: &ByteString, application_uri: Option<&str>) -> bool {
+    trust_list.validate(certificate.value.as_deref().unwrap_or_default(), application_uri).is_ok()
+}
+
+/// Abstraction from its text: `coarse` (default), `class` or `name`, optionally followed by predicates:
+/// `name:server_nonce,endpoints`.
+pub(crate) fn from_spec(spec: &str) -> MapperResult<Box<dyn OutputAbstraction>> {
+    from_spec_with_trust_list(spec, None)
+}
+
+/// Same as [`from_spec`], with the trust list required by the `trusted_certificate` predicate.
+pub(crate) fn from_spec_with_trust_list(spec: &str, trust_list: Option<Arc<TrustedList>>) -> MapperResult<Box<dyn OutputAbstraction>> {
+    let (level, predicates) = match spec.split_once(':') {
+        Some((level, predicates)) => (level.trim(), Some(predicates)),
+        None => (spec.trim(), None),
+    };
+    let level: Box<dyn OutputAbstraction> = match level {
+        "" | "coarse" => Box::new(Coarse),
+        "class" => Box::new(StatusClass),
+        "name" => Box::new(StatusName),
+        _ => return Err(MapperError::new(MapperErrorKind::UnexpectedValue, "unknown output abstraction level")),
+    };
+    let predicates: Vec<String> = match predicates {
+        Some(p) => p.split(',').map(str::trim).filter(|p| !p.is_empty()).map(String::from).collect(),
+        None => return Ok(level),
+    };
+    if let Some(unknown) = predicates.iter().find(|p| !Predicate::ALL.contains(&p.as_str())) {
+        return Err(MapperError::new(MapperErrorKind::UnexpectedValue, &format!("unknown predicate {}", unknown)));
+    }
+    if trust_list.is_none() && predicates.iter().any(|p| p == Predicate::TRUSTED_CERTIFICATE) {
+        return Err(MapperError::new(MapperErrorKind::MissingKey, "the trusted_certificate predicate needs a trust list"));
+    }
+    Ok(Box::new(FieldPredicates { inner: level, predicates, trust_list }))
+}
@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod tests {
+    use crate::abstraction::{self, Coarse, OutputAbstraction, StatusClass, StatusName};
+    use crate::msg::header::prelude::*;
+    use crate::msg::prelude::*;
+    use crate::msg::Msg;
+    use crate::uatypes::prelude::*;
+    use crate::uatypes::status_code::StatusCodeBits;
+
+    fn response_header(service_result: u32) -> ResponseHeader {
+        ResponseHeader {
+            timestamp: DateTime::new(),
+            request_handle: 0,
+            service_result: StatusCode::new(service_result),
+            service_diagnostic: DiagnosticInfo::new(),
+            string_table: UaArray::new(),
+            additional_header: ExtensionObject::null(),
+        }
+    }
+
+    fn get_endpoints(service_result: u32) -> Msg {
+        Msg::GetEndpointsResponse(GetEndpointsResponse {
+            message_header: MessageHeader::build(MessageType::MSG, b'F', 1),
+            security_header: SymmetricSecurityHeader { token_id: 1 },
+            sequence_header: SequenceHeader { sequence_number: 2, request_id: 2 },
+            node_id: NodeId::new_numeric(0, ObjectIds::GetEndpointsResponse_Encoding_DefaultBinary),
+            response_header: response_header(service_result),
+            endpoints: UaArray::new(),
+        })
+    }
+
+    fn service_fault(service_result: u32) -> Msg {
+        Msg::ServiceFault(ServiceFault {
+            message_header: MessageHeader::build(MessageType::MSG, b'F', 1),
+            security_header: SecurityHeader::Symmetric(SymmetricSecurityHeader { token_id: 1 }),
+            sequence_header: SequenceHeader { sequence_number: 2, request_id: 2 },
+            node_id: NodeId::new_numeric(0, ObjectIds::ServiceFault_Encoding_DefaultBinary),
+            response_header: response_header(service_result),
+        })
+    }
+
+    fn write(service_result: u32, results: Vec<u32>) -> Msg {
+        Msg::WriteResponse(WriteResponse {
+            message_header: MessageHeader::build(MessageType::MSG, b'F', 1),
+            security_header: SymmetricSecurityHeader { token_id: 1 },
+            sequence_header: SequenceHeader { sequence_number: 2, request_id: 2 },
+            node_id: NodeId::new_numeric(0, ObjectIds::WriteResponse_Encoding_DefaultBinary),
+            response_header: response_header(service_result),
+            results: UaArray::from(results),
+            diagnostic_infos: UaArray::new(),
+        })
+    }
+
+    #[test]
+    fn levels() {
+        let bad = get_endpoints(StatusCode::BadTooManySessions);
+        assert_eq!(Coarse.output(&bad), "GepResNOK,");
+        assert_eq!(StatusClass.output(&bad), "GepResBad,");
+        assert_eq!(StatusName.output(&bad), "GepResBadTooManySessions,");
+        let uncertain = get_endpoints(StatusCode::UncertainInitialValue);
+        assert_eq!(StatusClass.output(&uncertain), "GepResUncertain,");
+        assert_eq!(StatusName.output(&get_endpoints(StatusCode::Good)), "GepResGood,");
+        //only Good without info bits is OK, the severity of the other codes is told by the finer levels
+        let structure_changed = get_endpoints(StatusCode::Good | StatusCodeBits::STRUCTURE_CHANGED);
+        assert_eq!(Coarse.output(&structure_changed), "GepResNOK,");
+        assert_eq!(StatusClass.output(&structure_changed), "GepResGood,");
+        assert_eq!(StatusName.output(&structure_changed), "GepResGood|StructureChanged,");
+        assert_eq!(Coarse.output(&get_endpoints(StatusCode::GoodCompletesAsynchronously)), "GepResNOK,");
+        //a vendor code keeps its severity and is named in hexadecimal
+        let vendor = get_endpoints(0x80FF_0000);
+        assert_eq!(StatusClass.output(&vendor), "GepResBad,");
+        assert_eq!(StatusName.output(&vendor), "GepRes0x80FF0000,");
+        //faults and errors keep their output followed by the status
+        assert_eq!(StatusName.output(&service_fault(StatusCode::BadSessionIdInvalid)), "Service_fault_BadSessionIdInvalid,");
+        assert_eq!(StatusClass.output(&Msg::ErrorMessage(ErrorMessage::default())), "Err_Bad,");
+        //a rejected write gives the result of the write
+        assert_eq!(StatusName.output(&write(StatusCode::Good, vec![StatusCode::BadWriteNotSupported])), "WriteRepBadWriteNotSupported,");
+        assert_eq!(StatusName.output(&write(StatusCode::Good, vec![StatusCode::Good])), "WriteRepGood,");
+        //the outputs are split on the commas by the learner
+        assert_eq!(StatusName.output(&bad).matches(',').count(), 1);
+    }
+
+    #[test]
+    fn field_predicates() {
+        let output = abstraction::from_spec("class:endpoints,diagnostics,server_nonce").unwrap();
+        assert_eq!(output.output(&get_endpoints(StatusCode::Good)), "GepResGood[!endpoints|!diagnostics],");
+        let mut header = response_header(StatusCode::Good);
+        header.string_table = UaArray::from(vec![UaString::from("a")]);
+        let msg = Msg::ServiceFault(ServiceFault {
+            message_header: MessageHeader::build(MessageType::MSG, b'F', 1),
+            security_header: SecurityHeader::Symmetric(SymmetricSecurityHeader { token_id: 1 }),
+            sequence_header: SequenceHeader { sequence_number: 2, request_id: 2 },
+            node_id: NodeId::new_numeric(0, ObjectIds::ServiceFault_Encoding_DefaultBinary),
+            response_header: header,
+        });
+        assert_eq!(abstraction::from_spec("coarse:string_table").unwrap().output(&msg), "Service_fault[string_table],");
+        //no predicate applies to an Error message
+        assert_eq!(abstraction::from_spec("coarse:endpoints").unwrap().output(&Msg::ErrorMessage(ErrorMessage::default())), "Err,");
+    }
+
+    #[test]
+    fn spec() {
+        let bad = get_endpoints(StatusCode::BadTooManySessions);
+        assert_eq!(abstraction::from_spec("").unwrap().output(&bad), "GepResNOK,");
+        assert_eq!(abstraction::from_spec("name").unwrap().output(&bad), "GepResBadTooManySessions,");
+        assert_eq!(abstraction::from_spec("name:").unwrap().output(&bad), "GepResBadTooManySessions,");
+        assert!(abstraction::from_spec("fine").is_err());
+        assert!(abstraction::from_spec("name:endpoints,unknown").is_err());
+    }
+}
@@ -0,0 +1 @@
+mod abstraction_test;
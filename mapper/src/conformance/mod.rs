@@ -0,0 +1,158 @@
+//! Conformance oracle.
+//!
+//! The learner only sees the abstract outputs: a response with a wrong content gives the same output
+//! as a correct one. The [`Conformance`] checker of the [`Handle`](crate::handle::Handle) validates each
+//! decoded response of a word against rules of the specification (request handle echoed, timestamp,
+//! length of the nonces, revised timeouts, endpoints of CreateSession) and gives [`Finding`]s
+//! with the index of the symbol they answer. The outputs are not changed.
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::crypto::security_policy::SecurityPolicy;
+use crate::msg::Msg;
+use crate::uatypes::prelude::*;
+use crate::Serialize;
+
+/// Names of the rules checked on the responses.
+pub struct Rule;
+impl Rule {
+    ///the request handle of the response header is the one of the request
+    pub const REQUEST_HANDLE: &'static str = "request_handle";
+    ///the timestamp of the response header is close to the time of the mapper
+    pub const TIMESTAMP: &'static str = "timestamp";
+    ///the server nonce has the length of the security policy (OpenSecureChannel) or at least 32 bytes (sessions)
+    pub const SERVER_NONCE: &'static str = "server_nonce";
+    ///the revised session timeout is a positive duration
+    pub const SESSION_TIMEOUT: &'static str = "session_timeout";
+    ///the revised lifetime of the secure channel is not 0
+    pub const CHANNEL_LIFETIME: &'static str = "channel_lifetime";
+    ///the endpoints of CreateSession are the endpoints of GetEndpoints
+    pub const SERVER_ENDPOINTS: &'static str = "server_endpoints";
+}
+
+/// A response that breaks a rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    ///index of the symbol of the word answered by the response
+    pub(crate) index: usize,
+    pub(crate) rule: &'static str,
+    pub(crate) description: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.rule, self.description)
+    }
+}
+
+/// What the rules need to know of the request answered by a response.
+pub(crate) struct Sent {
+    request_handle: Option<u32>,
+    nonce_length: usize,
+}
+
+impl Sent {
+    pub(crate) fn new(msg: &Msg, security_policy: &SecurityPolicy) -> Sent {
+        Sent {
+            request_handle: msg.request_header().map(|h| h.request_handle),
+            nonce_length: security_policy.secure_channel_nonce_length,
+        }
+    }
+}
+
+/// Conformance checker. It keeps the endpoints given by GetEndpoints for each target to check the
+/// endpoints of CreateSession.
+pub struct Conformance {
+    ///maximal difference in seconds between the timestamp of a response and the time of the mapper
+    pub(crate) max_clock_skew: i64,
+    endpoints: HashMap<usize, Vec<Vec<u8>>>,
+}
+
+impl Default for Conformance {
+    fn default() -> Self {
+        Conformance::new(Conformance::DEFAULT_MAX_CLOCK_SKEW)
+    }
+}
+
+impl Conformance {
+    pub const DEFAULT_MAX_CLOCK_SKEW: i64 = 300;
+    ///minimal length of the server nonce of CreateSession and ActivateSession
+    pub const SESSION_NONCE_LENGTH: usize = 32;
+
+    pub fn new(max_clock_skew: i64) -> Conformance {
+        Conformance { max_clock_skew, endpoints: HashMap::new() }
+    }
+
+    ///keep the endpoints of the target to compare them with the endpoints of CreateSession
+    pub(crate) fn record_endpoints(&mut self, target: usize, endpoints: &[EndpointDescription]) {
+        let mut endpoints: Vec<Vec<u8>> = endpoints.iter().map(|e| e.serialize()).collect();
+        endpoints.sort();
+        self.endpoints.insert(target, endpoints);
+    }
+
+    ///findings of a response of the target to the symbol `index`. `sent` is None when the request is unknown
+    ///(mutated), the request handle is not checked then.
+    pub(crate) fn check(&mut self, target: usize, index: usize, sent: Option<&Sent>, msg: &Msg) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        let mut finding = |rule: &'static str, description: String| findings.push(Finding { index, rule, description });
+        if let Some(header) = msg.response_header() {
+            if let Some(expected) = sent.and_then(|s| s.request_handle) {
+                if header.request_handle != expected {
+                    finding(Rule::REQUEST_HANDLE, format!("{} instead of {}", header.request_handle, expected));
+                }
+            }
+            let skew = header.timestamp.get() - DateTime::new_now().get();
+            if skew.abs() > self.max_clock_skew {
+                finding(Rule::TIMESTAMP, format!("{}s from the time of the mapper", skew));
+            }
+        }
+        match msg {
+            Msg::OpenSecureChannelResponse(m) => {
+                let expected = sent.map_or(0, |s| s.nonce_length);
+                let length = nonce_length(&m.server_nonce);
+                if expected > 0 && length != expected {
+                    finding(Rule::SERVER_NONCE, format!("{} bytes instead of {}", length, expected));
+                }
+                if m.response_header.service_result.is_good() && m.security_token.revised_lifetime == 0 {
+                    finding(Rule::CHANNEL_LIFETIME, "revised lifetime of 0".to_string());
+                }
+            }
+            Msg::GetEndpointsResponse(m) => self.record_endpoints(target, &m.endpoints),
+            Msg::CreateSessionResponse(m) if m.response_header.service_result.is_good() => {
+                let length = nonce_length(&m.server_nonce);
+                if length < Conformance::SESSION_NONCE_LENGTH {
+                    finding(Rule::SERVER_NONCE, format!("{} bytes, at least {} expected", length, Conformance::SESSION_NONCE_LENGTH));
+                }
+                if !(m.revised_session_timeout.is_finite() && m.revised_session_timeout > 0.0) {
+                    finding(Rule::SESSION_TIMEOUT, format!("revised session timeout of {}", m.revised_session_timeout));
+                }
+                if let Some(expected) = self.endpoints.get(&target) {
+                    let mut endpoints: Vec<Vec<u8>> = m.server_endpoints.iter().map(|e| e.serialize()).collect();
+                    endpoints.sort();
+                    if &endpoints != expected {
+                        finding(
+                            Rule::SERVER_ENDPOINTS,
+                            format!("{} endpoints differ from the {} endpoints of GetEndpoints", m.server_endpoints.len(), expected.len()),
+                        );
+                    }
+                }
+            }
+            Msg::ActivateSessionResponse(m) if m.response_header.service_result.is_good() => {
+                let length = nonce_length(&m.server_nonce);
+                if length < Conformance::SESSION_NONCE_LENGTH {
+                    finding(Rule::SERVER_NONCE, format!("{} bytes, at least {} expected", length, Conformance::SESSION_NONCE_LENGTH));
+                }
+            }
+            _ => {}
+        }
+        findings
+    }
+}
+
+fn nonce_length(nonce: &ByteString) -> usize {
+    nonce.value.as_ref().map_or(0, |v| v.len())
+}
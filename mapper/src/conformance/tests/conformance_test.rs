@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use crate::conformance::{Conformance, Rule, Sent};
+    use crate::crypto::security_policy::{SecurityPolicy, SecurityPolicyUri};
+    use crate::msg::header::prelude::*;
+    use crate::msg::prelude::*;
+    use crate::msg::Msg;
+    use crate::uatypes::prelude::*;
+
+    fn response_header(request_handle: u32) -> ResponseHeader {
+        ResponseHeader {
+            timestamp: DateTime::new_now(),
+            request_handle,
+            service_result: StatusCode::new(StatusCode::Good),
+            service_diagnostic: DiagnosticInfo::new(),
+            string_table: UaArray::new(),
+            additional_header: ExtensionObject::null(),
+        }
+    }
+
+    fn endpoint(url: &str) -> EndpointDescription {
+        EndpointDescription {
+            endpoint_url: UaString::from(url),
+            server: ApplicationDescription {
+                application_uri: UaString::from("urn:server"),
+                product_uri: UaString::new(),
+                application_name: LocalizedText::new(UaString::from("en"), UaString::from("server")),
+                application_type: 0,
+                gateway_server_uri: UaString::new(),
+                discovery_policy_uri: UaString::new(),
+                discovery_urls: UaArray::new(),
+            },
+            server_certificate: ByteString::new(),
+            security_mode: 1,
+            security_policy_uri: UaString::from(SecurityPolicyUri::None),
+            user_identity_tokens: UaArray::empty(),
+            transport_profile_uri: UaString::new(),
+            security_level: 0,
+        }
+    }
+
+    fn get_endpoints(endpoints: Vec<EndpointDescription>) -> Msg {
+        Msg::GetEndpointsResponse(GetEndpointsResponse {
+            message_header: MessageHeader::build(MessageType::MSG, b'F', 1),
+            security_header: SymmetricSecurityHeader { token_id: 1 },
+            sequence_header: SequenceHeader { sequence_number: 2, request_id: 2 },
+            node_id: NodeId::new_numeric(0, ObjectIds::GetEndpointsResponse_Encoding_DefaultBinary),
+            response_header: response_header(1),
+            endpoints: UaArray::from(endpoints),
+        })
+    }
+
+    fn create_session(server_nonce: Vec<u8>, revised_session_timeout: f64, endpoints: Vec<EndpointDescription>) -> Msg {
+        Msg::CreateSessionResponse(CreateSessionResponse {
+            message_header: MessageHeader::build(MessageType::MSG, b'F', 1),
+            security_header: SymmetricSecurityHeader { token_id: 1 },
+            sequence_header: SequenceHeader { sequence_number: 3, request_id: 3 },
+            node_id: NodeId::new_numeric(0, ObjectIds::CreateSessionResponse_Encoding_DefaultBinary),
+            response_header: response_header(0),
+            session_id: NodeId::new_numeric(1, 1),
+            authentication_token: NodeId::new_numeric(1, 2),
+            revised_session_timeout,
+            server_nonce: ByteString::from(server_nonce),
+            server_certificate: ByteString::new(),
+            server_endpoints: UaArray::from(endpoints),
+            server_software_certificates: UaArray::empty(),
+            server_signature: SignatureData::new(),
+            max_request_message_size: 0,
+        })
+    }
+
+    fn rules(findings: &[crate::conformance::Finding]) -> Vec<&str> {
+        findings.iter().map(|f| f.rule).collect()
+    }
+
+    #[test]
+    fn request_handle_and_timestamp() {
+        let mut conformance = Conformance::default();
+        let request = Msg::GetEndpointsRequest(GetEndpointsRequest::build(1, 1));
+        let sent = Sent::new(&request, &SecurityPolicy::new(SecurityPolicyUri::None));
+        assert!(conformance.check(0, 0, Some(&sent), &get_endpoints(vec![])).is_empty());
+        let mut response = get_endpoints(vec![]);
+        if let Msg::GetEndpointsResponse(m) = &mut response {
+            m.response_header.request_handle = 7;
+            m.response_header.timestamp = DateTime::new();
+        }
+        let findings = conformance.check(0, 2, Some(&sent), &response);
+        assert_eq!(rules(&findings), vec![Rule::REQUEST_HANDLE, Rule::TIMESTAMP]);
+        assert_eq!(findings[0].index, 2);
+        assert_eq!(findings[0].to_string(), "request_handle: 7 instead of 1");
+        //the request handle of a mutated request is not known
+        assert_eq!(rules(&conformance.check(0, 2, None, &response)), vec![Rule::TIMESTAMP]);
+    }
+
+    #[test]
+    fn create_session_content() {
+        let mut conformance = Conformance::default();
+        assert!(conformance.check(0, 0, None, &create_session(vec![0; 32], 30000.0, vec![endpoint("a")])).is_empty());
+        conformance.check(0, 0, None, &get_endpoints(vec![endpoint("a"), endpoint("b")]));
+        //the order of the endpoints does not matter
+        assert!(conformance.check(0, 1, None, &create_session(vec![0; 32], 30000.0, vec![endpoint("b"), endpoint("a")])).is_empty());
+        let findings = conformance.check(0, 1, None, &create_session(vec![0; 16], 0.0, vec![endpoint("a")]));
+        assert_eq!(rules(&findings), vec![Rule::SERVER_NONCE, Rule::SESSION_TIMEOUT, Rule::SERVER_ENDPOINTS]);
+        assert_eq!(rules(&conformance.check(0, 1, None, &create_session(vec![0; 32], f64::NAN, vec![endpoint("a"), endpoint("b")]))), vec![Rule::SESSION_TIMEOUT]);
+        //the endpoints are kept for each target
+        assert!(conformance.check(1, 1, None, &create_session(vec![0; 32], 30000.0, vec![])).is_empty());
+    }
+}
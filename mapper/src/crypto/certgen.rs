@@ -0,0 +1,427 @@
+//! Generation of the client certificates and keys of the mapper.
+//!
+//! A [`CertificateAuthority`] issues application instance certificates and user certificates with the extensions of
+//! OPC UA Part 6 (6.2.2) and the revocation list of the certificates it revoked. [`Defect`]s give certificates a server
+//! must reject. [`ClientCertificates`] holds the keys and certificates of a [`Handle`](crate::handle::Handle): the
+//! certificates of the private key are valid, the ones of the false private key have the defects, and each defect has
+//! its own key and certificates for the `opn_cert_<defect>` and `activate_user_cert_<defect>` symbols. The [`Chain`]s
+//! of a certificate of the private key issued by an intermediate CA are sent by the `opn_chain_<chain>` and
+//! `create_session_chain_<chain>` symbols.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use openssl::asn1::Asn1Time;
+use openssl::bn::BigNum;
+use openssl::hash::MessageDigest;
+use openssl::pkey::{PKey, Private};
+use openssl::rsa::Rsa;
+use openssl::sign::Signer;
+use openssl::x509::extension::{AuthorityKeyIdentifier, BasicConstraints, ExtendedKeyUsage, KeyUsage, SubjectAlternativeName, SubjectKeyIdentifier};
+use openssl::x509::{X509Name, X509};
+
+use crate::result_prelude::*;
+
+/// Deliberate defect of a generated certificate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Defect {
+    ///valid until yesterday
+    Expired,
+    ///valid from tomorrow
+    NotYetValid,
+    ///the uri of the subjectAltName is not the application uri (application certificates only)
+    WrongApplicationUri,
+    ///no KeyUsage extension
+    MissingKeyUsage,
+    ///signed with SHA1
+    Sha1Signature,
+    ///RSA key of 1024 bits
+    WeakKey,
+    ///issued by a CA generated for the certificate, unknown to the servers
+    UntrustedCa,
+    ///revoked in the revocation list of the CA
+    Revoked,
+}
+
+impl Defect {
+    pub const ALL: [Defect; 8] = [
+        Defect::Expired,
+        Defect::NotYetValid,
+        Defect::WrongApplicationUri,
+        Defect::MissingKeyUsage,
+        Defect::Sha1Signature,
+        Defect::WeakKey,
+        Defect::UntrustedCa,
+        Defect::Revoked,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Defect::Expired => "expired",
+            Defect::NotYetValid => "not_yet_valid",
+            Defect::WrongApplicationUri => "wrong_uri",
+            Defect::MissingKeyUsage => "missing_key_usage",
+            Defect::Sha1Signature => "sha1_signature",
+            Defect::WeakKey => "weak_key",
+            Defect::UntrustedCa => "untrusted_chain",
+            Defect::Revoked => "revoked",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Defect> {
+        Defect::ALL.iter().find(|d| d.name() == name.trim()).copied()
+    }
+
+    ///defects from their names separated by commas: `expired,weak_key`
+    pub fn parse_list(text: &str) -> MapperResult<Vec<Defect>> {
+        text.split(',')
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .map(|n| Defect::from_name(n).ok_or_else(|| MapperError::new(MapperErrorKind::UnexpectedValue, &format!("unknown certificate defect {}", n))))
+            .collect()
+    }
+}
+
+/// Chain of certificates sent as the certificate of the client: concatenated DER certificates, leaf first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Chain {
+    ///leaf and intermediate CA
+    Complete,
+    ///leaf issued by the intermediate CA, without the intermediate CA
+    MissingIntermediate,
+    ///intermediate CA before the leaf
+    WrongOrder,
+    ///intermediate CA sent twice
+    Duplicate,
+    ///complete chain without the second half of the intermediate CA
+    Truncated,
+}
+
+impl Chain {
+    pub const ALL: [Chain; 5] = [Chain::Complete, Chain::MissingIntermediate, Chain::WrongOrder, Chain::Duplicate, Chain::Truncated];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Chain::Complete => "complete",
+            Chain::MissingIntermediate => "missing_intermediate",
+            Chain::WrongOrder => "wrong_order",
+            Chain::Duplicate => "duplicate",
+            Chain::Truncated => "truncated",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Chain> {
+        Chain::ALL.iter().find(|c| c.name() == name.trim()).copied()
+    }
+
+    ///chain of the DER `leaf` and `intermediate`
+    pub fn build(&self, leaf: &[u8], intermediate: &[u8]) -> Vec<u8> {
+        match self {
+            Chain::Complete => [leaf, intermediate].concat(),
+            Chain::MissingIntermediate => leaf.to_vec(),
+            Chain::WrongOrder => [intermediate, leaf].concat(),
+            Chain::Duplicate => [leaf, intermediate, intermediate].concat(),
+            Chain::Truncated => [leaf, &intermediate[..intermediate.len() / 2]].concat(),
+        }
+    }
+}
+
+/// Subject of a generated certificate.
+enum Subject<'a> {
+    Application { application_uri: &'a str, host: &'a str },
+    User { name: &'a str },
+}
+
+/// Certificate authority issuing the certificates of the mapper.
+pub struct CertificateAuthority {
+    pub(crate) key: PKey<Private>,
+    pub(crate) certificate: X509,
+    next_serial: u32,
+    revoked: Vec<u32>,
+}
+
+impl CertificateAuthority {
+    ///self-signed CA valid for ten years
+    pub fn new(common_name: &str) -> MapperResult<CertificateAuthority> {
+        CertificateAuthority::build(common_name, 1, None)
+    }
+
+    ///intermediate CA issued by the CA, valid for ten years
+    pub fn intermediate(&mut self, common_name: &str) -> MapperResult<CertificateAuthority> {
+        let serial_number = self.next_serial;
+        self.next_serial += 1;
+        CertificateAuthority::build(common_name, serial_number, Some(self))
+    }
+
+    fn build(common_name: &str, serial_number: u32, issuer: Option<&CertificateAuthority>) -> MapperResult<CertificateAuthority> {
+        let key = rsa_key(2048)?;
+        let mut builder = X509::builder().map_err(openssl_error)?;
+        let name = name(common_name)?;
+        builder.set_version(2).map_err(openssl_error)?;
+        let serial_number = serial(serial_number)?;
+        builder.set_serial_number(&serial_number).map_err(openssl_error)?;
+        builder.set_subject_name(&name).map_err(openssl_error)?;
+        builder.set_issuer_name(issuer.map_or(&*name, |i| i.certificate.subject_name())).map_err(openssl_error)?;
+        builder.set_pubkey(&key).map_err(openssl_error)?;
+        let (not_before, not_after) = (time(-1)?, time(3650)?);
+        builder.set_not_before(&not_before).map_err(openssl_error)?;
+        builder.set_not_after(&not_after).map_err(openssl_error)?;
+        let context = builder.x509v3_context(issuer.map(|i| &*i.certificate), None);
+        let mut extensions = vec![
+            BasicConstraints::new().critical().ca().build(),
+            KeyUsage::new().critical().key_cert_sign().crl_sign().build(),
+            SubjectKeyIdentifier::new().build(&context),
+        ];
+        if issuer.is_some() {
+            extensions.push(AuthorityKeyIdentifier::new().keyid(false).build(&context));
+        }
+        for extension in extensions {
+            builder.append_extension(extension.map_err(openssl_error)?).map_err(openssl_error)?;
+        }
+        builder.sign(issuer.map_or(&key, |i| &i.key), MessageDigest::sha256()).map_err(openssl_error)?;
+        Ok(CertificateAuthority { key, certificate: builder.build(), next_serial: 2, revoked: Vec::new() })
+    }
+
+    pub fn certificate_der(&self) -> MapperResult<Vec<u8>> {
+        self.certificate.to_der().map_err(openssl_error)
+    }
+
+    ///application instance certificate of `application_uri` on `host`, for `key` or a generated key
+    pub fn application_certificate(&mut self, application_uri: &str, host: &str, key: Option<&PKey<Private>>, defects: &[Defect]) -> MapperResult<(X509, PKey<Private>)> {
+        self.issue(Subject::Application { application_uri, host }, key, defects)
+    }
+
+    ///user certificate of `name`, for `key` or a generated key
+    pub fn user_certificate(&mut self, name: &str, key: Option<&PKey<Private>>, defects: &[Defect]) -> MapperResult<(X509, PKey<Private>)> {
+        self.issue(Subject::User { name }, key, defects)
+    }
+
+    ///DER revocation list of the revoked certificates, signed by the CA
+    pub fn crl(&self) -> MapperResult<Vec<u8>> {
+        //sha256WithRSAEncryption
+        let algorithm = der(0x30, &[der(0x06, &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b]), der(0x05, &[])].concat());
+        let now = utc_time(unix_now());
+        let entries: Vec<u8> = self.revoked.iter().flat_map(|s| der(0x30, &[integer(*s), now.clone()].concat())).collect();
+        let issuer = self.certificate.subject_name().to_der().map_err(openssl_error)?;
+        let next_update = utc_time(unix_now() + 30 * 86400);
+        let mut tbs = [integer(1), algorithm.clone(), issuer, now, next_update].concat();
+        if !entries.is_empty() {
+            tbs.extend(der(0x30, &entries));
+        }
+        let tbs = der(0x30, &tbs);
+        let mut signer = Signer::new(MessageDigest::sha256(), &self.key).map_err(openssl_error)?;
+        signer.update(&tbs).map_err(openssl_error)?;
+        let signature = [vec![0], signer.sign_to_vec().map_err(openssl_error)?].concat();
+        Ok(der(0x30, &[tbs, algorithm, der(0x03, &signature)].concat()))
+    }
+
+    fn issue(&mut self, subject: Subject, key: Option<&PKey<Private>>, defects: &[Defect]) -> MapperResult<(X509, PKey<Private>)> {
+        //the untrusted CA issues the certificate with the other defects
+        if defects.contains(&Defect::UntrustedCa) {
+            let defects: Vec<Defect> = defects.iter().filter(|d| **d != Defect::UntrustedCa).copied().collect();
+            return CertificateAuthority::new("untrusted CA")?.issue(subject, key, &defects);
+        }
+        let key = match (key, defects.contains(&Defect::WeakKey)) {
+            (None, weak) => rsa_key(if weak { 1024 } else { 2048 })?,
+            (Some(_), true) => return Err(MapperError::new(MapperErrorKind::UnexpectedValue, "a weak key is generated, no key must be given")),
+            (Some(key), false) => key.clone(),
+        };
+        let serial_number = self.next_serial;
+        self.next_serial += 1;
+        if defects.contains(&Defect::Revoked) {
+            self.revoked.push(serial_number);
+        }
+        let mut builder = X509::builder().map_err(openssl_error)?;
+        builder.set_version(2).map_err(openssl_error)?;
+        let serial_number = serial(serial_number)?;
+        builder.set_serial_number(&serial_number).map_err(openssl_error)?;
+        let common_name = match subject {
+            Subject::Application { host, .. } => format!("mapper@{}", host),
+            Subject::User { name } => name.to_string(),
+        };
+        let subject_name = name(&common_name)?;
+        builder.set_subject_name(&subject_name).map_err(openssl_error)?;
+        builder.set_issuer_name(self.certificate.subject_name()).map_err(openssl_error)?;
+        builder.set_pubkey(&key).map_err(openssl_error)?;
+        let (not_before, not_after) = if defects.contains(&Defect::Expired) {
+            (-365, -1)
+        } else if defects.contains(&Defect::NotYetValid) {
+            (1, 365)
+        } else {
+            (-1, 365)
+        };
+        let (not_before, not_after) = (time(not_before)?, time(not_after)?);
+        builder.set_not_before(&not_before).map_err(openssl_error)?;
+        builder.set_not_after(&not_after).map_err(openssl_error)?;
+        let mut extensions = vec![BasicConstraints::new().critical().build()];
+        if !defects.contains(&Defect::MissingKeyUsage) {
+            let mut key_usage = KeyUsage::new();
+            key_usage.critical().digital_signature().non_repudiation().key_encipherment();
+            if let Subject::Application { .. } = subject {
+                key_usage.data_encipherment();
+            }
+            extensions.push(key_usage.build());
+        }
+        let context = builder.x509v3_context(Some(&self.certificate), None);
+        extensions.push(SubjectKeyIdentifier::new().build(&context));
+        extensions.push(AuthorityKeyIdentifier::new().keyid(false).build(&context));
+        match subject {
+            Subject::Application { application_uri, host } => {
+                extensions.push(ExtendedKeyUsage::new().server_auth().client_auth().build());
+                let uri = if defects.contains(&Defect::WrongApplicationUri) { format!("{}:wrong", application_uri) } else { application_uri.to_string() };
+                extensions.push(SubjectAlternativeName::new().uri(&uri).dns(host).build(&context));
+            }
+            Subject::User { .. } => extensions.push(ExtendedKeyUsage::new().client_auth().build()),
+        }
+        for extension in extensions {
+            builder.append_extension(extension.map_err(openssl_error)?).map_err(openssl_error)?;
+        }
+        let digest = if defects.contains(&Defect::Sha1Signature) { MessageDigest::sha1() } else { MessageDigest::sha256() };
+        builder.sign(&self.key, digest).map_err(openssl_error)?;
+        Ok((builder.build(), key))
+    }
+}
+
+/// Keys and certificates of a [`Handle`](crate::handle::Handle), in DER. The private keys are PKCS#1 RSA keys.
+pub struct ClientCertificates {
+    pub(crate) private_key: Vec<u8>,
+    pub(crate) false_private_key: Vec<u8>,
+    pub(crate) certificate: Vec<u8>,
+    pub(crate) false_certificate: Vec<u8>,
+    pub(crate) user_certificate: Vec<u8>,
+    pub(crate) user_false_certificate: Vec<u8>,
+    ///key, application certificate and user certificate of each defect, empty when the certificates are read from files
+    pub(crate) defects: Vec<(Defect, DefectCertificates)>,
+    ///chains of a certificate of the private key, empty when the certificates are read from files
+    pub(crate) chains: Vec<(Chain, Vec<u8>)>,
+}
+
+/// Key of a defect with its application certificate and user certificate, in DER.
+pub struct DefectCertificates {
+    pub(crate) private_key: Vec<u8>,
+    pub(crate) certificate: Vec<u8>,
+    pub(crate) user_certificate: Vec<u8>,
+}
+
+impl ClientCertificates {
+    ///keys and certificates read from DER files, the certificates may be chains of concatenated certificates
+    pub fn read(private_key: &str, false_private_key: &str, certificate: &str, false_certificate: &str, user_certificate: &str, user_false_certificate: &str) -> MapperResult<ClientCertificates> {
+        let read = |path: &str| std::fs::read(path).map_err(|_| MapperError::new(MapperErrorKind::MissingKey, &format!("cannot read {}", path)));
+        Ok(ClientCertificates {
+            private_key: read(private_key)?,
+            false_private_key: read(false_private_key)?,
+            certificate: read(certificate)?,
+            false_certificate: read(false_certificate)?,
+            user_certificate: read(user_certificate)?,
+            user_false_certificate: read(user_false_certificate)?,
+            defects: Vec::new(),
+            chains: Vec::new(),
+        })
+    }
+
+    ///certificates issued by `ca` for `application_uri` on `host`. The false certificates, and their generated false key, have the defects.
+    ///The certificates of each defect are generated as well, and the chains of a certificate of the private key issued by `intermediate`.
+    pub fn generate(ca: &mut CertificateAuthority, intermediate: &mut CertificateAuthority, application_uri: &str, host: &str, defects: &[Defect]) -> MapperResult<ClientCertificates> {
+        let (certificate, key) = ca.application_certificate(application_uri, host, None, &[])?;
+        let (user_certificate, _) = ca.user_certificate("user", Some(&key), &[])?;
+        let (false_certificate, false_key) = ca.application_certificate(application_uri, host, None, defects)?;
+        let user_defects: Vec<Defect> = defects.iter().filter(|d| **d != Defect::WeakKey).copied().collect();
+        let (user_false_certificate, _) = ca.user_certificate("user", Some(&false_key), &user_defects)?;
+        let mut defect_certificates = Vec::new();
+        for defect in Defect::ALL {
+            let (certificate, key) = ca.application_certificate(application_uri, host, None, &[defect])?;
+            let user_defect: &[Defect] = if defect == Defect::WeakKey { &[] } else { &[defect] };
+            let (user_certificate, _) = ca.user_certificate("user", Some(&key), user_defect)?;
+            defect_certificates.push((
+                defect,
+                DefectCertificates {
+                    private_key: rsa_der(&key)?,
+                    certificate: certificate.to_der().map_err(openssl_error)?,
+                    user_certificate: user_certificate.to_der().map_err(openssl_error)?,
+                },
+            ));
+        }
+        let leaf = intermediate.application_certificate(application_uri, host, Some(&key), &[])?.0.to_der().map_err(openssl_error)?;
+        let intermediate = intermediate.certificate_der()?;
+        let chains = Chain::ALL.iter().map(|c| (*c, c.build(&leaf, &intermediate))).collect();
+        Ok(ClientCertificates {
+            private_key: rsa_der(&key)?,
+            false_private_key: rsa_der(&false_key)?,
+            certificate: certificate.to_der().map_err(openssl_error)?,
+            false_certificate: false_certificate.to_der().map_err(openssl_error)?,
+            user_certificate: user_certificate.to_der().map_err(openssl_error)?,
+            user_false_certificate: user_false_certificate.to_der().map_err(openssl_error)?,
+            defects: defect_certificates,
+            chains,
+        })
+    }
+}
+
+fn rsa_der(key: &PKey<Private>) -> MapperResult<Vec<u8>> {
+    key.rsa().and_then(|r| r.private_key_to_der()).map_err(openssl_error)
+}
+
+fn openssl_error(e: openssl::error::ErrorStack) -> MapperError {
+    MapperError::new(MapperErrorKind::CertificateError, &e.to_string())
+}
+
+fn rsa_key(bits: u32) -> MapperResult<PKey<Private>> {
+    Rsa::generate(bits).and_then(PKey::from_rsa).map_err(openssl_error)
+}
+
+fn name(common_name: &str) -> MapperResult<X509Name> {
+    let mut name = X509Name::builder().map_err(openssl_error)?;
+    name.append_entry_by_text("O", "mapper").map_err(openssl_error)?;
+    name.append_entry_by_text("CN", common_name).map_err(openssl_error)?;
+    Ok(name.build())
+}
+
+fn serial(serial: u32) -> MapperResult<openssl::asn1::Asn1Integer> {
+    BigNum::from_u32(serial).and_then(|s| s.to_asn1_integer()).map_err(openssl_error)
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64)
+}
+
+//time in `days` days, negative in the past
+fn time(days: i64) -> MapperResult<Asn1Time> {
+    Asn1Time::from_unix(unix_now() + days * 86400).map_err(openssl_error)
+}
+
+fn der(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut value = vec![tag];
+    if content.len() < 0x80 {
+        value.push(content.len() as u8);
+    } else {
+        let length: Vec<u8> = content.len().to_be_bytes().iter().skip_while(|b| **b == 0).copied().collect();
+        value.push(0x80 | length.len() as u8);
+        value.extend(length);
+    }
+    value.extend(content);
+    value
+}
+
+fn integer(value: u32) -> Vec<u8> {
+    let mut bytes: Vec<u8> = value.to_be_bytes().iter().skip_while(|b| **b == 0).copied().collect();
+    if bytes.first().is_none_or(|b| b & 0x80 != 0) {
+        bytes.insert(0, 0);
+    }
+    der(0x02, &bytes)
+}
+
+//UTCTime of a unix time: YYMMDDHHMMSSZ
+fn utc_time(unix: i64) -> Vec<u8> {
+    let (days, seconds) = (unix.div_euclid(86400), unix.rem_euclid(86400));
+    //civil date of a number of days since 1970-01-01
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    let text = format!("{:02}{:02}{:02}{:02}{:02}{:02}Z", year % 100, month, day, seconds / 3600, seconds % 3600 / 60, seconds % 60);
+    der(0x17, text.as_bytes())
+}
@@ -4,6 +4,7 @@ use openssl::rsa;
 use openssl::symm;
 
 use super::pkey::{PrivateKey, PublicKey};
+use crate::result_prelude::*;
 
 ///Enumeration of supported encryption algortihm (asymmetric or symmetric).
 #[derive(Copy, Clone)]
@@ -224,10 +225,19 @@ impl<'a> EncrypterDecrypter<'a> {
     }
 
     pub fn decrypt(&mut self, src: &[u8]) -> Vec<u8> {
+        self.try_decrypt(src).expect("asymmetric decryption failed")
+    }
+
+    ///decrypt without panicking on malformed cipher text (wrong size or padding)
+    pub fn try_decrypt(&mut self, src: &[u8]) -> MapperResult<Vec<u8>> {
         // decrypt data using our private key
 
         let decrypter = &self.decrypter;
-        let cipher_text_block_size = decrypter.decrypt_len(src).unwrap();
+        let error = || MapperError::new(MapperErrorKind::DecryptionError, "asymmetric decryption failed");
+        let cipher_text_block_size = decrypter.decrypt_len(src).map_err(|_| error())?;
+        if cipher_text_block_size == 0 || !src.len().is_multiple_of(cipher_text_block_size) {
+            return Err(error());
+        }
         let mut dst = vec![];
 
         // Decrypt the data
@@ -241,13 +251,13 @@ impl<'a> EncrypterDecrypter<'a> {
                 let src = &src[src_idx..(src_idx + cipher_text_block_size)];
                 let dst = &mut dst[dst_idx..(dst_idx + cipher_text_block_size)];
 
-                decrypter.decrypt(src, dst).unwrap()
+                decrypter.decrypt(src, dst).map_err(|_| error())?
             };
             src_idx += cipher_text_block_size;
         }
         let mut res = dst.to_vec();
         res.truncate(dst_idx);
-        res
+        Ok(res)
     }
 }
 
@@ -291,25 +301,29 @@ impl SymCipher {
     }
 
     pub fn decrypt(&self, data: &[u8]) -> Vec<u8> {
-        // let iv=self.iv.as_deref();
-        // symm::decrypt(self.symmetric_encryption_algorithm.get_cipher(),&self.key,iv,data).unwrap()
+        self.try_decrypt(data).expect("symmetric decryption failed")
+    }
+
+    ///decrypt without panicking on malformed cipher text (not a multiple of the block size)
+    pub fn try_decrypt(&self, data: &[u8]) -> MapperResult<Vec<u8>> {
+        let error = || MapperError::new(MapperErrorKind::DecryptionError, "symmetric decryption failed");
         let mut decrypter = openssl::symm::Crypter::new(
             self.symmetric_encryption_algorithm.get_cipher(),
             symm::Mode::Decrypt,
             &self.key,
             self.iv.as_deref(),
         )
-        .unwrap();
+        .map_err(|_| error())?;
         decrypter.pad(false);
         let block_size = self
             .symmetric_encryption_algorithm
             .get_cipher()
             .block_size();
         let mut plaintext = vec![0; data.len() + block_size];
-        let mut count = decrypter.update(&data, &mut plaintext).unwrap();
-        count += decrypter.finalize(&mut plaintext[count..]).unwrap();
+        let mut count = decrypter.update(data, &mut plaintext).map_err(|_| error())?;
+        count += decrypter.finalize(&mut plaintext[count..]).map_err(|_| error())?;
         plaintext.truncate(count);
-        plaintext
+        Ok(plaintext)
     }
 
     pub fn plain_text_block_size_key_size(&self) -> (usize, usize) {
@@ -2,6 +2,7 @@
 //! Nevertheless it works on vector and not on message. To use it on message
 //! please use the look into the msg part.
 
+pub mod certgen;
 pub mod encryption;
 pub mod hash;
 pub mod pkey;
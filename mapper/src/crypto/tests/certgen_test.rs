@@ -0,0 +1,179 @@
+#[cfg(test)]
+mod test {
+    use crate::crypto::certgen::{CertificateAuthority, Chain, ClientCertificates, Defect};
+    use crate::crypto::security_policy::{SecurityPolicy, SecurityPolicyUri};
+    use crate::crypto::x509::{split_chain, TrustedList};
+    use crate::handle::Handle;
+    use crate::msg::Msg;
+    use crate::scan::certificate::{CertificateReport, Validity};
+    use crate::uatypes::prelude::*;
+    use openssl::pkey::PKey;
+    use openssl::x509::X509;
+
+    const URI: &str = "urn:mapper:client";
+
+    fn trust_list(ca: &CertificateAuthority) -> TrustedList {
+        let mut list = TrustedList { trusted: vec![ca.certificate.clone()], ..TrustedList::new() };
+        list.add_crl(&ca.crl().unwrap()).unwrap();
+        list
+    }
+
+    fn status(ca: &CertificateAuthority, certificate: &X509) -> u32 {
+        trust_list(ca).validate(&certificate.to_der().unwrap(), Some(URI)).err().map_or(0, |e| e.status)
+    }
+
+    #[test]
+    fn application_certificate() {
+        let mut ca = CertificateAuthority::new("test CA").unwrap();
+        let (certificate, key) = ca.application_certificate(URI, "localhost", None, &[]).unwrap();
+        assert!(certificate.public_key().unwrap().public_eq(&key));
+        let chain = [certificate.to_der().unwrap(), ca.certificate_der().unwrap()].concat();
+        let report = CertificateReport::analyze(&chain, Some(URI)).unwrap();
+        assert_eq!(report.findings(), Vec::<String>::new());
+        assert_eq!(report.key_bits, 2048);
+        assert_eq!(report.dns_names, vec!["localhost".to_string()]);
+        assert_eq!(report.key_usage, Some(vec!["digitalSignature", "nonRepudiation", "keyEncipherment", "dataEncipherment"]));
+        assert_eq!(report.extended_key_usage, Some(vec!["serverAuth".to_string(), "clientAuth".to_string()]));
+        assert!(!report.ca && !report.self_signed);
+        assert_eq!(status(&ca, &certificate), 0);
+        //the user certificate of the same key
+        let (user, _) = ca.user_certificate("user", Some(&key), &[]).unwrap();
+        assert!(user.public_key().unwrap().public_eq(&key));
+        let report = CertificateReport::analyze(&user.to_der().unwrap(), None).unwrap();
+        assert_eq!(report.extended_key_usage, Some(vec!["clientAuth".to_string()]));
+        assert!(report.application_uris.is_empty());
+    }
+
+    #[test]
+    fn defects() {
+        let mut ca = CertificateAuthority::new("test CA").unwrap();
+        let mut certificate = |defect: Defect| ca.application_certificate(URI, "localhost", None, &[defect]).unwrap().0;
+        let (expired, not_yet_valid, wrong_uri) = (certificate(Defect::Expired), certificate(Defect::NotYetValid), certificate(Defect::WrongApplicationUri));
+        let (untrusted, revoked) = (certificate(Defect::UntrustedCa), certificate(Defect::Revoked));
+        let report = |c: &X509| CertificateReport::analyze(&c.to_der().unwrap(), Some(URI)).unwrap();
+        assert_eq!(report(&expired).validity, Validity::Expired);
+        assert_eq!(report(&not_yet_valid).validity, Validity::NotYetValid);
+        assert_eq!(report(&wrong_uri).application_uris, vec![format!("{}:wrong", URI)]);
+        assert_eq!(report(&certificate(Defect::MissingKeyUsage)).key_usage, None);
+        assert_eq!(report(&certificate(Defect::Sha1Signature)).signature_algorithm, "sha1WithRSAEncryption");
+        assert_eq!(report(&certificate(Defect::WeakKey)).key_bits, 1024);
+        assert_eq!(status(&ca, &expired), StatusCode::BadCertificateTimeInvalid);
+        assert_eq!(status(&ca, &not_yet_valid), StatusCode::BadCertificateTimeInvalid);
+        assert_eq!(status(&ca, &wrong_uri), StatusCode::BadCertificateUriInvalid);
+        assert_eq!(status(&ca, &untrusted), StatusCode::BadCertificateChainIncomplete);
+        assert_eq!(status(&ca, &revoked), StatusCode::BadCertificateRevoked);
+        let key = PKey::from_rsa(openssl::rsa::Rsa::generate(2048).unwrap()).unwrap();
+        assert!(ca.application_certificate(URI, "localhost", Some(&key), &[Defect::WeakKey]).is_err());
+    }
+
+    #[test]
+    fn defect_names() {
+        assert_eq!(Defect::parse_list(" expired, weak_key,").unwrap(), vec![Defect::Expired, Defect::WeakKey]);
+        assert_eq!(Defect::parse_list("").unwrap(), vec![]);
+        assert!(Defect::parse_list("expired,broken").is_err());
+        for defect in Defect::ALL {
+            assert_eq!(Defect::from_name(defect.name()), Some(defect));
+        }
+    }
+
+    #[test]
+    fn client_certificates() {
+        let mut ca = CertificateAuthority::new("test CA").unwrap();
+        let mut intermediate = ca.intermediate("test intermediate CA").unwrap();
+        let certificates = ClientCertificates::generate(&mut ca, &mut intermediate, URI, "localhost", &[Defect::Expired, Defect::WeakKey]).unwrap();
+        let key = |der: &[u8]| PKey::from_rsa(openssl::rsa::Rsa::private_key_from_der(der).unwrap()).unwrap();
+        let certificate = |der: &[u8]| X509::from_der(der).unwrap();
+        let (private_key, false_private_key) = (key(&certificates.private_key), key(&certificates.false_private_key));
+        assert!(certificate(&certificates.certificate).public_key().unwrap().public_eq(&private_key));
+        assert!(certificate(&certificates.user_certificate).public_key().unwrap().public_eq(&private_key));
+        assert!(certificate(&certificates.false_certificate).public_key().unwrap().public_eq(&false_private_key));
+        assert!(certificate(&certificates.user_false_certificate).public_key().unwrap().public_eq(&false_private_key));
+        assert_eq!(false_private_key.bits(), 1024);
+        assert_eq!(status(&ca, &certificate(&certificates.certificate)), 0);
+        assert_eq!(status(&ca, &certificate(&certificates.false_certificate)), StatusCode::BadCertificateTimeInvalid);
+        Handle::from_certificates(&certificates, 1);
+    }
+
+    #[test]
+    fn defect_symbols() {
+        let mut ca = CertificateAuthority::new("test CA").unwrap();
+        let mut intermediate = ca.intermediate("test intermediate CA").unwrap();
+        let certificates = ClientCertificates::generate(&mut ca, &mut intermediate, URI, "localhost", &[]).unwrap();
+        assert_eq!(certificates.defects.len(), Defect::ALL.len());
+        for (defect, c) in &certificates.defects {
+            let certificate = X509::from_der(&c.certificate).unwrap();
+            let key = PKey::from_rsa(openssl::rsa::Rsa::private_key_from_der(&c.private_key).unwrap()).unwrap();
+            assert!(certificate.public_key().unwrap().public_eq(&key));
+            assert!(X509::from_der(&c.user_certificate).unwrap().public_key().unwrap().public_eq(&key));
+            //the weaknesses not checked by the validation are reported by the analysis
+            let chain = [c.certificate.clone(), ca.certificate_der().unwrap()].concat();
+            let findings = CertificateReport::analyze(&chain, Some(URI)).unwrap().findings();
+            assert!(status(&ca, &certificate) != 0 || !findings.is_empty(), "{}", defect.name());
+        }
+        let mut handle = Handle::from_certificates(&certificates, 3);
+        let mut security_policy = SecurityPolicy::new(SecurityPolicyUri::Basic256Sha256);
+        let mut translate = |handle: &mut Handle, symbol: &str| {
+            handle.translate_from_abstract_to_object(&UaString::from("opc.tcp://localhost:4840"), symbol, &mut security_policy, &1000, &1000.0, &ByteString::new(), None, None)
+        };
+        //the symbols without request header have no additional parameters
+        assert!(handle.check_symbol("read_req[Count:6=3]").is_ok());
+        assert!(handle.check_symbol("hello[Count:6=3]").is_err());
+        assert!(translate(&mut handle, "hello[Count:6=3]").is_err());
+        //unknown symbols are rejected instead of panicking
+        for symbol in ["opn_cert_expird", "read_req[a=1", "write_req_status_sever", "unknown"] {
+            assert!(handle.check_symbol(symbol).is_err(), "{}", symbol);
+            assert!(translate(&mut handle, symbol).is_err(), "{}", symbol);
+        }
+        assert!(handle.check_symbol("write_req_status_server").is_ok());
+        assert!(handle.check_symbol("read_namespace_array").is_ok());
+        match translate(&mut handle, "opn_cert_expired") {
+            Ok(Some(Msg::OpenSecureChannelRequest(request))) => assert_eq!(request.security_header.sender_certificate, ByteString::from(certificates.defects[0].1.certificate.clone())),
+            _ => panic!("expected an OpenSecureChannelRequest"),
+        }
+        //without the generated certificates (handle built from files) the defect symbols cannot be sent
+        let certificates = ClientCertificates { defects: vec![], ..certificates };
+        let mut handle = Handle::from_certificates(&certificates, 3);
+        assert!(handle.check_symbol("opn_cert_expired").is_err());
+        assert!(handle.check_symbol("activate_user_cert_revoked").is_err());
+        assert!(translate(&mut handle, "opn_cert_expired").is_err());
+    }
+
+    #[test]
+    fn chains() {
+        let mut ca = CertificateAuthority::new("test CA").unwrap();
+        let mut intermediate = ca.intermediate("test intermediate CA").unwrap();
+        let certificates = ClientCertificates::generate(&mut ca, &mut intermediate, URI, "localhost", &[]).unwrap();
+        let mut list = trust_list(&ca);
+        list.add_crl(&intermediate.crl().unwrap()).unwrap();
+        let chain = |chain: Chain| certificates.chains.iter().find(|(c, _)| *c == chain).map(|(_, d)| d.clone()).unwrap();
+        let status = |chain: &[u8]| list.validate(chain, Some(URI)).err().map_or(0, |e| e.status);
+        let complete = split_chain(&chain(Chain::Complete)).unwrap().iter().map(|c| X509::from_der(c).unwrap()).collect::<Vec<X509>>();
+        assert_eq!(complete.len(), 2);
+        assert_eq!(complete[1].to_der().unwrap(), intermediate.certificate_der().unwrap());
+        assert!(complete[0].public_key().unwrap().public_eq(&PKey::from_rsa(openssl::rsa::Rsa::private_key_from_der(&certificates.private_key).unwrap()).unwrap()));
+        assert_eq!(status(&chain(Chain::Complete)), 0);
+        assert_eq!(status(&chain(Chain::MissingIntermediate)), StatusCode::BadCertificateChainIncomplete);
+        assert_eq!(status(&chain(Chain::Truncated)), StatusCode::BadCertificateInvalid);
+        assert_eq!(split_chain(&chain(Chain::Duplicate)).unwrap().len(), 3);
+        assert_eq!(split_chain(&chain(Chain::WrongOrder)).unwrap()[0], intermediate.certificate_der().unwrap().as_slice());
+        for chain in Chain::ALL {
+            assert_eq!(Chain::from_name(chain.name()), Some(chain));
+        }
+        let mut handle = Handle::from_certificates(&certificates, 3);
+        let mut security_policy = SecurityPolicy::new(SecurityPolicyUri::Basic256Sha256);
+        let endpoint_url = UaString::from("opc.tcp://localhost:4840");
+        match handle.translate_from_abstract_to_object(&endpoint_url, "opn_chain_wrong_order", &mut security_policy, &1000, &1000.0, &ByteString::new(), None, None) {
+            Ok(Some(Msg::OpenSecureChannelRequest(request))) => assert_eq!(request.security_header.sender_certificate, ByteString::from(chain(Chain::WrongOrder))),
+            _ => panic!("expected an OpenSecureChannelRequest"),
+        }
+        match handle.translate_from_abstract_to_object(&endpoint_url, "create_session_chain_truncated", &mut security_policy, &1000, &1000.0, &ByteString::new(), None, None) {
+            Ok(Some(Msg::CreateSessionRequest(request))) => assert_eq!(request.client_certificate, ByteString::from(chain(Chain::Truncated))),
+            _ => panic!("expected a CreateSessionRequest"),
+        }
+        //without the generated certificates (handle built from files) the chain symbols cannot be sent
+        let certificates = ClientCertificates { chains: vec![], ..certificates };
+        let mut handle = Handle::from_certificates(&certificates, 3);
+        assert!(handle.check_symbol("create_session_chain_complete").is_err());
+        assert!(handle.translate_from_abstract_to_object(&endpoint_url, "opn_chain_wrong_order", &mut security_policy, &1000, &1000.0, &ByteString::new(), None, None).is_err());
+    }
+}
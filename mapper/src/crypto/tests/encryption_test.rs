@@ -267,7 +267,7 @@ mod tests {
         let decrypted = client_decrypter.decrypt(&encrypted_client);
         plaintext_client.extend(&decrypted);
         let (_, opn_request) =
-            crate::msg::open_secure_channel::OpenSecureChannelRequest::deserialize(
+            crate::msg::services::OpenSecureChannelRequest::deserialize(
                 &plaintext_client,
             ).unwrap();
         let client_nonce = opn_request.client_nonce.value.unwrap();
@@ -283,7 +283,7 @@ mod tests {
         let decrypted = server_decrypter.decrypt(&encrypted_server);
         plaintext_server.extend(&decrypted);
         let (_, opn_response) =
-            crate::msg::open_secure_channel::OpenSecureChannelResponse::deserialize(
+            crate::msg::services::OpenSecureChannelResponse::deserialize(
                 &plaintext_server,
             ).unwrap();
         let server_nonce = opn_response.server_nonce.value.unwrap();
@@ -357,7 +357,7 @@ mod tests {
         let decrypted = client_decrypter.decrypt(&encrypted_client);
         plaintext_client.extend(&decrypted);
         let (_, opn_request) =
-            crate::msg::open_secure_channel::OpenSecureChannelRequest::deserialize(
+            crate::msg::services::OpenSecureChannelRequest::deserialize(
                 &plaintext_client,
             ).unwrap();
         let client_nonce = opn_request.client_nonce;
@@ -373,7 +373,7 @@ mod tests {
         let decrypted = server_decrypter.decrypt(&encrypted_server);
         plaintext_server.extend(&decrypted);
         let (_, opn_response) =
-            crate::msg::open_secure_channel::OpenSecureChannelResponse::deserialize(
+            crate::msg::services::OpenSecureChannelResponse::deserialize(
                 &plaintext_server,
             ).unwrap();
         let server_nonce = opn_response.server_nonce;
@@ -420,7 +420,7 @@ mod tests {
         let decrypted = client_decrypter.decrypt(&encrypted_client);
         plaintext_client.extend(&decrypted);
         let (_, opn_request) =
-            crate::msg::open_secure_channel::OpenSecureChannelRequest::deserialize(
+            crate::msg::services::OpenSecureChannelRequest::deserialize(
                 &plaintext_client,
             ).unwrap();
         let client_nonce = opn_request.client_nonce.value.unwrap();
@@ -436,7 +436,7 @@ mod tests {
         let decrypted = server_decrypter.decrypt(&encrypted_server);
         plaintext_server.extend(&decrypted);
         let (_, opn_response) =
-            crate::msg::open_secure_channel::OpenSecureChannelResponse::deserialize(
+            crate::msg::services::OpenSecureChannelResponse::deserialize(
                 &plaintext_server,
             ).unwrap();
         let mut server_nonce = opn_response.server_nonce.value.unwrap();
@@ -490,7 +490,7 @@ mod tests {
         let msg=crate::msg::parser::parse(&plaintext_client).unwrap();
         // msg.debug_print();
         let msg =match msg{
-            msg::Msg::ActiveSessionRequest(s)=>s,
+            msg::Msg::ActivateSessionRequest(s)=>s,
             _=>panic!("impossible"),
         };
         let signature=msg.client_signature.signature.clone().value.unwrap();
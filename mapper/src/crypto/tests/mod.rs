@@ -1,6 +1,8 @@
+mod certgen_test;
 mod derive_key_test;
 mod encryption_test;
 mod hash_test;
+mod x509_test;
 use std::num::ParseIntError;
 fn decode_hex(s: &str) -> Result<Vec<u8>, ParseIntError> {
     (0..s.len())
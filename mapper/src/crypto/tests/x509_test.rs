@@ -0,0 +1,215 @@
+#[cfg(test)]
+mod test {
+    use crate::abstraction;
+    use crate::crypto::x509::TrustedList;
+    use crate::msg::header::prelude::*;
+    use crate::msg::prelude::*;
+    use crate::msg::Msg;
+    use crate::uatypes::prelude::*;
+    use openssl::asn1::Asn1Time;
+    use openssl::bn::BigNum;
+    use openssl::hash::MessageDigest;
+    use openssl::pkey::{PKey, Private};
+    use openssl::rsa::Rsa;
+    use openssl::sign::Signer;
+    use openssl::x509::extension::{BasicConstraints, SubjectAlternativeName};
+    use openssl::x509::{X509Name, X509};
+    use std::fs;
+    use std::sync::Arc;
+
+    const CTT: &[u8] = include_bytes!("cert_server_ctt.der");
+    //sha256WithRSAEncryption
+    const SHA256_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b];
+
+    struct Pki {
+        ca_key: PKey<Private>,
+        ca: X509,
+        server: X509,
+    }
+
+    fn key() -> PKey<Private> {
+        PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap()
+    }
+
+    fn certificate(common_name: &str, serial: u32, key: &PKey<Private>, issuer: Option<(&X509, &PKey<Private>)>) -> X509 {
+        let mut name = X509Name::builder().unwrap();
+        name.append_entry_by_text("CN", common_name).unwrap();
+        let name = name.build();
+        let mut builder = X509::builder().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_serial_number(&BigNum::from_u32(serial).unwrap().to_asn1_integer().unwrap()).unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_pubkey(key).unwrap();
+        builder.set_not_before(&Asn1Time::days_from_now(0).unwrap()).unwrap();
+        builder.set_not_after(&Asn1Time::days_from_now(30).unwrap()).unwrap();
+        match issuer {
+            Some((ca, ca_key)) => {
+                let san = SubjectAlternativeName::new().uri("urn:server").dns("localhost").build(&builder.x509v3_context(Some(ca), None)).unwrap();
+                builder.append_extension(san).unwrap();
+                builder.set_issuer_name(ca.subject_name()).unwrap();
+                builder.sign(ca_key, MessageDigest::sha256()).unwrap();
+            }
+            None => {
+                builder.append_extension(BasicConstraints::new().critical().ca().build().unwrap()).unwrap();
+                builder.set_issuer_name(&name).unwrap();
+                builder.sign(key, MessageDigest::sha256()).unwrap();
+            }
+        }
+        builder.build()
+    }
+
+    fn pki() -> Pki {
+        let ca_key = key();
+        let ca = certificate("ca", 1, &ca_key, None);
+        let server = certificate("server", 2, &key(), Some((&ca, &ca_key)));
+        Pki { ca_key, ca, server }
+    }
+
+    fn der(tag: u8, content: &[u8]) -> Vec<u8> {
+        let mut value = vec![tag];
+        match content.len() {
+            l if l < 0x80 => value.push(l as u8),
+            l if l < 0x100 => value.extend([0x81, l as u8]),
+            l => value.extend([0x82, (l >> 8) as u8, l as u8]),
+        }
+        value.extend(content);
+        value
+    }
+
+    //revocation list of the CA revoking the serial numbers
+    fn crl(pki: &Pki, revoked: &[u8]) -> Vec<u8> {
+        let algorithm = der(0x30, &[der(0x06, SHA256_RSA), der(0x05, &[])].concat());
+        let time = der(0x17, b"220101000000Z");
+        let entries: Vec<u8> = revoked.iter().flat_map(|s| der(0x30, &[der(0x02, &[*s]), time.clone()].concat())).collect();
+        let mut tbs = [der(0x02, &[1]), algorithm.clone(), pki.ca.subject_name().to_der().unwrap(), time.clone()].concat();
+        if !entries.is_empty() {
+            tbs.extend(der(0x30, &entries));
+        }
+        let tbs = der(0x30, &tbs);
+        let mut signer = Signer::new(MessageDigest::sha256(), &pki.ca_key).unwrap();
+        signer.update(&tbs).unwrap();
+        let signature = [vec![0], signer.sign_to_vec().unwrap()].concat();
+        der(0x30, &[tbs, algorithm, der(0x03, &signature)].concat())
+    }
+
+    fn status(list: &TrustedList, certificate: &[u8], uri: Option<&str>) -> u32 {
+        list.validate(certificate, uri).err().map_or(0, |e| e.status)
+    }
+
+    #[test]
+    fn self_signed() {
+        let key = key();
+        let certificate = certificate("ca", 1, &key, None);
+        let der = certificate.to_der().unwrap();
+        let mut list = TrustedList::new();
+        assert_eq!(status(&list, &der, None), StatusCode::BadCertificateUntrusted);
+        list.trusted.push(certificate.clone());
+        assert_eq!(list.validate(&der, None), Ok(()));
+        //no uri in the subjectAltName
+        assert_eq!(status(&list, &der, Some("urn:server")), StatusCode::BadCertificateUriInvalid);
+        list.rejected.push(certificate);
+        assert_eq!(status(&list, &der, None), StatusCode::BadCertificateUntrusted);
+        assert_eq!(status(&list, &der[..100], None), StatusCode::BadCertificateInvalid);
+        let ctt = TrustedList { trusted: vec![X509::from_der(CTT).unwrap()], ..TrustedList::new() };
+        let error = ctt.validate(CTT, None).unwrap_err();
+        assert_eq!(error.status, StatusCode::BadCertificateTimeInvalid);
+        assert!(error.to_string().starts_with("BadCertificateTimeInvalid: "));
+    }
+
+    #[test]
+    fn chain() {
+        let pki = pki();
+        let server = pki.server.to_der().unwrap();
+        let mut list = TrustedList::new();
+        assert_eq!(status(&list, &server, None), StatusCode::BadCertificateChainIncomplete);
+        //the chain is sent but the CA is not trusted
+        let chain = [server.clone(), pki.ca.to_der().unwrap()].concat();
+        assert_eq!(status(&list, &chain, None), StatusCode::BadCertificateUntrusted);
+        list.trusted.push(pki.ca.clone());
+        assert_eq!(status(&list, &server, Some("urn:server")), StatusCode::BadCertificateRevocationUnknown);
+        list.add_crl(&crl(&pki, &[5])).unwrap();
+        assert_eq!(list.validate(&server, Some("urn:server")), Ok(()));
+        assert_eq!(list.validate(&chain, Some("urn:server")), Ok(()));
+        assert_eq!(status(&list, &server, Some("urn:client")), StatusCode::BadCertificateUriInvalid);
+        list.add_crl(&crl(&pki, &[2])).unwrap();
+        assert_eq!(status(&list, &server, None), StatusCode::BadCertificateRevoked);
+        //the CA is only an issuer, the server certificate is trusted
+        let mut list = TrustedList { trusted: vec![pki.server.clone()], issuers: vec![pki.ca.clone()], ..TrustedList::new() };
+        list.add_crl(&crl(&pki, &[])).unwrap();
+        assert_eq!(list.validate(&server, None), Ok(()));
+        //a list signed by another key is ignored
+        let other = Pki { ca_key: key(), ca: pki.ca.clone(), server: pki.server.clone() };
+        let mut list = TrustedList { trusted: vec![pki.ca.clone()], ..TrustedList::new() };
+        list.add_crl(&crl(&other, &[2])).unwrap();
+        assert_eq!(status(&list, &server, None), StatusCode::BadCertificateRevocationUnknown);
+    }
+
+    #[test]
+    fn load() {
+        let pki = pki();
+        let dir = std::env::temp_dir().join(format!("mapper_pki_{}", std::process::id()));
+        fs::create_dir_all(dir.join("trusted/certs")).unwrap();
+        fs::create_dir_all(dir.join("trusted/crl")).unwrap();
+        fs::create_dir_all(dir.join("rejected/certs")).unwrap();
+        fs::write(dir.join("trusted/certs/ca.pem"), pki.ca.to_pem().unwrap()).unwrap();
+        let pem = format!("-----BEGIN X509 CRL-----\n{}\n-----END X509 CRL-----\n", openssl::base64::encode_block(&crl(&pki, &[])));
+        fs::write(dir.join("trusted/crl/ca.crl"), pem).unwrap();
+        let list = TrustedList::load(&dir).unwrap();
+        assert_eq!((list.trusted.len(), list.issuers.len(), list.rejected.len(), list.crls.len()), (1, 0, 0, 1));
+        assert_eq!(list.validate(&pki.server.to_der().unwrap(), Some("urn:server")), Ok(()));
+        fs::write(dir.join("rejected/certs/server.der"), pki.server.to_der().unwrap()).unwrap();
+        let list = TrustedList::load(&dir).unwrap();
+        assert_eq!(status(&list, &pki.server.to_der().unwrap(), None), StatusCode::BadCertificateUntrusted);
+        fs::write(dir.join("trusted/crl/invalid.crl"), b"invalid").unwrap();
+        assert!(TrustedList::load(&dir).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(TrustedList::load(&dir).is_err());
+    }
+
+    #[test]
+    fn trusted_certificate_predicate() {
+        let pki = pki();
+        let endpoint = |certificate: &X509| EndpointDescription {
+            endpoint_url: UaString::from("opc.tcp://localhost:4840"),
+            server: ApplicationDescription {
+                application_uri: UaString::from("urn:server"),
+                product_uri: UaString::new(),
+                application_name: LocalizedText::new(UaString::from("en"), UaString::from("server")),
+                application_type: 0,
+                gateway_server_uri: UaString::new(),
+                discovery_policy_uri: UaString::new(),
+                discovery_urls: UaArray::new(),
+            },
+            server_certificate: ByteString::from(certificate.to_der().unwrap()),
+            security_mode: 1,
+            security_policy_uri: UaString::from("http://opcfoundation.org/UA/SecurityPolicy#None"),
+            user_identity_tokens: UaArray::new(),
+            transport_profile_uri: UaString::new(),
+            security_level: 0,
+        };
+        let get_endpoints = |endpoints: Vec<EndpointDescription>| {
+            Msg::GetEndpointsResponse(GetEndpointsResponse {
+                message_header: MessageHeader::build(MessageType::MSG, b'F', 1),
+                security_header: SymmetricSecurityHeader { token_id: 1 },
+                sequence_header: SequenceHeader { sequence_number: 2, request_id: 2 },
+                node_id: NodeId::new_numeric(0, ObjectIds::GetEndpointsResponse_Encoding_DefaultBinary),
+                response_header: ResponseHeader {
+                    timestamp: DateTime::new(),
+                    request_handle: 0,
+                    service_result: StatusCode::new(0),
+                    service_diagnostic: DiagnosticInfo::new(),
+                    string_table: UaArray::new(),
+                    additional_header: ExtensionObject::null(),
+                },
+                endpoints: UaArray::from(endpoints),
+            })
+        };
+        assert!(abstraction::from_spec("coarse:trusted_certificate").is_err());
+        let mut list = TrustedList { trusted: vec![pki.ca.clone()], ..TrustedList::new() };
+        list.add_crl(&crl(&pki, &[])).unwrap();
+        let output = abstraction::from_spec_with_trust_list("coarse:trusted_certificate", Some(Arc::new(list))).unwrap();
+        assert_eq!(output.output(&get_endpoints(vec![endpoint(&pki.server)])), "GepResOK[trusted_certificate],");
+        assert_eq!(output.output(&get_endpoints(vec![endpoint(&pki.server), endpoint(&pki.ca)])), "GepResOK[!trusted_certificate],");
+        assert_eq!(output.output(&get_endpoints(vec![])), "GepResOK,");
+    }
+}
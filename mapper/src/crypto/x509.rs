@@ -1,6 +1,332 @@
-use openssl::x509::X509;
-///not required for the mapper now
+//! Trust list of the server certificates.
+//!
+//! The [`TrustedList`] is loaded from a PKI directory with the layout of OPC UA applications:
+//! `trusted/certs`, `trusted/crl`, `issuers/certs`, `issuers/crl` and `rejected/certs`. Certificates and CRLs
+//! are DER or PEM files. [`TrustedList::validate`] follows the steps of the certificate validation of
+//! OPC UA Part 4 (6.1.3) and gives the status code a client would report.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use openssl::asn1::{Asn1Object, Asn1Time};
+use openssl::hash::MessageDigest;
+use openssl::sign::Verifier;
+use openssl::x509::{X509Ref, X509VerifyResult, X509};
+
+use crate::result_prelude::*;
+use crate::uatypes::status_code::StatusCode;
+
+/// Certificates and CRLs of a PKI directory.
 pub struct TrustedList {
+    ///trusted certificates, a chain is trusted when one of its certificates is in this list
     pub(crate) trusted: Vec<X509>,
-    pub(crate) ca: Option<X509>,
+    ///CA certificates used to build the chains, not trusted by themselves
+    pub(crate) issuers: Vec<X509>,
+    ///certificates always rejected
+    pub(crate) rejected: Vec<X509>,
+    pub(crate) crls: Vec<Crl>,
+}
+
+/// Revoked serial numbers of a certificate revocation list.
+pub(crate) struct Crl {
+    ///DER of the issuer name
+    issuer: Vec<u8>,
+    ///serial numbers without leading zeros
+    revoked: Vec<Vec<u8>>,
+    ///DER of the signed part
+    tbs: Vec<u8>,
+    signature_algorithm: Vec<u8>,
+    signature: Vec<u8>,
+}
+
+/// Reason why a certificate is not accepted, with the status code of OPC UA Part 4.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrustError {
+    pub(crate) status: u32,
+    pub(crate) description: String,
+}
+
+impl fmt::Display for TrustError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", StatusCode::new(self.status), self.description)
+    }
+}
+
+impl TrustedList {
+    pub fn new() -> TrustedList {
+        TrustedList { trusted: Vec::new(), issuers: Vec::new(), rejected: Vec::new(), crls: Vec::new() }
+    }
+
+    ///certificates and CRLs of a PKI directory, the missing subdirectories are empty
+    pub fn load(dir: &Path) -> MapperResult<TrustedList> {
+        if !dir.is_dir() {
+            return Err(MapperError::new(MapperErrorKind::MissingKey, &format!("no PKI directory {}", dir.display())));
+        }
+        let mut list = TrustedList::new();
+        list.trusted = load_files(&dir.join("trusted/certs"), certificates)?;
+        list.issuers = load_files(&dir.join("issuers/certs"), certificates)?;
+        list.rejected = load_files(&dir.join("rejected/certs"), certificates)?;
+        list.crls = load_files(&dir.join("trusted/crl"), crl)?;
+        list.crls.extend(load_files(&dir.join("issuers/crl"), crl)?);
+        Ok(list)
+    }
+
+    ///add a DER or PEM certificate revocation list
+    pub fn add_crl(&mut self, data: &[u8]) -> MapperResult<()> {
+        self.crls.extend(crl(data)?);
+        Ok(())
+    }
+
+    ///validation of a certificate (or a chain of DER certificates) sent by a server whose application uri is `application_uri`:
+    ///chain, trust, validity period, application uri and revocation, in this order
+    pub fn validate(&self, certificate: &[u8], application_uri: Option<&str>) -> Result<(), TrustError> {
+        let error = |status: u32, description: String| Err(TrustError { status, description });
+        let sent: Vec<X509> = match split_chain(certificate).map(|c| c.iter().map(|der| X509::from_der(der)).collect::<Result<Vec<X509>, _>>()) {
+            Some(Ok(sent)) if !sent.is_empty() => sent,
+            _ => return error(StatusCode::BadCertificateInvalid, "the certificate cannot be decoded".to_string()),
+        };
+        let chain = match self.build_chain(&sent) {
+            Ok(chain) => chain,
+            Err(issuer) => return error(StatusCode::BadCertificateChainIncomplete, format!("no certificate of the issuer {}", subject(issuer))),
+        };
+        if let Some(rejected) = chain.iter().find(|c| contains(&self.rejected, c)) {
+            return error(StatusCode::BadCertificateUntrusted, format!("{} is in the rejected list", subject(rejected)));
+        }
+        if !chain.iter().any(|c| contains(&self.trusted, c)) {
+            return error(StatusCode::BadCertificateUntrusted, format!("no certificate of the chain of {} is trusted", subject(chain[0])));
+        }
+        let now = Asn1Time::days_from_now(0).map_err(|_| TrustError { status: StatusCode::BadInternalError, description: "no current time".to_string() })?;
+        for (i, c) in chain.iter().enumerate() {
+            if c.not_before() > now || c.not_after() < now {
+                let status = if i == 0 { StatusCode::BadCertificateTimeInvalid } else { StatusCode::BadCertificateIssuerTimeInvalid };
+                return error(status, format!("{} is valid from {} to {}", subject(c), c.not_before(), c.not_after()));
+            }
+        }
+        if let Some(uri) = application_uri {
+            let uris: Vec<String> = chain[0].subject_alt_names().iter().flatten().filter_map(|n| n.uri().map(String::from)).collect();
+            if !uris.iter().any(|u| u == uri) {
+                return error(StatusCode::BadCertificateUriInvalid, format!("the subjectAltName has no uri {}", uri));
+            }
+        }
+        //the self-signed certificate at the end of the chain is not checked
+        for (i, pair) in chain.windows(2).enumerate() {
+            let (c, issuer) = (pair[0], pair[1]);
+            let crls: Vec<&Crl> = self.crls.iter().filter(|crl| crl.issued_by(issuer)).collect();
+            let (revoked, unknown) = if i == 0 {
+                (StatusCode::BadCertificateRevoked, StatusCode::BadCertificateRevocationUnknown)
+            } else {
+                (StatusCode::BadCertificateIssuerRevoked, StatusCode::BadCertificateIssuerRevocationUnknown)
+            };
+            if crls.is_empty() {
+                return error(unknown, format!("no revocation list of {}", subject(issuer)));
+            }
+            let serial = c.serial_number().to_bn().map(|s| s.to_vec()).unwrap_or_default();
+            if crls.iter().any(|crl| crl.revoked.contains(&serial)) {
+                return error(revoked, format!("{} is revoked by {}", subject(c), subject(issuer)));
+            }
+        }
+        Ok(())
+    }
+
+    //chain from the first certificate sent up to a self-signed certificate with the certificates sent, the issuers and the
+    //trusted certificates, or the certificate whose issuer is missing
+    fn build_chain<'a>(&'a self, sent: &'a [X509]) -> Result<Vec<&'a X509Ref>, &'a X509Ref> {
+        let mut chain: Vec<&X509Ref> = vec![&sent[0]];
+        let candidates: Vec<&X509Ref> = sent[1..].iter().chain(&self.issuers).chain(&self.trusted).map(|c| c.as_ref()).collect();
+        loop {
+            let current = chain[chain.len() - 1];
+            if current.issued(current) == X509VerifyResult::OK {
+                return Ok(chain);
+            }
+            let issuer = candidates.iter().find(|c| {
+                c.issued(current) == X509VerifyResult::OK && c.public_key().is_ok_and(|k| current.verify(&k).unwrap_or(false))
+            });
+            match issuer {
+                //a loop between issuers ends the chain
+                Some(issuer) if !chain.iter().any(|c| c.to_der().ok() == issuer.to_der().ok()) => chain.push(issuer),
+                Some(_) => return Ok(chain),
+                None if contains(&self.trusted, current) => return Ok(chain),
+                None => return Err(current),
+            }
+        }
+    }
+}
+
+impl Default for TrustedList {
+    fn default() -> Self {
+        TrustedList::new()
+    }
+}
+
+impl Crl {
+    fn parse(der: &[u8]) -> Option<Crl> {
+        let (_, list, _) = tlv(der)?;
+        let (_, tbs_content, rest) = tlv(list)?;
+        let tbs = &list[..list.len() - rest.len()];
+        let (_, algorithm, rest) = tlv(rest)?;
+        let (_, signature_algorithm, _) = tlv(algorithm)?;
+        let (_, signature, _) = tlv(rest)?;
+        let mut fields = tbs_content;
+        let mut next = || -> Option<(u8, &[u8], &[u8])> {
+            let (tag, content, rest) = tlv(fields)?;
+            let whole = &fields[..fields.len() - rest.len()];
+            fields = rest;
+            Some((tag, content, whole))
+        };
+        //optional version, then the signature algorithm
+        let (tag, _, _) = next()?;
+        if tag == 0x02 {
+            next()?;
+        }
+        let (_, _, issuer) = next()?;
+        let issuer = issuer.to_vec();
+        //thisUpdate, then the optional nextUpdate, revokedCertificates and crlExtensions
+        next()?;
+        let mut revoked = Vec::new();
+        while let Some((tag, content, _)) = next() {
+            if tag != 0x30 {
+                continue;
+            }
+            let mut entries = content;
+            while let Some((_, entry, rest)) = tlv(entries) {
+                if let Some((0x02, serial, _)) = tlv(entry) {
+                    revoked.push(serial.iter().skip_while(|b| **b == 0).copied().collect());
+                }
+                entries = rest;
+            }
+        }
+        Some(Crl { issuer, revoked, tbs: tbs.to_vec(), signature_algorithm: signature_algorithm.to_vec(), signature: signature.get(1..)?.to_vec() })
+    }
+
+    //the list is issued and signed by the certificate
+    fn issued_by(&self, issuer: &X509Ref) -> bool {
+        if issuer.subject_name().to_der().ok().as_deref() != Some(&self.issuer[..]) {
+            return false;
+        }
+        let digest = Asn1Object::from_str(&oid_text(&self.signature_algorithm))
+            .ok()
+            .and_then(|o| o.nid().signature_algorithms())
+            .and_then(|a| MessageDigest::from_nid(a.digest));
+        match (digest, issuer.public_key()) {
+            (Some(digest), Ok(key)) => Verifier::new(digest, &key)
+                .and_then(|mut v| {
+                    v.update(&self.tbs)?;
+                    v.verify(&self.signature)
+                })
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+}
+
+fn contains(list: &[X509], certificate: &X509Ref) -> bool {
+    let der = certificate.to_der().ok();
+    list.iter().any(|c| c.to_der().ok() == der)
+}
+
+fn subject(certificate: &X509Ref) -> String {
+    certificate
+        .subject_name()
+        .entries()
+        .map(|e| format!("{}={}", e.object().nid().short_name().unwrap_or("?"), e.data().as_utf8().map(|v| v.to_string()).unwrap_or_default()))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+//content of the files of a directory, nothing if it does not exist
+fn load_files<T>(dir: &Path, parse: fn(&[u8]) -> MapperResult<Vec<T>>) -> MapperResult<Vec<T>> {
+    let mut items = Vec::new();
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(items),
+    };
+    let mut paths: Vec<_> = entries.filter_map(|e| e.ok().map(|e| e.path())).filter(|p| p.is_file()).collect();
+    paths.sort();
+    for path in paths {
+        let data = fs::read(&path).map_err(|_| MapperError::new(MapperErrorKind::MissingKey, &format!("cannot read {}", path.display())))?;
+        let parsed = parse(&data).map_err(|_| MapperError::new(MapperErrorKind::ParsingError, &format!("invalid file {}", path.display())))?;
+        items.extend(parsed);
+    }
+    Ok(items)
+}
+
+fn certificates(data: &[u8]) -> MapperResult<Vec<X509>> {
+    let error = |_| MapperError::new(MapperErrorKind::ParsingError, "invalid certificate");
+    if data.starts_with(b"-----BEGIN") {
+        return X509::stack_from_pem(data).map_err(error);
+    }
+    X509::from_der(data).map(|c| vec![c]).map_err(error)
+}
+
+fn crl(data: &[u8]) -> MapperResult<Vec<Crl>> {
+    let error = || MapperError::new(MapperErrorKind::ParsingError, "invalid revocation list");
+    let ders = match std::str::from_utf8(data) {
+        Ok(text) if text.starts_with("-----BEGIN") => pem_blocks(text, "X509 CRL").ok_or_else(error)?,
+        _ => vec![data.to_vec()],
+    };
+    ders.iter().map(|der| Crl::parse(der).ok_or_else(error)).collect()
+}
+
+//DER of the PEM blocks with the label
+fn pem_blocks(text: &str, label: &str) -> Option<Vec<Vec<u8>>> {
+    let (begin, end) = (format!("-----BEGIN {}-----", label), format!("-----END {}-----", label));
+    let mut blocks = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(&begin) {
+        let block = &rest[start + begin.len()..];
+        let stop = block.find(&end)?;
+        let base64: String = block[..stop].split_whitespace().collect();
+        blocks.push(openssl::base64::decode_block(&base64).ok()?);
+        rest = &block[stop + end.len()..];
+    }
+    Some(blocks)
+}
+
+/// Tag, content and rest of a DER value.
+pub(crate) fn tlv(data: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, data) = data.split_first()?;
+    let (&first, data) = data.split_first()?;
+    let (length, data) = match first {
+        0..=0x7f => (first as usize, data),
+        0x81..=0x84 => {
+            let count = (first & 0x7f) as usize;
+            let bytes = data.get(..count)?;
+            (bytes.iter().fold(0usize, |l, b| (l << 8) | *b as usize), &data[count..])
+        }
+        _ => return None,
+    };
+    let content = data.get(..length)?;
+    Some((tag, content, &data[length..]))
+}
+
+/// DER certificates of a chain, the certificates are concatenated.
+pub(crate) fn split_chain(mut data: &[u8]) -> Option<Vec<&[u8]>> {
+    let mut certificates = Vec::new();
+    while !data.is_empty() {
+        let (_, _, rest) = tlv(data)?;
+        certificates.push(&data[..data.len() - rest.len()]);
+        data = rest;
+    }
+    Some(certificates)
+}
+
+/// Dotted text of a DER object identifier: 1.3.6.1.5.5.7.3.1
+pub(crate) fn oid_text(oid: &[u8]) -> String {
+    let mut arcs: Vec<u64> = Vec::new();
+    let mut value = 0u64;
+    for b in oid {
+        value = (value << 7) | (b & 0x7f) as u64;
+        if b & 0x80 == 0 {
+            if arcs.is_empty() {
+                let first = (value / 40).min(2);
+                arcs.push(first);
+                arcs.push(value - first * 40);
+            } else {
+                arcs.push(value);
+            }
+            value = 0;
+        }
+    }
+    arcs.iter().map(|a| a.to_string()).collect::<Vec<String>>().join(".")
 }
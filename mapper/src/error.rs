@@ -11,27 +11,106 @@ pub enum MapperErrorKind{
     SendError,
     VariantError,
     RawRsaError,
+    DecryptionError,
+    CertificateError,
+}
+
+//where a decoding error happened. The path is built from the innermost field outward
+//while the error goes up through the derived Deserialize implementations.
+#[derive(Clone)]
+struct Location{
+    //last structure that wrapped the error, the outermost one once decoded
+    type_name:&'static str,
+    //fields and array indexes below type_name, e.g. "endpoints[2].server"
+    path:String,
+    //length of the data from the start of the innermost field that failed
+    remaining:usize,
+    //offset in the message, known once the error reached the message parser
+    offset:Option<usize>,
 }
 
 #[derive(Clone)]
 pub  struct MapperError{
     error_kind:MapperErrorKind,
     display:String,
+    location:Option<Box<Location>>,
 }
 
 impl MapperError{
     pub (crate) fn new(error_kind:MapperErrorKind,message:&str)-> MapperError{
-        MapperError { error_kind, display: String::from(message) }
+        MapperError { error_kind, display: String::from(message), location: None }
     }
 
     pub (crate) fn to_abstract(&self)->String{
         return format!("{:?}",self.error_kind)
     }
 
+    ///The error happened while decoding `field` of `type_name`. `remaining` is the length of the data starting at the field.
+    pub (crate) fn in_field(mut self,type_name:&'static str,field:&str,remaining:usize)->MapperError{
+        match self.location.as_mut(){
+            Some(location)=>{
+                location.type_name=type_name;
+                location.path=if location.path.starts_with('[') {
+                    format!("{}{}",field,location.path)
+                } else {
+                    format!("{}.{}",field,location.path)
+                };
+            },
+            None=>self.location=Some(Box::new(Location { type_name, path: String::from(field), remaining, offset: None })),
+        }
+        self
+    }
+
+    ///The error happened while decoding the element `index` of an array. `remaining` is the length of the data starting at the element.
+    pub (crate) fn in_element(mut self,index:usize,remaining:usize)->MapperError{
+        match self.location.as_mut(){
+            Some(location)=>{
+                location.path=if location.path.starts_with('[') {
+                    format!("[{}]{}",index,location.path)
+                } else {
+                    format!("[{}].{}",index,location.path)
+                };
+            },
+            None=>self.location=Some(Box::new(Location { type_name: "", path: format!("[{}]",index), remaining, offset: None })),
+        }
+        self
+    }
+
+    ///Turn the remaining length into an offset in the message of length `message_len` that has been decoded.
+    pub (crate) fn locate(mut self,message_len:usize)->MapperError{
+        if let Some(location)=self.location.as_mut(){
+            location.offset=Some(message_len.saturating_sub(location.remaining));
+        }
+        self
+    }
+
+    ///Path of the field that could not be decoded, such as `CreateSessionResponse.endpoints[2].server.application_name`
+    pub fn path(&self)->Option<String>{
+        let location=self.location.as_ref()?;
+        if location.type_name.is_empty() || location.path.starts_with('[') {
+            Some(format!("{}{}",location.type_name,location.path))
+        } else {
+            Some(format!("{}.{}",location.type_name,location.path))
+        }
+    }
+
+    ///Offset in the message of the field that could not be decoded
+    pub fn offset(&self)->Option<usize>{
+        self.location.as_ref()?.offset
+    }
+
 }
 impl fmt::Debug for MapperError{
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    write!(f,"{:?} info:{}",self.error_kind,self.display)
+    write!(f,"{:?} info:",self.error_kind)?;
+    if let (Some(path),Some(location))=(self.path(),&self.location){
+        write!(f,"{}: ",path)?;
+        match location.offset{
+            Some(offset)=>return write!(f,"{} at {:#x}",self.display,offset),
+            None=>return write!(f,"{} with {} bytes left",self.display,location.remaining),
+        }
+    }
+    write!(f,"{}",self.display)
 }
 }
 
@@ -40,4 +119,3 @@ impl fmt::Display for MapperError {
         write!(f, "{:#?}", self)
     }
 }
-
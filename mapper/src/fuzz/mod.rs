@@ -0,0 +1,196 @@
+//! Grammar-aware fuzzing from a learned state.
+//!
+//! A word prefix is used to bring the server in a given state, then the target
+//! symbol is mutated field by field using the knowledge of the OPC UA encoding
+//! (boundary integers, null vs empty strings, oversized arrays, wrong NodeId
+//! encodings ...). Mutants go through the same signing and encryption as regular
+//! messages in [`Handle`] so they reach the application layer of the server.
+
+pub mod mutation;
+pub mod targets;
+#[cfg(test)]
+mod tests;
+
+use std::fmt;
+
+use crate::handle::{Handle, Word};
+use crate::liveness::Liveness;
+
+/// A mutant of an encoded structure.
+/// `path` is the field that has been mutated (for instance `request_header.authentication_token`)
+/// and `bytes` the full encoding of the structure with this field replaced.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mutation {
+    pub(crate) path: String,
+    pub(crate) description: String,
+    pub(crate) bytes: Vec<u8>,
+}
+
+impl Mutation {
+    pub(crate) fn new(description: &str, bytes: Vec<u8>) -> Mutation {
+        Mutation {
+            path: String::new(),
+            description: String::from(description),
+            bytes,
+        }
+    }
+}
+
+/// Structural mutation of a type. The mutations returned are already encoded.
+pub trait Mutate {
+    fn mutations(&self) -> Vec<Mutation>;
+}
+
+//join a field name with the path of the mutation of the field.
+pub(crate) fn join_path(field: &str, path: &str) -> String {
+    if path.is_empty() {
+        String::from(field)
+    } else if path.starts_with('[') {
+        format!("{}{}", field, path)
+    } else {
+        format!("{}.{}", field, path)
+    }
+}
+
+/// Build the mutations of a structure from its fields.
+/// Each element is (field name, regular encoding of the field, mutations of the field).
+/// It is used by the derive macro Mutate and by structures that implement the encoding by hand.
+pub(crate) fn combine(fields: Vec<(&str, Vec<u8>, Vec<Mutation>)>) -> Vec<Mutation> {
+    let mut result = Vec::new();
+    for (i, (name, _, mutations)) in fields.iter().enumerate() {
+        for mutation in mutations {
+            let mut bytes = Vec::new();
+            for (j, (_, encoded, _)) in fields.iter().enumerate() {
+                if i == j {
+                    bytes.extend_from_slice(&mutation.bytes);
+                } else {
+                    bytes.extend_from_slice(encoded);
+                }
+            }
+            result.push(Mutation {
+                path: join_path(name, &mutation.path),
+                description: mutation.description.clone(),
+                bytes,
+            });
+        }
+    }
+    result
+}
+
+/// Outcome of a mutant sent after the prefix.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FuzzOutcome {
+    ///abstract output of the server, as returned by submit_word
+    Response(String),
+    Err,
+    Eof,
+    NoResp,
+    ///the server does not accept new connection after the mutant
+    Crash,
+    ///the server does not answer a new connection after the mutant
+    Hang,
+}
+
+impl FuzzOutcome {
+    /// Classify the outputs of a word and the state of the server after it.
+    pub(crate) fn classify(outputs: &[String], liveness: Liveness) -> FuzzOutcome {
+        match liveness {
+            Liveness::Crash => return FuzzOutcome::Crash,
+            Liveness::Hang => return FuzzOutcome::Hang,
+            Liveness::Alive => {}
+        }
+        let output = match outputs.last() {
+            Some(o) => o,
+            None => return FuzzOutcome::NoResp,
+        };
+        //`Eof,` ends the outputs of a closed connection, `Eof` is given for the messages that could not be sent
+        if output.trim_end_matches(',').ends_with("Eof") {
+            return FuzzOutcome::Eof;
+        }
+        match output.as_str() {
+            Handle::R_ERR => FuzzOutcome::Err,
+            Handle::R_NO_RESP => FuzzOutcome::NoResp,
+            _ => FuzzOutcome::Response(output.clone()),
+        }
+    }
+}
+
+impl fmt::Display for FuzzOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FuzzOutcome::Response(s) => write!(f, "{}", s),
+            FuzzOutcome::Err => write!(f, "Err"),
+            FuzzOutcome::Eof => write!(f, "Eof"),
+            FuzzOutcome::NoResp => write!(f, "No resp"),
+            FuzzOutcome::Crash => write!(f, "Crash"),
+            FuzzOutcome::Hang => write!(f, "Hang"),
+        }
+    }
+}
+
+/// Result of one mutant
+#[derive(Debug, Clone)]
+pub struct FuzzResult {
+    pub index: usize,
+    pub path: String,
+    pub description: String,
+    pub outcome: FuzzOutcome,
+}
+
+//number of responses in an abstract output such as "OpnRepOK,"
+fn count_responses(output: &str) -> usize {
+    output.split(',').filter(|s| !s.is_empty()).count()
+}
+
+impl<'a> Handle<'a> {
+    /// Send the prefix then each mutant of the target symbol, one connection per mutant.
+    /// A first run without mutation is used to learn the number of responses of the prefix.
+    /// If the prefix does not reach the target (connection closed), nothing is fuzzed.
+    /// The liveness of the server is checked after each mutant, with the liveness check of the
+    /// handle if one is set, with a Hello/Ack otherwise.
+    pub fn fuzz(&mut self, socket_addr: String, prefix: Vec<&str>, target: &str, target_index: usize, timeout: u64) -> Vec<FuzzResult> {
+        let mut word = prefix.clone();
+        word.push(target);
+        let reference = self.submit_word(socket_addr.clone(), word.clone(), target_index, timeout, vec![], vec![]);
+        if reference[..prefix.len()].iter().any(|o| o.contains(Handle::R_EOF)) {
+            return vec![];
+        }
+        let mut nb_messages = Vec::with_capacity(prefix.len());
+        let mut known_no_resp = vec![];
+        for (i, output) in reference[..prefix.len()].iter().enumerate() {
+            if output == Handle::R_NO_RESP || output == Handle::R_INTERNAL_UPDATE {
+                known_no_resp.push(i);
+                nb_messages.push(0);
+            } else {
+                nb_messages.push(count_responses(output));
+            }
+        }
+        let liveness_set = self.liveness_check().is_some();
+        if !liveness_set {
+            self.set_liveness_check(false, timeout, None);
+        }
+        let word = Word { socket_addr, messages: word, target_index, timeout, nb_messages, known_no_resp };
+        let mut results = vec![];
+        let mut index = 0;
+        loop {
+            let (outputs, mutation) = self.submit_word_mutated(word.clone(), Some(index));
+            let mutation = match mutation {
+                Some(m) => m,
+                None => break,
+            };
+            let liveness = self.liveness().map(|(_, state)| state).unwrap_or(Liveness::Alive);
+            let outcome = FuzzOutcome::classify(&outputs, liveness);
+            results.push(FuzzResult {
+                index,
+                path: mutation.path,
+                description: mutation.description,
+                outcome,
+            });
+            index += 1;
+        }
+        if !liveness_set {
+            self.disable_liveness_check();
+        }
+        results
+    }
+}
@@ -0,0 +1,391 @@
+//! Mutations of the OPC UA built-in types and of the structures that are encoded by hand.
+//! Structures encoded field by field derive `Mutate` instead.
+
+use super::{combine, join_path, Mutate, Mutation};
+use crate::msg::header::prelude::*;
+use crate::uatypes::data_value;
+use crate::uatypes::localized_text;
+use crate::uatypes::node_id::EncodingValue;
+use crate::uatypes::prelude::*;
+use crate::Serialize;
+use std::fmt;
+
+///size used for oversized strings and byte strings. It stays below the usual buffer size
+///(65536) to have the message accepted by the transport layer.
+pub const OVERSIZED_LENGTH: usize = 16384;
+///number of elements of oversized arrays
+pub const OVERSIZED_ARRAY_LENGTH: usize = 1024;
+
+//mutations replacing the value by boundaries (except the current value)
+fn boundaries<T: Serialize + PartialEq + fmt::Display>(value: &T, candidates: &[T]) -> Vec<Mutation> {
+    let mut result: Vec<Mutation> = Vec::new();
+    for candidate in candidates {
+        if candidate == value {
+            continue;
+        }
+        let bytes = candidate.serialize();
+        if result.iter().any(|m| m.bytes == bytes) {
+            continue;
+        }
+        result.push(Mutation::new(&format!("boundary {}", candidate), bytes));
+    }
+    result
+}
+
+macro_rules! impl_mutate_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Mutate for $t {
+                fn mutations(&self) -> Vec<Mutation> {
+                    boundaries(self, &[<$t>::MIN, <$t>::MAX, 0, 1, <$t>::MIN + 1, <$t>::MAX - 1, <$t>::MAX / 2 + 1])
+                }
+            }
+        )*
+    };
+}
+impl_mutate_integer!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+macro_rules! impl_mutate_float {
+    ($($t:ty),*) => {
+        $(
+            impl Mutate for $t {
+                fn mutations(&self) -> Vec<Mutation> {
+                    let mut result = boundaries(self, &[0.0, -0.0, <$t>::MIN, <$t>::MAX, <$t>::EPSILON, <$t>::INFINITY, <$t>::NEG_INFINITY]);
+                    result.push(Mutation::new("NaN", <$t>::NAN.serialize()));
+                    result
+                }
+            }
+        )*
+    };
+}
+impl_mutate_float!(f32, f64);
+
+impl Mutate for bool {
+    fn mutations(&self) -> Vec<Mutation> {
+        vec![
+            Mutation::new("boolean not canonical", vec![0x02]),
+            Mutation::new("boolean not canonical", vec![0xff]),
+            Mutation::new("boolean negated", (!*self).serialize()),
+        ]
+    }
+}
+
+//mutations common to String and ByteString since both are encoded as length + bytes
+fn length_prefixed_mutations(value: Option<&[u8]>) -> Vec<Mutation> {
+    let mut result = Vec::new();
+    if value.is_some() {
+        result.push(Mutation::new("null", (-1_i32).serialize()));
+    }
+    if value.is_none_or(|v| !v.is_empty()) {
+        result.push(Mutation::new("empty", 0_i32.serialize()));
+    }
+    let mut bytes = (-2_i32).serialize();
+    bytes.extend_from_slice(value.unwrap_or(&[]));
+    result.push(Mutation::new("negative length", bytes));
+
+    let content = value.unwrap_or(&[]);
+    let mut bytes = (i32::MAX).serialize();
+    bytes.extend_from_slice(content);
+    result.push(Mutation::new("length larger than content", bytes));
+
+    let mut bytes = (OVERSIZED_LENGTH as i32).serialize();
+    bytes.extend(vec![b'A'; OVERSIZED_LENGTH]);
+    result.push(Mutation::new("oversized", bytes));
+    result
+}
+
+impl Mutate for UaString {
+    fn mutations(&self) -> Vec<Mutation> {
+        let mut result = length_prefixed_mutations(self.value.as_ref().map(|s| s.as_bytes()));
+        let mut bytes = 2_i32.serialize();
+        bytes.extend_from_slice(&[0xc3, 0x28]);
+        result.push(Mutation::new("invalid utf8", bytes));
+        result
+    }
+}
+
+impl Mutate for ByteString {
+    fn mutations(&self) -> Vec<Mutation> {
+        length_prefixed_mutations(self.value.as_deref())
+    }
+}
+
+//mutations of an array, `values` is None for a null array
+fn array_mutations<T: Mutate + Serialize>(values: Option<&[T]>) -> Vec<Mutation> {
+    let array = values.unwrap_or(&[]);
+    let elements: Vec<u8> = array.iter().flat_map(|el| el.serialize()).collect();
+    let mut result = Vec::new();
+    if values.is_some() {
+        result.push(Mutation::new("null array", (-1_i32).serialize()));
+    }
+    if values.is_none_or(|v| !v.is_empty()) {
+        result.push(Mutation::new("empty array", 0_i32.serialize()));
+    }
+    let mut bytes = (-2_i32).serialize();
+    bytes.extend_from_slice(&elements);
+    result.push(Mutation::new("negative array length", bytes));
+
+    let mut bytes = (i32::MAX).serialize();
+    bytes.extend_from_slice(&elements);
+    result.push(Mutation::new("array length larger than content", bytes));
+
+    if let Some(first) = array.first() {
+        let first = first.serialize();
+        let mut bytes = (OVERSIZED_ARRAY_LENGTH as i32).serialize();
+        for _ in 0..OVERSIZED_ARRAY_LENGTH {
+            bytes.extend_from_slice(&first);
+        }
+        result.push(Mutation::new("oversized array", bytes));
+    }
+    //mutation of each element
+    let length = (array.len() as i32).serialize();
+    let encoded: Vec<Vec<u8>> = array.iter().map(|el| el.serialize()).collect();
+    for (i, el) in array.iter().enumerate() {
+        for mutation in el.mutations() {
+            let mut bytes = length.clone();
+            for (j, e) in encoded.iter().enumerate() {
+                if i == j {
+                    bytes.extend_from_slice(&mutation.bytes);
+                } else {
+                    bytes.extend_from_slice(e);
+                }
+            }
+            result.push(Mutation {
+                path: join_path(&format!("[{}]", i), &mutation.path),
+                description: mutation.description,
+                bytes,
+            });
+        }
+    }
+    result
+}
+
+impl<T: Mutate + Serialize> Mutate for Vec<T> {
+    fn mutations(&self) -> Vec<Mutation> {
+        array_mutations(Some(self))
+    }
+}
+
+impl<T: Mutate + Serialize> Mutate for UaArray<T> {
+    fn mutations(&self) -> Vec<Mutation> {
+        array_mutations(self.values().as_deref())
+    }
+}
+
+impl Mutate for NodeId {
+    fn mutations(&self) -> Vec<Mutation> {
+        let mut result = Vec::new();
+        let encoded = self.serialize();
+        //same identifier with another (valid but unusual) encoding
+        if let Identifier::Numeric(id) = self.identifier {
+            if encoded[0] != EncodingValue::NUMERIC {
+                let mut bytes = vec![EncodingValue::NUMERIC];
+                bytes.extend_from_slice(&self.namespace.serialize());
+                bytes.extend_from_slice(&id.serialize());
+                result.push(Mutation::new("numeric encoding instead of compact one", bytes));
+            }
+            let mut bytes = vec![EncodingValue::STRING];
+            bytes.extend_from_slice(&self.namespace.serialize());
+            bytes.extend_from_slice(&UaString::from(&id.to_string()).serialize());
+            result.push(Mutation::new("string identifier with numeric value", bytes));
+        }
+        let mut bytes = encoded.clone();
+        bytes[0] |= EncodingValue::NAMSPACE_URI | EncodingValue::SERVER_INDEX;
+        result.push(Mutation::new("expanded node id flags", bytes));
+        result.push(Mutation::new("invalid encoding", vec![0x06]));
+        result.push(Mutation::new("truncated", vec![encoded[0]]));
+        let mut bytes = vec![EncodingValue::NUMERIC];
+        bytes.extend_from_slice(&u16::MAX.serialize());
+        bytes.extend_from_slice(&0_u32.serialize());
+        result.push(Mutation::new("unknown namespace", bytes));
+        let mut bytes = vec![EncodingValue::STRING];
+        bytes.extend_from_slice(&self.namespace.serialize());
+        bytes.extend_from_slice(&UaString::new().serialize());
+        result.push(Mutation::new("null string identifier", bytes));
+        let mut bytes = vec![EncodingValue::BYTE_STRING];
+        bytes.extend_from_slice(&self.namespace.serialize());
+        bytes.extend_from_slice(&ByteString::from(vec![]).serialize());
+        result.push(Mutation::new("empty byte string identifier", bytes));
+        let mut bytes = vec![EncodingValue::GUID];
+        bytes.extend_from_slice(&self.namespace.serialize());
+        bytes.extend_from_slice(&[0; 16]);
+        result.push(Mutation::new("null guid identifier", bytes));
+        result
+    }
+}
+
+impl Mutate for ExtensionObject {
+    fn mutations(&self) -> Vec<Mutation> {
+        let mut body = self.serialize();
+        body.drain(..self.type_id.serialize().len());
+        let bogus_type_ids = [
+            ("bogus type id", NodeId::new_numeric(0, 0)),
+            ("bogus type id", NodeId::new_numeric(0, u32::MAX)),
+            ("bogus type id", NodeId::new_string(0, UaString::from("bogus"))),
+            ("bogus type id", NodeId::new_numeric(u16::MAX, 324)),
+        ];
+        let mut result: Vec<Mutation> = bogus_type_ids
+            .iter()
+            .map(|(description, type_id)| {
+                let mut bytes = type_id.serialize();
+                bytes.extend_from_slice(&body);
+                Mutation::new(description, bytes)
+            })
+            .collect();
+        let type_id = self.type_id.serialize();
+        for (description, encoding) in [("invalid body encoding", 0x03), ("xml body encoding", 0x02), ("no body encoding", 0x00)] {
+            if body.is_empty() || body[0] == encoding {
+                continue;
+            }
+            let mut bytes = type_id.clone();
+            bytes.push(encoding);
+            bytes.extend_from_slice(&body[1..]);
+            result.push(Mutation::new(description, bytes));
+        }
+        if let ExtensionObjectBody::ByteString(b) = &self.body {
+            for mutation in b.mutations() {
+                let mut bytes = type_id.clone();
+                bytes.push(crate::uatypes::extension_object::EncodingValue::BYTE_STRING);
+                bytes.extend_from_slice(&mutation.bytes);
+                result.push(Mutation {
+                    path: join_path("body", &mutation.path),
+                    description: mutation.description,
+                    bytes,
+                });
+            }
+        }
+        result
+    }
+}
+
+impl Mutate for DateTime {
+    fn mutations(&self) -> Vec<Mutation> {
+        vec![
+            Mutation::new("datetime min", i64::MIN.serialize()),
+            Mutation::new("datetime max", i64::MAX.serialize()),
+            Mutation::new("datetime null", 0_i64.serialize()),
+            Mutation::new("datetime negative", (-1_i64).serialize()),
+        ]
+    }
+}
+
+impl Mutate for Guid {
+    fn mutations(&self) -> Vec<Mutation> {
+        vec![
+            Mutation::new("null guid", vec![0; 16]),
+            Mutation::new("guid all ones", vec![0xff; 16]),
+        ]
+    }
+}
+
+impl Mutate for LocalizedText {
+    fn mutations(&self) -> Vec<Mutation> {
+        let encoded = self.serialize();
+        let mut result = vec![
+            Mutation::new("mask without content", vec![localized_text::EncodingValue::LOCALE | localized_text::EncodingValue::TEXT]),
+            Mutation::new("empty mask", vec![0x00]),
+        ];
+        let mut bytes = encoded.clone();
+        bytes[0] |= 0xfc;
+        result.push(Mutation::new("reserved mask bits", bytes));
+        let mut bytes = vec![localized_text::EncodingValue::TEXT];
+        bytes.extend_from_slice(&(OVERSIZED_LENGTH as i32).serialize());
+        bytes.extend(vec![b'A'; OVERSIZED_LENGTH]);
+        result.push(Mutation::new("oversized text", bytes));
+        result
+    }
+}
+
+impl Mutate for Variant {
+    fn mutations(&self) -> Vec<Mutation> {
+        let encoded = self.serialize();
+        let mut result = vec![
+            Mutation::new("null variant", vec![DataTypeId::NULL]),
+            Mutation::new("unknown variant type", vec![0x3f]),
+            Mutation::new("variant type without value", vec![encoded[0] & 0x3f]),
+        ];
+        if self.is_array() {
+            //the dimensions of a matrix do not match the number of values
+            let mut bytes = vec![encoded[0] | DataTypeId::ARRAY_DIM_ENCODED];
+            bytes.extend_from_slice(&self.value.serialize());
+            bytes.extend_from_slice(&vec![self.value.len() as i32 + 1, 2].serialize());
+            result.push(Mutation::new("array dimensions larger than the array", bytes));
+            let mut bytes = encoded.clone();
+            bytes[0] &= !DataTypeId::ARRAY_VALUE_ENCODED;
+            result.push(Mutation::new("array flagged as scalar", bytes));
+        } else if encoded.len() > 1 {
+            //a scalar is claimed to be an array: the value is read as the array length
+            let mut bytes = encoded.clone();
+            bytes[0] |= DataTypeId::ARRAY_VALUE_ENCODED;
+            result.push(Mutation::new("scalar flagged as array", bytes));
+            let mut bytes = vec![encoded[0] | DataTypeId::ARRAY_VALUE_ENCODED];
+            bytes.extend_from_slice(&(i32::MAX).serialize());
+            bytes.extend_from_slice(&encoded[1..]);
+            result.push(Mutation::new("array length larger than content", bytes));
+            let mut bytes = encoded.clone();
+            bytes[0] |= DataTypeId::ARRAY_DIM_ENCODED;
+            bytes.extend_from_slice(&1_i32.serialize());
+            bytes.extend_from_slice(&(-1_i32).serialize());
+            result.push(Mutation::new("negative array dimension", bytes));
+        }
+        result
+    }
+}
+
+impl Mutate for DataValue {
+    fn mutations(&self) -> Vec<Mutation> {
+        let encoded = self.serialize();
+        let mut result = vec![
+            Mutation::new("mask without content", vec![0x3f]),
+            Mutation::new("empty data value", vec![0x00]),
+        ];
+        let mut bytes = encoded.clone();
+        bytes[0] |= 0xc0;
+        result.push(Mutation::new("reserved mask bits", bytes));
+        if let Some(value) = &self.value {
+            for mutation in value.mutations() {
+                let mut bytes = vec![data_value::EncodingValue::HAS_VALUE];
+                bytes.extend_from_slice(&mutation.bytes);
+                result.push(Mutation {
+                    path: join_path("value", &mutation.path),
+                    description: mutation.description,
+                    bytes,
+                });
+            }
+        }
+        result
+    }
+}
+
+impl Mutate for RequestHeader {
+    fn mutations(&self) -> Vec<Mutation> {
+        let mut additional_header = vec![Mutation::new("unknown additional header", ExtensionObject {
+            type_id: NodeId::new_numeric(0, u32::MAX),
+            body: ExtensionObjectBody::ByteString(ByteString::from(vec![0; 4])),
+        }.serialize())];
+        additional_header.extend(self.additional_header.mutations());
+        combine(vec![
+            ("authentication_token", self.authentication_token.serialize(), self.authentication_token.mutations()),
+            ("timestamp", self.timestamp.serialize(), self.timestamp.mutations()),
+            ("request_handle", self.request_handle.serialize(), self.request_handle.mutations()),
+            ("return_diagnostic", self.return_diagnostic.serialize(), self.return_diagnostic.mutations()),
+            ("audit_entry", self.audit_entry.serialize(), self.audit_entry.mutations()),
+            ("timout_hint", self.timout_hint.serialize(), self.timout_hint.mutations()),
+            ("additional_header", self.additional_header.serialize(), additional_header),
+        ])
+    }
+}
+
+//headers are kept intact. Otherwise the message is rejected at the transport layer.
+macro_rules! impl_mutate_none {
+    ($($t:ty),*) => {
+        $(
+            impl Mutate for $t {
+                fn mutations(&self) -> Vec<Mutation> {
+                    vec![]
+                }
+            }
+        )*
+    };
+}
+impl_mutate_none!(MessageHeader, ConnectionMessageHeader, SequenceHeader, SymmetricSecurityHeader, AsymmetricSecurityHeader);
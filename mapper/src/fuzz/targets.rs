@@ -0,0 +1,67 @@
+//! Entry points for coverage-guided fuzzers (cargo fuzz, afl ...) over the decoding stack, the cargo-fuzz
+//! targets of `fuzz/` call them. Whatever the input, they must return without panicking: malformed data is
+//! reported through `MapperError`. The tests of this module run them on mutated requests and responses.
+
+use crate::msg::header::prelude::*;
+use crate::msg::prelude::*;
+use crate::uatypes::generated::*;
+use crate::uatypes::prelude::*;
+use crate::Deserialize;
+
+include!(concat!(env!("OUT_DIR"), "/fuzz_targets.rs"));
+
+//decode data with T and with arrays of T
+fn decode<T: Deserialize>(data: &[u8]) {
+    let _ = T::deserialize(data);
+    let _ = Vec::<T>::deserialize(data);
+    let _ = UaArray::<T>::deserialize(data);
+}
+
+/// Parse a whole message as received from a server (or a client), and decode it with each message type.
+pub fn parse(data: &[u8]) {
+    let _ = crate::msg::parser::parse(data);
+    let _ = crate::msg::get_offset_to_encrypt(&data.to_vec());
+    messages(data);
+}
+
+/// Decode data with each built-in type and each structure of uatypes, hand-written or generated.
+pub fn uatypes(data: &[u8]) {
+    decode::<bool>(data);
+    decode::<i8>(data);
+    decode::<u8>(data);
+    decode::<i16>(data);
+    decode::<u16>(data);
+    decode::<i32>(data);
+    decode::<u32>(data);
+    decode::<i64>(data);
+    decode::<u64>(data);
+    decode::<f32>(data);
+    decode::<f64>(data);
+    decode::<String>(data);
+    decode::<UaString>(data);
+    decode::<ByteString>(data);
+    decode::<XmlElement>(data);
+    decode::<DateTime>(data);
+    decode::<Guid>(data);
+    decode::<NodeId>(data);
+    decode::<ExpandedNodeId>(data);
+    decode::<StatusCode>(data);
+    decode::<QualifiedName>(data);
+    decode::<LocalizedText>(data);
+    decode::<ExtensionObject>(data);
+    decode::<DataValue>(data);
+    decode::<Variant>(data);
+    decode::<DiagnosticInfo>(data);
+    decode::<ApplicationDescription>(data);
+    decode::<EndpointDescription>(data);
+    decode::<UserTokenPolicy>(data);
+    decode::<UserNameIdentityToken>(data);
+    decode::<X509IdentityToken>(data);
+    decode::<AnonymousIdentityToken>(data);
+    decode::<SignatureData>(data);
+    decode::<ReadValueId>(data);
+    decode::<WriteValue>(data);
+    decode::<RequestHeader>(data);
+    decode::<ResponseHeader>(data);
+    generated(data);
+}
@@ -0,0 +1,279 @@
+#[cfg(test)]
+mod tests {
+    use crate::crypto::security_policy::SecurityPolicyUri;
+    use crate::fuzz::targets;
+    use crate::handle::{Handle, OpcUaResponse};
+    use crate::msg::header::prelude::*;
+    use crate::msg::prelude::*;
+    use crate::msg::{parser, set_size};
+    use crate::uatypes::extension_object::ExtensionObjectBody;
+    use crate::uatypes::node_ids::ObjectIds;
+    use crate::uatypes::prelude::*;
+    use crate::{Deserialize, Serialize};
+    use std::io::Write;
+    use std::net::{TcpListener, TcpStream};
+
+    //xorshift, deterministic inputs without extra dependency
+    struct Rng(u64);
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+    }
+
+    //service message sent by a server: headers of the secure channel, encoding id, response header and body
+    fn response(node_id: u32, service_result: u32, body: &[Vec<u8>]) -> Vec<u8> {
+        let asymmetric = node_id == ObjectIds::OpenSecureChannelResponse_Encoding_DefaultBinary;
+        let mut result = match asymmetric {
+            true => [MessageHeader::default().serialize(), AsymmetricSecurityHeader::default().serialize()].concat(),
+            false => [MessageHeader::build(MessageType::MSG, b'F', 1).serialize(), SymmetricSecurityHeader { token_id: 1 }.serialize()].concat(),
+        };
+        result.extend_from_slice(&SequenceHeader { sequence_number: 2, request_id: 2 }.serialize());
+        result.extend_from_slice(&NodeId::new_numeric(0, node_id).serialize());
+        let response_header = ResponseHeader {
+            timestamp: DateTime::new_now(),
+            request_handle: 1,
+            service_result: StatusCode::new(service_result),
+            service_diagnostic: DiagnosticInfo::new(),
+            string_table: UaArray::from(vec![UaString::from("reason")]),
+            additional_header: ExtensionObject { type_id: NodeId::new_numeric(0, 0), body: ExtensionObjectBody::None },
+        };
+        result.extend_from_slice(&response_header.serialize());
+        for field in body {
+            result.extend_from_slice(field);
+        }
+        set_size(&mut result);
+        result
+    }
+
+    fn endpoint() -> EndpointDescription {
+        EndpointDescription {
+            endpoint_url: UaString::from("opc.tcp://localhost:4840"),
+            server: ApplicationDescription {
+                application_uri: UaString::from("urn:server"),
+                product_uri: UaString::new(),
+                application_name: LocalizedText::new(UaString::from("en"), UaString::from("server")),
+                application_type: 0,
+                gateway_server_uri: UaString::new(),
+                discovery_policy_uri: UaString::new(),
+                discovery_urls: UaArray::from(vec![UaString::from("opc.tcp://localhost:4840")]),
+            },
+            server_certificate: ByteString::from(vec![0x30, 0x82, 0x01, 0x0a]),
+            security_mode: 1,
+            security_policy_uri: UaString::from(SecurityPolicyUri::None),
+            user_identity_tokens: UaArray::from(vec![UserTokenPolicy {
+                policy_id: UaString::from("anonymous"),
+                token_type: 0,
+                issued_token_type: UaString::new(),
+                issuer_endpoint_url: UaString::new(),
+                security_policy_uri: UaString::new(),
+            }]),
+            transport_profile_uri: UaString::new(),
+            security_level: 0,
+        }
+    }
+
+    //requests of the mapper and responses of a server
+    fn seeds() -> Vec<Vec<u8>> {
+        let session = NodeId::new_numeric(1, 42);
+        let target = NodeId::new_numeric(2, 2258);
+        let value = DataValue::from_value(DataTypeId::DOUBLE, "43.5").unwrap();
+        let array = Variant::from_datatype(DataTypeId::INT_32, "[[1, 2], [3, 4]]").unwrap();
+        let endpoints = UaArray::from(vec![endpoint()]);
+        let no_diagnostics = UaArray::<DiagnosticInfo>::new();
+        let error = ErrorMessage { reason: UaString::from("BadTcpMessageTypeInvalid"), ..Default::default() };
+        vec![
+            HelloMessage::build(&UaString::from("opc.tcp://localhost:4840")).serialize(),
+            GetEndpointsRequest::build(1, 1).serialize(),
+            CloseSessionRequest::build(&session).serialize(),
+            ReadRequest::build(&session, &target).serialize(),
+            WriteRequest::build(&session, &target, &value).serialize(),
+            value.serialize(),
+            array.serialize(),
+            AckowledgeMessage::default().serialize(),
+            error.serialize(),
+            response(ObjectIds::OpenSecureChannelResponse_Encoding_DefaultBinary, 0, &[
+                0_u32.serialize(),
+                [5_u32.serialize(), 1_u32.serialize(), DateTime::new_now().serialize(), 3600000_u32.serialize()].concat(),
+                ByteString::from(vec![7; 32]).serialize(),
+            ]),
+            response(ObjectIds::GetEndpointsResponse_Encoding_DefaultBinary, 0, &[endpoints.serialize()]),
+            response(ObjectIds::CreateSessionResponse_Encoding_DefaultBinary, 0, &[
+                NodeId::new_numeric(1, 1).serialize(),
+                NodeId::new_bytestring(0, ByteString::from(vec![1, 2, 3])).serialize(),
+                1200000.5_f64.serialize(),
+                ByteString::from(vec![0; 32]).serialize(),
+                endpoint().server_certificate.serialize(),
+                endpoints.serialize(),
+                (-1_i32).serialize(),
+                SignatureData::new().serialize(),
+                65536_u32.serialize(),
+            ]),
+            response(ObjectIds::ReadResponse_Encoding_DefaultBinary, 0, &[
+                UaArray::from(vec![value.clone(), DataValue::empty()]).serialize(),
+                no_diagnostics.serialize(),
+            ]),
+            response(ObjectIds::ServiceFault_Encoding_DefaultBinary, StatusCode::BadSessionIdInvalid, &[]),
+        ]
+    }
+
+    //mutate a valid encoding: byte flips, boundaries, truncation and bogus lengths
+    fn mutate(rng: &mut Rng, seed: &[u8]) -> Vec<u8> {
+        let mut data = seed.to_vec();
+        for _ in 0..1 + rng.below(4) {
+            if data.is_empty() {
+                break;
+            }
+            let pos = rng.below(data.len());
+            match rng.below(5) {
+                0 => data[pos] ^= 1 << rng.below(8),
+                1 => data[pos] = [0x00, 0xff, 0x7f, 0x80][rng.below(4)],
+                2 => data.truncate(pos),
+                3 => {
+                    let length = [-2_i32, -1, i32::MAX, 0x10000][rng.below(4)];
+                    let end = (pos + 4).min(data.len());
+                    data.splice(pos..end, length.serialize());
+                }
+                _ => data[pos] = rng.below(256) as u8,
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn seeds_are_valid() {
+        //the messages among the seeds, the responses included, are parsed
+        let messages: Vec<Vec<u8>> = seeds().into_iter().filter(|s| s.len() >= 8 && s[3] == b'F').collect();
+        assert_eq!(messages.len(), 12);
+        for message in messages {
+            assert!(parser::parse(&message).is_ok(), "{:?}", parser::parse(&message));
+        }
+    }
+
+    #[test]
+    fn fuzz_parse() {
+        let mut rng = Rng(0x9e3779b97f4a7c15);
+        for seed in seeds() {
+            for _ in 0..2000 {
+                targets::parse(&mutate(&mut rng, &seed));
+            }
+        }
+        //random bytes behind each message type
+        for message_type in [&b"HEL"[..], b"ACK", b"ERR", b"RHE", b"MSG", b"OPN", b"CLO"] {
+            for _ in 0..500 {
+                let mut data = message_type.to_vec();
+                for _ in 0..rng.below(64) {
+                    data.push(rng.below(256) as u8);
+                }
+                targets::parse(&data);
+            }
+        }
+    }
+
+    #[test]
+    fn fuzz_uatypes() {
+        let mut rng = Rng(0x2545f4914f6cdd1d);
+        for seed in seeds() {
+            for _ in 0..300 {
+                let data = mutate(&mut rng, &seed);
+                let start = rng.below(data.len().max(1));
+                targets::uatypes(&data[start.min(data.len())..]);
+            }
+        }
+        for _ in 0..2000 {
+            let data: Vec<u8> = (0..rng.below(48)).map(|_| rng.below(256) as u8).collect();
+            targets::uatypes(&data);
+        }
+    }
+
+    #[test]
+    fn short_messages() {
+        for data in [&b""[..], b"M", b"MS", b"MSG", b"MSGF", b"MSGF\x08\x00\x00\x00", b"OPNF\x30\x00\x00\x00\x00\x00\x00\x00"] {
+            assert!(parser::parse(data).is_err());
+        }
+        //negative length of the security policy uri
+        let mut opn = b"OPNF\x30\x00\x00\x00\x00\x00\x00\x00".to_vec();
+        opn.extend_from_slice(&(-5_i32).serialize());
+        assert!(parser::parse(&opn).is_err());
+        let mut opn = b"OPNF\x30\x00\x00\x00\x00\x00\x00\x00".to_vec();
+        opn.extend_from_slice(&i32::MAX.serialize());
+        assert!(parser::parse(&opn).is_err());
+    }
+
+    #[test]
+    fn nesting_depth() {
+        //variant inside variant inside variant ...
+        let nested = vec![DataTypeId::VARIANT; 100000];
+        assert!(Variant::deserialize(&nested).is_err());
+        let nested = vec![0x40_u8; 100000];
+        assert!(DiagnosticInfo::deserialize(&nested).is_err());
+        let mut nested = vec![];
+        for _ in 0..100000 {
+            nested.extend_from_slice(&[0x01, DataTypeId::DATAVALUE]);
+        }
+        assert!(DataValue::deserialize(&nested).is_err());
+        //the depth is released after decoding
+        let mut variant = vec![DataTypeId::VARIANT; 10];
+        variant.extend_from_slice(&[DataTypeId::BOOLEAN, 1]);
+        assert!(Variant::deserialize(&variant).is_ok());
+        assert!(Variant::deserialize(&variant).is_ok());
+    }
+
+    #[test]
+    fn configured_limits() {
+        let hello = UaString::from("hello").serialize();
+        let array = vec![1_u32, 2, 3].serialize();
+        assert!(UaString::deserialize(&hello).is_ok());
+        DecodeLimits::new(4, 2, 1, DecodeLimits::DEFAULT_MAX_MESSAGE_SIZE).apply();
+        assert!(UaString::deserialize(&hello).is_err());
+        assert!(ByteString::deserialize(&hello).is_err());
+        assert!(Vec::<u32>::deserialize(&array).is_err());
+        let nested = vec![DataTypeId::VARIANT, DataTypeId::BOOLEAN, 1];
+        assert!(Variant::deserialize(&nested).is_err());
+        DecodeLimits::default().apply();
+        assert!(Vec::<u32>::deserialize(&array).is_ok());
+        assert!(Variant::deserialize(&nested).is_ok());
+        //huge array length without content
+        let huge = (i32::MAX).serialize();
+        assert!(Vec::<u8>::deserialize(&huge).is_err());
+        //invalid utf8 is an error, not a panic
+        let mut invalid = 2_i32.serialize();
+        invalid.extend_from_slice(&[0xc3, 0x28]);
+        assert!(UaString::deserialize(&invalid).is_err());
+        assert!(String::deserialize(&invalid).is_err());
+    }
+
+    #[test]
+    fn oversized_message_skipped() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            //a message over the limit followed by an acknowledge
+            let mut oversized = [b"MSGF".to_vec(), 100_u32.serialize()].concat();
+            oversized.resize(100, 0xAB);
+            let ack = [b"ACKF".to_vec(), 12_u32.serialize(), 0_u32.serialize()].concat();
+            stream.write_all(&[oversized, ack].concat()).unwrap();
+        });
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.set_read_timeout(Some(std::time::Duration::from_millis(1000))).unwrap();
+        DecodeLimits::new(DecodeLimits::DEFAULT_MAX_STRING_LENGTH, DecodeLimits::DEFAULT_MAX_ARRAY_LENGTH, DecodeLimits::DEFAULT_MAX_DEPTH, 64).apply();
+        let responses = Handle::recv_opcua_response(&mut stream, 8, Some(2));
+        DecodeLimits::default().apply();
+        server.join().unwrap();
+        match responses.as_slice() {
+            [OpcUaResponse::Some(header), OpcUaResponse::Some(ack)] => {
+                assert_eq!(header.len(), 8);
+                assert_eq!(&ack[..4], b"ACKF");
+                assert_eq!(ack.len(), 12);
+            }
+            r => panic!("unexpected responses {:?}", r),
+        }
+    }
+}
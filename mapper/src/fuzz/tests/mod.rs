@@ -0,0 +1,2 @@
+mod decode_test;
+mod mutation_test;
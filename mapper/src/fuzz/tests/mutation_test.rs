@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use crate::fuzz::{FuzzOutcome, Mutate};
+    use crate::liveness::Liveness;
+    use crate::msg::services::ReadRequest;
+    use crate::uatypes::prelude::*;
+    use crate::Serialize;
+
+    #[test]
+    fn integer_boundaries() {
+        let mutations = 5_u32.mutations();
+        let values: Vec<Vec<u8>> = mutations.iter().map(|m| m.bytes.clone()).collect();
+        assert!(values.contains(&0_u32.serialize()));
+        assert!(values.contains(&u32::MAX.serialize()));
+        assert!(!values.contains(&5_u32.serialize()));
+        //no duplicate
+        let mutations = 0_u32.mutations();
+        assert!(!mutations.iter().any(|m| m.bytes == 0_u32.serialize()));
+    }
+
+    #[test]
+    fn string_null_and_empty() {
+        let mutations = UaString::from("abc").mutations();
+        assert_eq!(mutations[0].bytes, (-1_i32).serialize());
+        assert_eq!(mutations[1].bytes, 0_i32.serialize());
+        //a null string has no null mutation
+        let mutations = UaString::new().mutations();
+        assert!(!mutations.iter().any(|m| m.description == "null"));
+    }
+
+    #[test]
+    fn array_mutations() {
+        let array: Vec<u32> = vec![1, 2];
+        let mutations = array.mutations();
+        assert_eq!(mutations[0].bytes, (-1_i32).serialize());
+        assert_eq!(mutations[1].bytes, 0_i32.serialize());
+        let element = mutations.iter().find(|m| m.path == "[1]").unwrap();
+        assert_eq!(&element.bytes[..8], &[2, 0, 0, 0, 1, 0, 0, 0]);
+        assert_eq!(element.bytes.len(), 12);
+        //a null array has no null mutation but an empty one
+        let mutations = UaArray::<u32>::new().mutations();
+        assert!(!mutations.iter().any(|m| m.description == "null array"));
+        assert!(mutations.iter().any(|m| m.bytes == 0_i32.serialize()));
+    }
+
+    #[test]
+    fn derived_struct_keeps_other_fields() {
+        let name = QualifiedName::new(3, UaString::from("abc"));
+        let encoded_name = UaString::from("abc").serialize();
+        for mutation in name.mutations() {
+            if mutation.path == "namespace_index" {
+                assert_eq!(&mutation.bytes[2..], &encoded_name[..]);
+            } else {
+                assert_eq!(mutation.path, "name");
+                assert_eq!(&mutation.bytes[..2], &[3, 0]);
+            }
+        }
+    }
+
+    #[test]
+    fn read_request_headers_untouched() {
+        let request = ReadRequest::build(&NodeId::new_numeric(0, 0), &NodeId::new_numeric(0, 2258));
+        let encoded = request.serialize();
+        let mutations = request.mutations();
+        assert!(!mutations.is_empty());
+        for mutation in mutations {
+            assert!(!mutation.path.starts_with("message_header"));
+            assert!(!mutation.path.starts_with("security_header"));
+            assert!(!mutation.path.starts_with("sequence_header"));
+            assert_eq!(&mutation.bytes[..24], &encoded[..24]);
+        }
+    }
+
+    #[test]
+    fn classify() {
+        let outputs = |last: &str| vec!["Ack,".to_string(), last.to_string()];
+        assert_eq!(FuzzOutcome::classify(&outputs("OpnResOK,Eof,"), Liveness::Alive), FuzzOutcome::Eof);
+        assert_eq!(FuzzOutcome::classify(&outputs("Eof"), Liveness::Alive), FuzzOutcome::Eof);
+        assert_eq!(FuzzOutcome::classify(&outputs("Err,"), Liveness::Alive), FuzzOutcome::Err);
+        assert_eq!(FuzzOutcome::classify(&outputs("OpnResOK,Eof,"), Liveness::Crash), FuzzOutcome::Crash);
+        assert_eq!(FuzzOutcome::classify(&outputs("OpnResOK,"), Liveness::Hang), FuzzOutcome::Hang);
+        assert_eq!(FuzzOutcome::classify(&outputs("OpnResOK,"), Liveness::Alive), FuzzOutcome::Response("OpnResOK,".to_string()));
+    }
+}
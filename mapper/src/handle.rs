@@ -2,23 +2,32 @@ use crate::crypto::{hash};
 use crate::crypto::pkey::{PrivateKey, PublicKey};
 use crate::crypto::security_policy::SecurityPolicy;
 use crate::crypto::security_policy::SecurityPolicyUri;
-use crate::msg::close_secure_channel::CloseSecureChannelRequest;
-use crate::msg::close_session::CloseSessionRequest;
-use crate::msg::create_session::CreateSessionRequest;
-use crate::msg::get_endpoints::{GetEndPointsRequest};
+use crate::crypto::certgen::{Chain, ClientCertificates, Defect};
+use crate::crypto::x509::TrustedList;
 use crate::msg::prelude::*;
 use crate::msg::message_type::MessageType;
-use crate::msg::open_secure_channel::{OpenSecureChannelRequest};
 use crate::msg::{self, Msg};
+use crate::uatypes::generated::AdditionalParametersType;
 use crate::uatypes::prelude::*;
+use crate::uatypes::data_value;
 
 use crate::{Serialize};
+use crate::fuzz::{Mutate, Mutation};
+use crate::abstraction::{self, OutputAbstraction};
+use crate::conformance::{self, Conformance, Finding};
+use crate::liveness::{self, Liveness, LivenessCheck};
+use crate::scan::{self, EndpointScan, Outcome, ScanReport};
+use crate::scan::certificate::CertificateReport;
+use crate::liveness::pcap::{Capture, Direction};
 use openssl::rsa::Rsa;
 use openssl::x509::X509;
 use core::panic;
 
 use std::io::{prelude::*, ErrorKind};
-// use crate::result_prelude::*;
+use crate::result_prelude::*;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
 use std::net::{TcpStream};
 use std::{thread, time};
 
@@ -27,12 +36,30 @@ use std::{thread, time};
 /// for now it is still a mess due to debug purpose
 
 #[derive(Debug)]
-enum OpcUaResponse{
+pub (crate) enum OpcUaResponse{
     Eof,
     Some(Vec<u8>),
     NoResp,
 }
 
+/// Word sent to a target: its symbols, the number of responses of each symbol and the symbols without response
+#[derive(Clone)]
+pub (crate) struct Word<'w>{
+    pub (crate) socket_addr:String,
+    pub (crate) messages:Vec<&'w str>,
+    pub (crate) target_index:usize,
+    pub (crate) timeout:u64,
+    pub (crate) nb_messages:Vec<usize>,
+    pub (crate) known_no_resp:Vec<usize>,
+}
+
+impl<'w> Word<'w>{
+    //word whose symbols have one response each, except the ones of `known_no_resp`
+    fn new(socket_addr:&str,messages:Vec<&'w str>,target_index:usize,timeout:u64,known_no_resp:Vec<usize>)->Word<'w>{
+        Word{socket_addr:socket_addr.to_string(),messages,target_index,timeout,nb_messages:vec![],known_no_resp}
+    }
+}
+
 pub struct Handle<'a> {
     //buffer information
     receiver_buffer_size: u32,
@@ -59,6 +86,12 @@ pub struct Handle<'a> {
     sender_false_certificate: ByteString,
     user_certificate:ByteString,
     user_false_certificate:ByteString,
+    //key, application certificate and user certificate of each certificate defect
+    defect_credentials:Vec<(Defect,DefectCredentials)>,
+    //defect of the certificate of the last OpenSecureChannel, its key signs the channel instead of the false key
+    channel_defect:Option<Defect>,
+    //chains of the certificate of the private key, leaf first
+    chains:Vec<(Chain,ByteString)>,
     receiver_certificate_thumbprint: Vec<ByteString>,
     client_nonce:ByteString,
     server_nonce:ByteString,
@@ -73,13 +106,60 @@ pub struct Handle<'a> {
     shift_secure_token_id: bool,
     endpoint_url:Vec<UaString>,
     target_node:NodeId,
+    //target node given with a namespace uri, resolved against the namespace array of each server
+    target_node_uri:Option<ExpandedNodeId>,
+    namespace_arrays:HashMap<usize,Vec<UaString>>,
+    namespace_array_read:Option<Vec<UaString>>,
+    reading_namespace_array:bool,
     target_node_value:DataValue,
+    //attributes read and written by read_items and write_items
+    read_items:Vec<ReadValueId>,
+    write_items:Vec<WriteValue>,
+    //node ids of the items as given, their namespace uri is resolved as the one of the target node
+    read_item_nodes:Vec<ExpandedNodeId>,
+    write_item_nodes:Vec<ExpandedNodeId>,
+    //the last request is read_items or write_items, its response gives the result of each item
+    items_requested:bool,
+    //the last request writes a status or a timestamp (write_req_status...), its response gives the result of the write
+    write_fields_requested:bool,
+    //abstraction of the responses into outputs
+    output_abstraction:Box<dyn OutputAbstraction>,
     c_chunk:bool,
+    decode_limits:DecodeLimits,
+    //liveness oracle
+    liveness:Option<LivenessCheck>,
+    capture:Option<Capture>,
+    //state of the server after the last word and index of the symbol where it is reported, None without liveness check
+    last_liveness:Option<(usize,Liveness)>,
+    //responses of the last word that could not be parsed: index of the symbol and error
+    parse_errors:Vec<(usize,String)>,
+    //status of the responses of the last word that are not Good: index of the symbol and status
+    service_results:Vec<(usize,StatusCode)>,
+    //details of the errors and diagnostics of the responses of the last word: index of the symbol and details
+    diagnostics:Vec<(usize,String)>,
+    //return_diagnostic mask of the request headers
+    return_diagnostics:u32,
+    //conformance oracle and its findings on the responses of the last word
+    conformance:Conformance,
+    conformance_findings:Vec<Finding>,
+    //endpoints of GetEndpoints for each target
+    server_endpoints:Vec<Vec<EndpointDescription>>,
+    //policy id of the user token policy tried by the scanner, the other policies are ignored
+    scan_policy_id:Option<UaString>,
+    //trust list validating the server certificates, used by the output abstraction and the scanner
+    trust_list:Option<Arc<TrustedList>>,
 
 
     
 }
 
+//key, application certificate and user certificate with a certificate defect
+struct DefectCredentials{
+    private_key:PrivateKey,
+    certificate:ByteString,
+    user_certificate:ByteString,
+}
+
 impl<'a> Handle<'a> {
         //define abstract messages
         pub const HELLO: &'static str = "hello";
@@ -97,10 +177,48 @@ impl<'a> Handle<'a> {
         pub const ACTIVE_SESS_WRONG_USER: &'static str = "active_session_wrong_user";
         pub const ACTIVE_SESS_CERT: &'static str = "active_session_cert";
         pub const ACTIVE_SESS_WRONG_CERT: &'static str = "active_session_cert_wrong";
+        //followed by the name of a certificate defect (crate::crypto::certgen::Defect): opn_cert_expired, activate_user_cert_revoked
+        pub const OPN_CERT: &'static str = "opn_cert_";
+        pub const ACTIVE_SESS_USER_CERT: &'static str = "activate_user_cert_";
+        //followed by the name of a certificate chain (crate::crypto::certgen::Chain): opn_chain_wrong_order, create_session_chain_truncated
+        pub const OPN_CHAIN: &'static str = "opn_chain_";
+        pub const CREATE_SESS_CHAIN: &'static str = "create_session_chain_";
         pub const READ_REQ: &'static str = "read_req";
+        pub const READ_NAMESPACE_ARRAY: &'static str = "read_namespace_array";
+        pub const READ_ITEMS: &'static str = "read_items";
+        pub const WRITE_ITEMS: &'static str = "write_items";
         pub const WRITE_REQ: &'static str = "write_req";
+        //the DataValue written also has a status code and/or timestamps, any combination of the fields is accepted: write_req_status_server
+        pub const WRITE_REQ_STATUS: &'static str = "write_req_status";
+        pub const WRITE_REQ_SOURCE_TIMESTAMP: &'static str = "write_req_source";
+        pub const WRITE_REQ_SERVER_TIMESTAMP: &'static str = "write_req_server";
+        pub const WRITE_REQ_FULL: &'static str = "write_req_status_source_server";
         pub const NULL_SIZE: &'static str = "nullsize";
         pub const OPEN_REQ_C_CHUNK:&'static str="open_secure_channel_c_chunk";
+        //symbols with a fixed name, the write_req, certificate defect and chain symbols are recognized from their name
+        pub const SYMBOLS: [&'static str; 21] = [
+            Handle::HELLO,
+            Handle::OPN_REQ,
+            Handle::OPN_REQ_WRONG,
+            Handle::GET_ENDPOINT_REQ,
+            Handle::CLO_REQ,
+            Handle::CREATE_SESS,
+            Handle::CREATE_SESS_SEC_TOKEN_ID,
+            Handle::CLOSE_SESS,
+            Handle::ACTIVE_SESS,
+            Handle::ACTIVE_SESS_SEC_TOKEN_ID,
+            Handle::SET_SEC_MODE_NONE,
+            Handle::ACTIVE_SESS_ANON,
+            Handle::ACTIVE_SESS_WRONG_USER,
+            Handle::ACTIVE_SESS_CERT,
+            Handle::ACTIVE_SESS_WRONG_CERT,
+            Handle::READ_REQ,
+            Handle::READ_NAMESPACE_ARRAY,
+            Handle::READ_ITEMS,
+            Handle::WRITE_ITEMS,
+            Handle::NULL_SIZE,
+            Handle::OPEN_REQ_C_CHUNK,
+        ];
 
 
 
@@ -133,23 +251,37 @@ impl<'a> Handle<'a> {
         pub const R_WRITE_REQ: &'static str = "WriteReq,";
         pub const R_WRITE_REPOK: &'static str = "WriteRepOK,";
         pub const R_WRITE_REPNOK: &'static str = "WriteRepNOK,";
+        //followed by the name of the bad result of a write of write_req_status, write_req_source...: WriteRepBadWriteNotSupported,
+        pub const R_WRITE_REP: &'static str = "WriteRep";
         pub const R_PARSE_ERR: &'static str = "ParseErr,";
+        //the symbol cannot be sent, see check_symbol
+        pub const R_INVALID_SYMBOL: &'static str = "InvalidSymbol,";
+        //the namespace uris of the node ids cannot be resolved on the server
+        pub const R_UNRESOLVED: &'static str = "Unresolved,";
         pub const R_NULL_SIZE: &'static str = "nullsize,";
         pub const R_EOF: &'static str = "Eof,";
         pub const R_NO_RESP: &'static str = "No resp,";
 
     pub fn new_basic256_sha256(key_path:&str,false_key_path:&str,own_cert_path: &str,sender_false_certificate_path:&str,usr_cert_path:&str,usr_false_cert_path:&str,security_mode:u32) -> Handle<'a> {
-        let sender_certificate = std::fs::read(own_cert_path).unwrap();
+        let certificates=ClientCertificates::read(key_path,false_key_path,own_cert_path,sender_false_certificate_path,usr_cert_path,usr_false_cert_path).unwrap();
+        Handle::from_certificates(&certificates,security_mode)
+    }
 
-        let private_key_raw = std::fs::read(key_path).unwrap();
-        let rsa = Rsa::private_key_from_der(&private_key_raw).unwrap();
+    ///Same as new_basic256_sha256 with the keys and certificates in memory, e.g. generated by crate::crypto::certgen.
+    pub fn from_certificates(certificates:&ClientCertificates,security_mode:u32) -> Handle<'a> {
+        let rsa = Rsa::private_key_from_der(&certificates.private_key).unwrap();
         let private_key = PrivateKey::from_rsa(rsa).unwrap();
-        let private_key_false_raw = std::fs::read(false_key_path).unwrap();
-        let rsa = Rsa::private_key_from_der(&private_key_false_raw).unwrap();
+        let rsa = Rsa::private_key_from_der(&certificates.false_private_key).unwrap();
         let private_key_false = PrivateKey::from_rsa(rsa).unwrap();
-        let user_certificate_raw = std::fs::read(usr_cert_path).unwrap();
-        let user_false_certificate_raw = std::fs::read(usr_false_cert_path).unwrap();
-        let sender_false_certificate_raw = std::fs::read(sender_false_certificate_path).unwrap();
+        let sender_certificate = certificates.certificate.clone();
+        let user_certificate_raw = certificates.user_certificate.clone();
+        let user_false_certificate_raw = certificates.user_false_certificate.clone();
+        let sender_false_certificate_raw = certificates.false_certificate.clone();
+        let defect_credentials=certificates.defects.iter().map(|(defect,c)| {
+            let private_key=PrivateKey::from_rsa(Rsa::private_key_from_der(&c.private_key).unwrap()).unwrap();
+            (*defect,DefectCredentials{private_key,certificate:ByteString::from(c.certificate.clone()),user_certificate:ByteString::from(c.user_certificate.clone())})
+        }).collect();
+        let chains=certificates.chains.iter().map(|(chain,c)| (*chain,ByteString::from(c.clone()))).collect();
 
         let handle = Handle {
             receiver_buffer_size: 0,
@@ -173,6 +305,9 @@ impl<'a> Handle<'a> {
             sender_false_certificate: ByteString::from(sender_false_certificate_raw),
             user_certificate:ByteString::from(user_certificate_raw),
             user_false_certificate:ByteString::from(user_false_certificate_raw),
+            defect_credentials,
+            channel_defect:None,
+            chains,
             receiver_certificate_thumbprint: vec![],//ByteString::from(receiver_certificate_thumbprint),
             client_nonce:ByteString::new(),
             server_nonce:ByteString::new(),
@@ -186,8 +321,32 @@ impl<'a> Handle<'a> {
             shift_secure_token_id:false,
             endpoint_url:vec![],
             target_node:NodeId::empty(),
+            target_node_uri:None,
+            namespace_arrays:HashMap::new(),
+            namespace_array_read:None,
+            reading_namespace_array:false,
             target_node_value:DataValue::empty(),
+            read_items:Vec::new(),
+            write_items:Vec::new(),
+            read_item_nodes:Vec::new(),
+            write_item_nodes:Vec::new(),
+            items_requested:false,
+            write_fields_requested:false,
+            output_abstraction:Box::new(abstraction::Coarse),
             c_chunk:false,
+            decode_limits:DecodeLimits::default(),
+            liveness:None,
+            capture:None,
+            last_liveness:None,
+            parse_errors:Vec::new(),
+            service_results:Vec::new(),
+            diagnostics:Vec::new(),
+            return_diagnostics:0,
+            conformance:Conformance::default(),
+            conformance_findings:Vec::new(),
+            server_endpoints:Vec::new(),
+            scan_policy_id:None,
+            trust_list:None,
         };
         handle
     }
@@ -199,11 +358,124 @@ impl<'a> Handle<'a> {
     pub fn set_target_node(&mut self,type_:u8,namespace:u16,id:&str){
         let identifier=NodeId::from_str_to_id(id, type_);
         self.target_node=NodeId::new(namespace, identifier);
+        self.target_node_uri=None;
+
+    }
+
+    ///target node given with the OPC UA text syntax: `ns=2;s=Foo`, `i=2258` or `nsu=http://example.org/UA/;s=Foo`.
+    ///A namespace uri is resolved with the NamespaceArray of the server, read once for each target before its first word.
+    pub fn set_target_node_id(&mut self,id:&str)->MapperResult<()>{
+        let node_id=id.parse::<ExpandedNodeId>()?;
+        match node_id.namespace_uri(){
+            Some(_)=>self.target_node_uri=Some(node_id),
+            None=>{
+                self.target_node=node_id.node_id().clone();
+                self.target_node_uri=None;
+            },
+        }
+        Ok(())
+    }
+
+    //read the namespace array of the target with its own session and resolve the namespace uris of the target node and of the items
+    fn resolve_node_ids(&mut self,socket_addr:&str,target_index:usize,timeout:u64)->MapperResult<()>{
+        let has_uri=|nodes:&[ExpandedNodeId]| nodes.iter().any(|n| n.namespace_uri().is_some());
+        if self.target_node_uri.is_none() && !has_uri(&self.read_item_nodes) && !has_uri(&self.write_item_nodes){
+            return Ok(());
+        }
+        if !self.namespace_arrays.contains_key(&target_index){
+            //servers without anonymous access need the user session
+            for active_session in [Handle::ACTIVE_SESS_ANON,Handle::ACTIVE_SESS]{
+                let word=vec![Handle::HELLO,Handle::OPN_REQ,Handle::CREATE_SESS,active_session,Handle::READ_NAMESPACE_ARRAY,Handle::CLOSE_SESS,Handle::CLO_REQ];
+                self.send_word(Word::new(socket_addr,word,target_index,timeout,vec![6]),None);
+                self.reading_namespace_array=false;
+                if let Some(namespace_array)=self.namespace_array_read.take(){
+                    self.namespace_arrays.insert(target_index,namespace_array);
+                    break;
+                }
+            }
+        }
+        let namespace_array=self.namespace_arrays.get(&target_index)
+            .ok_or_else(|| MapperError::new(MapperErrorKind::UnexpectedValue,"failed to read the namespace array"))?;
+        if let Some(target_node_uri)=&self.target_node_uri{
+            self.target_node=target_node_uri.resolve(namespace_array)?;
+        }
+        for (item,node_id) in self.read_items.iter_mut().zip(&self.read_item_nodes){
+            item.node_id=node_id.resolve(namespace_array)?;
+        }
+        for (item,node_id) in self.write_items.iter_mut().zip(&self.write_item_nodes){
+            item.node_id=node_id.resolve(namespace_array)?;
+        }
+        Ok(())
+    }
+
+    ///value written by write_req, arrays and matrices are given between brackets: `[1,2,3]`, `[[1,2],[3,4]]`
+    pub fn set_target_node_value(&mut self,type_:u8,value:&str)->MapperResult<()>{
+        let mut data_value=DataValue::from_value(type_,value)?;
+        data_value.status=self.target_node_value.status;
+        data_value.source_time_stamp=self.target_node_value.source_time_stamp.clone();
+        data_value.server_time_stamp=self.target_node_value.server_time_stamp.clone();
+        self.target_node_value=data_value;
+        Ok(())
+    }
+
+    ///status code (name or value) and ISO 8601 timestamps of the DataValue written by the write_req_status/source/server symbols.
+    ///Without status the value is Good, without timestamp the current time is used.
+    pub fn set_target_node_value_fields(&mut self,status:Option<&str>,source_timestamp:Option<&str>,server_timestamp:Option<&str>)->MapperResult<()>{
+        let status=status.map(StatusCode::parse).transpose()?;
+        let source_timestamp=source_timestamp.map(DateTime::parse).transpose()?;
+        let server_timestamp=server_timestamp.map(DateTime::parse).transpose()?;
+        self.target_node_value.status=status.map(|s| s.get_value());
+        self.target_node_value.source_time_stamp=source_timestamp;
+        self.target_node_value.server_time_stamp=server_timestamp;
+        Ok(())
+    }
+
+    ///attributes read by read_items: `<node id>@<attribute>`, e.g. `ns=1;s=the.answer@UserAccessLevel` or `i=2258@RolePermissions`.
+    ///A namespace uri (`nsu=`) is resolved as the one of the target node.
+    pub fn set_read_items(&mut self,items:&[&str])->MapperResult<()>{
+        let items=items.iter()
+            .map(|i| ReadValueId::parse(i).map_err(|e| MapperError::new(MapperErrorKind::UnexpectedValue,&format!("invalid read item {}: {:?}",i,e))))
+            .collect::<MapperResult<Vec<_>>>()?;
+        (self.read_item_nodes,self.read_items)=items.into_iter().unzip();
+        Ok(())
+    }
+
+    ///attributes written by write_items: `<node id>@<attribute>:<data type id>=<value>`, e.g. `ns=1;s=the.answer@DisplayName:21=en|Answer`
+    pub fn set_write_items(&mut self,items:&[&str])->MapperResult<()>{
+        let items=items.iter()
+            .map(|i| WriteValue::parse(i).map_err(|e| MapperError::new(MapperErrorKind::UnexpectedValue,&format!("invalid write item {}: {:?}",i,e))))
+            .collect::<MapperResult<Vec<_>>>()?;
+        (self.write_item_nodes,self.write_items)=items.into_iter().unzip();
+        Ok(())
+    }
+
+    ///abstraction of the responses: `coarse` (OK/NOK, the default), `class` (Good/Uncertain/Bad) or `name` (name of the status code),
+    ///optionally followed by predicates on the fields of the responses, e.g. `name:server_nonce,endpoints`
+    ///the `trusted_certificate` predicate needs a trust list, see set_trust_list
+    pub fn set_output_abstraction(&mut self,spec:&str)->MapperResult<()>{
+        self.output_abstraction=abstraction::from_spec_with_trust_list(spec,self.trust_list.clone())?;
+        Ok(())
+    }
 
+    ///trust list loaded from a PKI directory (trusted/certs, trusted/crl, issuers/certs, issuers/crl and rejected/certs)
+    ///validating the server certificates for the `trusted_certificate` predicate and the scanner
+    pub fn set_trust_list(&mut self,dir:&str)->MapperResult<()>{
+        let trust_list=TrustedList::load(Path::new(dir))?;
+        self.trust_list=Some(Arc::new(trust_list));
+        Ok(())
     }
 
-    pub fn set_target_node_value(&mut self,type_:u8,value:&str){
-        self.target_node_value=DataValue::from_value(type_,value).unwrap();
+    ///optional fields of the DataValue written by a write symbol, None if the symbol is not a write
+    pub (crate) fn write_fields(symbol:&str)->Option<u8>{
+        if symbol==Handle::WRITE_REQ{
+            return Some(0);
+        }
+        symbol.strip_prefix("write_req_")?.split('_').try_fold(0,|fields,field| match field{
+            "status"=>Some(fields|data_value::EncodingValue::STATUS_CODE),
+            "source"=>Some(fields|data_value::EncodingValue::SOURCE_TIME_STAMP),
+            "server"=>Some(fields|data_value::EncodingValue::SERVER_TIME_STAMP),
+            _=>None,
+        })
     }
 
     pub fn get_server_certificate(&mut self,socket_addr:String,timeout :u64){
@@ -235,7 +507,7 @@ impl<'a> Handle<'a> {
         for (cpt,msg) in messages.into_iter().enumerate() {
             //session timeout is not usefull
             let msg = match self.translate_from_abstract_to_object(&UaString::from("opc.tcp://localhost:4840"),msg,&mut security_policy,&channel_timeout,&2000.0,&ByteString::new(),None,None){
-                Some(m)=>m,
+                Ok(Some(m))=>m,
                 _=> panic!("Unreachable"),
             };
 
@@ -258,10 +530,12 @@ impl<'a> Handle<'a> {
                         OpcUaResponse::Some(buf)=>{
                             let msg = crate::msg::parser::parse(&buf);
                             match msg{
-                                Ok(Msg::GetEndPointsResponse(m))=>{
+                                Ok(Msg::GetEndpointsResponse(m))=>{
                                     cert=Some(m.endpoints[0].server_certificate.clone());
+                                    self.conformance.record_endpoints(self.endpoint_url.len(),&m.endpoints);
+                                    self.server_endpoints.push(m.endpoints.to_vec());
                                     self.endpoint_url.push(m.endpoints[0].endpoint_url.clone());
-                                    self.update_from_msg(&Msg::GetEndPointsResponse(m),&mut security_policy);
+                                    self.update_from_msg(&Msg::GetEndpointsResponse(m),&mut security_policy);
                                 },
                                 Ok(m)=>{
                                     self.update_from_msg(&m,&mut security_policy);
@@ -297,7 +571,259 @@ impl<'a> Handle<'a> {
         
     }
 
+    ///Try each endpoint of the target given by GetEndpoints: a secure channel with its security mode and policy, then a session
+    ///activated with each of its user token policies (anonymous, user1/password or the user certificate).
+    ///get_server_certificate must have been called for the target.
+    pub fn scan_endpoints(&mut self,socket_addr:String,target_index:usize,timeout:u64)->MapperResult<ScanReport>{
+        let endpoints=match self.server_endpoints.get(target_index){
+            Some(endpoints)=>endpoints.clone(),
+            None=>return Err(MapperError::new(MapperErrorKind::UnexpectedValue,&format!("no endpoints for the target {}, use get_server_certificate first",target_index))),
+        };
+        let (security_mode,security_policy_uri)=(self.security_mode_save,self.security_policy_uri);
+        let mut report=ScanReport{target:socket_addr.clone(),endpoints:Vec::new()};
+        for endpoint in &endpoints{
+            let mut scan=EndpointScan::new(endpoint);
+            if let Some(trust_list)=&self.trust_list{
+                scan.certificate=Some(match endpoint.server_certificate.value.as_deref(){
+                    Some(certificate) if !certificate.is_empty()=>match trust_list.validate(certificate,endpoint.server.application_uri.value().as_deref()){
+                        Ok(())=>Outcome::Accepted,
+                        Err(e)=>Outcome::Rejected(e.to_string()),
+                    },
+                    _=>Outcome::NotTried("no certificate".to_string()),
+                });
+            }
+            match (scan.unsupported(),scan::supported_policy(&scan.security_policy_uri)){
+                (None,Some(policy))=>{
+                    self.security_policy_uri=policy;
+                    self.security_mode=scan.security_mode;
+                    self.security_mode_save=scan.security_mode;
+                    let word=vec![Handle::HELLO,Handle::OPN_REQ,Handle::CLO_REQ];
+                    let outputs=self.send_word(Word::new(&socket_addr,word,target_index,timeout,vec![2]),None).0;
+                    scan.channel=self.scan_outcome(&outputs,1,Handle::R_OPEN_REPOK);
+                    if scan.channel==Outcome::Accepted{
+                        for (token,policy) in scan.tokens.iter_mut().zip(endpoint.user_identity_tokens.iter()){
+                            let active_session=match policy.token_type{
+                                UserTokenType::ANONYMOUS=>Handle::ACTIVE_SESS_ANON,
+                                UserTokenType::USERNAME=>Handle::ACTIVE_SESS,
+                                UserTokenType::CERTIFICATE=>Handle::ACTIVE_SESS_CERT,
+                                _=>{
+                                    token.outcome=Outcome::NotTried("token type not supported".to_string());
+                                    continue;
+                                },
+                            };
+                            self.scan_policy_id=Some(policy.policy_id.clone());
+                            let word=vec![Handle::HELLO,Handle::OPN_REQ,Handle::CREATE_SESS,active_session,Handle::CLOSE_SESS,Handle::CLO_REQ];
+                            let outputs=self.send_word(Word::new(&socket_addr,word,target_index,timeout,vec![5]),None).0;
+                            token.outcome=self.scan_outcome(&outputs,3,Handle::R_ACTIVE_SESS_REPOK);
+                        }
+                        self.scan_policy_id=None;
+                    }
+                },
+                (reason,_)=>scan.channel=Outcome::NotTried(reason.unwrap_or_default()),
+            }
+            report.endpoints.push(scan);
+        }
+        self.security_policy_uri=security_policy_uri;
+        self.security_mode=security_mode;
+        self.security_mode_save=security_mode;
+        Ok(report)
+    }
+
+    ///Analysis of each distinct certificate of the endpoints of the target, compared with the application uri of these endpoints.
+    ///Without endpoints, the certificate received by get_server_certificate is analyzed.
+    pub fn analyze_server_certificates(&self,target_index:usize)->MapperResult<Vec<CertificateReport>>{
+        let mut certificates:Vec<(&ByteString,Option<&str>)>=Vec::new();
+        for endpoint in self.server_endpoints.get(target_index).into_iter().flatten(){
+            if !endpoint.server_certificate.isnull() && !certificates.iter().any(|(c,_)| *c==&endpoint.server_certificate){
+                certificates.push((&endpoint.server_certificate,endpoint.server.application_uri.value().as_deref()));
+            }
+        }
+        if certificates.is_empty(){
+            match self.server_certificate.get(target_index){
+                Some(certificate)=>certificates.push((certificate,None)),
+                None=>return Err(MapperError::new(MapperErrorKind::MissingKey,"no certificate for the target, use get_server_certificate first")),
+            }
+        }
+        certificates.iter().map(|(c,uri)| CertificateReport::analyze(c.value.as_deref().unwrap_or_default(),*uri)).collect()
+    }
+
+    //outcome of the symbol `index` of the last word: accepted when its output is `ok`, otherwise rejected with its status
+    fn scan_outcome(&self,outputs:&[String],index:usize,ok:&str)->Outcome{
+        match outputs.get(index){
+            Some(output) if output==ok=>Outcome::Accepted,
+            output=>match self.service_results.iter().find(|(i,_)| *i==index){
+                Some((_,status))=>Outcome::Rejected(status.to_string()),
+                None=>Outcome::Rejected(output.map_or("no output",|o| o.trim_end_matches(',')).to_string()),
+            },
+        }
+    }
+
     pub fn submit_word(&mut self,socket_addr:String, messages: Vec<&str>,target_index:usize,timeout :u64,nb_messages:Vec<usize>,known_no_resp:Vec<usize>) -> Vec<String> {
+        self.submit_word_mutated(Word{socket_addr,messages,target_index,timeout,nb_messages,known_no_resp},None).0
+    }
+
+    ///Same as submit_word but the last message is replaced by its mutant of index `mutant`.
+    ///The mutant used is returned with the outputs, None is returned when there is no such mutant (the last message is then not sent).
+    pub (crate) fn submit_word_mutated(&mut self,word:Word,mutant:Option<usize>) -> (Vec<String>,Option<Mutation>) {
+        //the word is not sent when the node ids cannot be resolved on this server
+        if let Err(e)=self.resolve_node_ids(&word.socket_addr,word.target_index,word.timeout){
+            println!("failed to resolve the node ids on {}: {:?}",word.socket_addr,e);
+            return (vec![Handle::R_UNRESOLVED.to_string();word.messages.len()],None);
+        }
+        let check=match self.liveness.clone(){
+            Some(c)=>c,
+            None=>return self.send_word(word,mutant),
+        };
+        if check.findings_dir.is_some(){
+            self.capture=Some(Capture::new());
+        }
+        let (socket_addr,messages,target_index)=(word.socket_addr.clone(),word.messages.clone(),word.target_index);
+        let (result,mutation)=self.send_word(word,mutant);
+        let capture=self.capture.take();
+        //the mutant does not exist, the last message has not been sent
+        if mutant.is_some() && mutation.is_none(){
+            return (result,mutation);
+        }
+        let state=self.check_liveness(&socket_addr,target_index,&check);
+        self.last_liveness=Some((liveness::lost_at(&result),state));
+        if let (Liveness::Crash|Liveness::Hang, Some(dir), Some(capture))=(state,&check.findings_dir,&capture){
+            match liveness::save_finding(dir,&socket_addr,state,&messages,&result,&self.parse_errors,&self.service_results,&self.diagnostics,mutation.as_ref(),capture){
+                Ok(path)=>println!("{} saved in {}",state,path.display()),
+                Err(e)=>println!("failed to save the finding: {}",e),
+            }
+        }
+        (result,mutation)
+    }
+
+    ///Responses of the last word that could not be parsed, with the index of the symbol they answer and the
+    ///field that failed to decode (`ParseErr` in the outputs)
+    pub fn parse_errors(&self)->&[(usize,String)]{
+        &self.parse_errors
+    }
+
+    ///State of the server after the last word, with the index of the symbol after which the connection was lost
+    ///(the last symbol if it was not). None when the liveness is not checked. The outputs are left as they are.
+    pub fn liveness(&self)->Option<(usize,Liveness)>{
+        self.last_liveness
+    }
+
+    ///Status of the responses of the last word that are not Good (ServiceFault, NOK responses and Error messages),
+    ///with the index of the symbol they answer
+    pub fn service_results(&self)->&[(usize,StatusCode)]{
+        &self.service_results
+    }
+
+    ///Reason of the Error messages and diagnostics of the responses of the last word, with the index of the symbol they answer.
+    ///The server only sends the diagnostics asked by the return_diagnostic mask, see set_return_diagnostics
+    pub fn diagnostics(&self)->&[(usize,String)]{
+        &self.diagnostics
+    }
+
+    ///Responses of the last word that break a rule of the specification, see the conformance module.
+    ///They do not change the outputs.
+    pub fn conformance_findings(&self)->&[Finding]{
+        &self.conformance_findings
+    }
+
+    ///maximal difference in seconds between the timestamp of a response and the time of the mapper (300 by default)
+    pub fn set_max_clock_skew(&mut self,seconds:u64){
+        self.conformance.max_clock_skew=seconds as i64;
+    }
+
+    ///return_diagnostic mask of the request headers (ReturnDiagnostics bits), 0 by default
+    pub fn set_return_diagnostics(&mut self,mask:u32){
+        self.return_diagnostics=mask;
+    }
+
+    ///check after each word if the server is still alive. Words leading to a crash or a hang are saved in `findings_dir` (if any)
+    pub fn set_liveness_check(&mut self,get_endpoints:bool,timeout:u64,findings_dir:Option<&str>){
+        self.liveness=Some(LivenessCheck::new(get_endpoints,timeout,findings_dir));
+    }
+
+    pub fn disable_liveness_check(&mut self){
+        self.liveness=None;
+    }
+
+    pub fn liveness_check(&self)->Option<&LivenessCheck>{
+        self.liveness.as_ref()
+    }
+
+    ///open a fresh connection and send Hello (and OPN, GetEndpoints and CLO if required) to know if the server is still alive.
+    pub fn check_liveness(&mut self,socket_addr:&str,target_index:usize,check:&LivenessCheck)->Liveness{
+        let sleep_duration = time::Duration::from_millis(100);
+        let mut stream;
+        let mut i=0;
+        loop{
+            if let Ok(s) = TcpStream::connect(socket_addr){
+                stream=s;
+                break;
+            }
+            if i==2{
+                return Liveness::Crash;
+            }
+            i+=1;
+            thread::sleep(sleep_duration);
+        }
+        stream.set_nonblocking(false).expect("set_nonblocking call failed");
+        stream.set_read_timeout(Some(std::time::Duration::from_millis(check.timeout))).unwrap();
+        let mut messages=vec![Handle::HELLO];
+        if check.get_endpoints{
+            messages.extend_from_slice(&[Handle::OPN_REQ,Handle::GET_ENDPOINT_REQ,Handle::CLO_REQ]);
+        }
+        let endpoint_url=self.endpoint_url.get(target_index).cloned().unwrap_or_else(|| UaString::from("opc.tcp://localhost:4840"));
+        let mut security_policy = SecurityPolicy::new(SecurityPolicyUri::None);
+        self.security_mode=MessageSecurityMode::NONE;
+        let channel_timeout:u32= check.timeout as u32+2000;
+        let mut state=Liveness::Alive;
+        for symbol in messages{
+            let msg = match self.translate_from_abstract_to_object(&endpoint_url,symbol,&mut security_policy,&channel_timeout,&2000.0,&ByteString::new(),None,None){
+                Ok(Some(m))=>m,
+                _=> panic!("Unreachable"),
+            };
+            if self.send_opcua(msg, &mut security_policy, &mut stream).is_err(){
+                state=Liveness::Crash;
+                break;
+            }
+            if symbol==Handle::CLO_REQ{
+                break;
+            }
+            match Handle::recv_opcua_response(&mut stream, 8,Some(1)).pop(){
+                Some(OpcUaResponse::Some(buf))=>{
+                    match crate::msg::parser::parse(&buf){
+                        Ok(Msg::ErrorMessage(_))=>break,//the server answers, it might just refuse the security policy none
+                        Ok(m)=>self.update_from_msg(&m,&mut security_policy),
+                        Err(_)=>break,
+                    }
+                },
+                Some(OpcUaResponse::Eof)=>{
+                    state=Liveness::Crash;
+                    break;
+                },
+                _=>{
+                    state=Liveness::Hang;
+                    break;
+                },
+            }
+        }
+        let _ =stream.shutdown(std::net::Shutdown::Both);
+        self.restore_state();
+        state
+    }
+
+    ///limits used to decode the responses of the server
+    pub fn set_decode_limits(&mut self,max_string_length:usize,max_array_length:usize,max_depth:usize,max_message_size:usize){
+        self.decode_limits=DecodeLimits::new(max_string_length,max_array_length,max_depth,max_message_size);
+    }
+
+    fn send_word(&mut self,word:Word,mutant:Option<usize>) -> (Vec<String>,Option<Mutation>) {
+        let Word{socket_addr,messages,target_index,timeout,nb_messages,known_no_resp}=word;
+        self.decode_limits.apply();
+        self.last_liveness=None;
+        self.parse_errors.clear();
+        self.service_results.clear();
+        self.diagnostics.clear();
+        self.conformance_findings.clear();
+        let mut mutation:Option<Mutation>=None;
         let mut result=Vec::with_capacity(messages.len());
         let sleep_duration = time::Duration::from_millis(500);
         let mut stream;
@@ -321,13 +847,14 @@ impl<'a> Handle<'a> {
         let receiver_certificate_thumbprint: ByteString = self.receiver_certificate_thumbprint[target_index].clone();
         let private_key: PrivateKey=self.private_key.clone();
         let private_key_false: PrivateKey=self.private_key_false.clone();
+        let defect_keys: Vec<(Defect,PrivateKey)>=self.defect_credentials.iter().map(|(d,c)| (*d,c.private_key.clone())).collect();
         let endpoint_url: UaString=self.endpoint_url[target_index].clone();
         let mut security_policy: SecurityPolicy;
         // self.security_mode=1;
         if self.security_mode == MessageSecurityMode::NONE{
             security_policy = SecurityPolicy::new(SecurityPolicyUri::None);
         }else{
-            security_policy = SecurityPolicy::new(SecurityPolicyUri::Basic256Sha256);
+            security_policy = SecurityPolicy::new(self.security_policy_uri);
         }
 
         //If the security policy is not none, asymmetric are required
@@ -349,20 +876,48 @@ impl<'a> Handle<'a> {
                 thread::sleep(sleeping_duration);
                 sleep=false;
             }
-            if msg==Handle::CLO_REQ{
+            if Handle::symbol_name(msg)==Handle::CLO_REQ{
                 sleep=true;
             }
             let msg = match self.translate_from_abstract_to_object(&endpoint_url,msg,&mut security_policy,&channel_timeout,&session_timeout,&receiver_certificate_thumbprint,Some(&server_public_key),Some(&server_certificate)){
-                Some(m)=>m,
-                _=> {result.push(Handle::R_INTERNAL_UPDATE.to_string());
+                Ok(Some(m))=>m,
+                Ok(None)=> {result.push(Handle::R_INTERNAL_UPDATE.to_string());
+                    continue},
+                Err(_)=> {result.push(Handle::R_INVALID_SYMBOL.to_string());
                     continue},
             };
             if self.security_false && !security_policy_changed && self.security_policy_uri != SecurityPolicyUri::None{
                 // security_policy=security_policy_false;
-                security_policy.set_asym(&private_key_false, &server_public_key);
+                let false_key=match self.channel_defect{
+                    Some(defect)=>defect_keys.iter().find(|(d,_)| *d==defect).map(|(_,k)| k).unwrap_or(&private_key_false),
+                    None=>&private_key_false,
+                };
+                security_policy.set_asym(false_key, &server_public_key);
                 security_policy_changed=true;
             }
-            let ret : std::io::Result<usize>= self.send_opcua(msg, &mut security_policy, &mut stream);
+            //the request handle of a mutated request is unknown
+            let sent=match mutant{
+                Some(_) if cpt==messages_len-1=>None,
+                _=>Some(conformance::Sent::new(&msg,&security_policy)),
+            };
+            let ret : std::io::Result<usize>= match mutant{
+                Some(k) if cpt==messages_len-1=>{
+                    mutation=msg.mutations().into_iter().nth(k);
+                    match &mutation{
+                        Some(m)=>{
+                            self.update_internal(&msg);
+                            let null_size=matches!(msg,Msg::NullSize(_));
+                            self.send_opcua_bytes(m.bytes.clone(), null_size, &mut security_policy, &mut stream)
+                        },
+                        None=>{
+                            let _ =stream.shutdown(std::net::Shutdown::Both);
+                            self.restore_state();
+                            return (result,None);
+                        },
+                    }
+                },
+                _=>self.send_opcua(msg, &mut security_policy, &mut stream),
+            };
             match ret{
                 Err(_)=> {
                     let len=messages_len-result.len();
@@ -371,7 +926,7 @@ impl<'a> Handle<'a> {
                     }
                     let _ =stream.shutdown(std::net::Shutdown::Both);
                     self.restore_state();
-                    return result;
+                    return (result,mutation);
                 },
                 Ok(_)=>{}
             };
@@ -396,7 +951,7 @@ impl<'a> Handle<'a> {
                             }
                             let _ =stream.shutdown(std::net::Shutdown::Both);
                             self.restore_state();
-                            return result;
+                            return (result,mutation);
                         },
                         OpcUaResponse::NoResp=>{
                             if to_push.len()==0{
@@ -404,15 +959,34 @@ impl<'a> Handle<'a> {
                             }
                         },
                         OpcUaResponse::Some(mut buf)=>{
+                            if let Some(capture)=self.capture.as_mut(){
+                                capture.record(Direction::Received,&buf);
+                            }
                             let _=msg::decrypt_msg(self.security_mode,&mut security_policy, &mut buf);
                             let msg = crate::msg::parser::parse(&buf);
                             match msg{
                                 Ok(m)=>{
+                                    if let Some(status)=m.service_result().filter(|s| s.get_value()!=StatusCode::Good){
+                                        self.service_results.push((cpt,status));
+                                    }
+                                    if let Some(diagnostic)=m.diagnostic(){
+                                        self.diagnostics.push((cpt,diagnostic));
+                                    }
+                                    let findings=self.conformance.check(target_index,cpt,sent.as_ref(),&m);
+                                    self.conformance_findings.extend(findings);
                                     self.update_from_msg(&m,&mut security_policy);
-                                    to_push.push_str(&Handle::update_response(&m));
+                                    let write_fields_response=if self.write_fields_requested {Handle::write_fields_response(&m)} else {None};
+                                    if self.items_requested{
+                                        to_push.push_str(&Handle::update_items_response(&m));
+                                    }else if let Some(output)=write_fields_response{
+                                        to_push.push_str(&output);
+                                    }else{
+                                        to_push.push_str(&self.output_abstraction.output(&m));
+                                    }
                                     
                                 },
-                                Err(_)=>{ 
+                                Err(e)=>{ 
+                                    self.parse_errors.push((cpt,format!("{:?}",e)));
                                     to_push.push_str(&Handle::R_PARSE_ERR.to_string());
                                 },
                             };
@@ -426,16 +1000,33 @@ impl<'a> Handle<'a> {
         }
         let _ =stream.shutdown(std::net::Shutdown::Both);
         self.restore_state();
-        result
+        (result,mutation)
     
     }
 
     fn restore_state(&mut self){
         self.security_mode=self.security_mode_save;
         self.security_false=false;
+        self.channel_defect=None;
+    }
+
+    //credentials of a certificate defect, they are generated with the certificates of the handle (see crate::crypto::certgen), check_symbol rejects the symbols of missing defects
+    fn defect_credentials(&self,defect:Defect)->&DefectCredentials{
+        match self.defect_credentials.iter().find(|(d,_)| *d==defect){
+            Some((_,credentials))=>credentials,
+            None=>panic!("no certificate with the defect {}, the certificates of the mapper must be generated",defect.name()),
+        }
+    }
+
+    //chain of the certificate of the private key, it is generated with the certificates of the handle, check_symbol rejects the symbols of missing chains
+    fn chain(&self,chain:Chain)->&ByteString{
+        match self.chains.iter().find(|(c,_)| *c==chain){
+            Some((_,certificates))=>certificates,
+            None=>panic!("no certificate chain {}, the certificates of the mapper must be generated",chain.name()),
+        }
     }
     ///when the amount of message is know we can use nb_message to receive precisely nb_messages
-    fn recv_opcua_response(stream: &mut TcpStream, buffer_size: usize,nb_message:Option<usize>) -> Vec<OpcUaResponse> {
+    pub (crate) fn recv_opcua_response(stream: &mut TcpStream, buffer_size: usize,nb_message:Option<usize>) -> Vec<OpcUaResponse> {
         let mut cpt:isize=0; 
         let nb_message=match nb_message{
             Some(n)=>n as isize,
@@ -464,10 +1055,37 @@ impl<'a> Handle<'a> {
             }
 
             let size = crate::msg::get_size(&buffer).unwrap();
+            //the size comes from the server, it is bounded before allocating the message
+            if DecodeLimits::check_message_size(size as usize).is_err(){
+                result.push(OpcUaResponse::Some(buffer));
+                //the rest of the message is skipped so that the next read starts on a header,
+                //the connection is closed when it cannot be skipped
+                let rest=u64::from(size).saturating_sub(buffer_size as u64);
+                match std::io::copy(&mut (&*stream).take(rest),&mut std::io::sink()){
+                    Ok(skipped) if skipped==rest=>{},
+                    _=>{
+                        let _ =stream.shutdown(std::net::Shutdown::Both);
+                        result.push(OpcUaResponse::Eof);
+                        break;
+                    },
+                }
+                cpt+=1;
+                continue;
+            }
             if size > buffer_size as u32 {
                 let mut msg = buffer.to_vec();
                 let mut buffer = vec![0; size as usize - buffer_size];
-                stream.read_exact(&mut buffer).unwrap();
+                match stream.read_exact(&mut buffer){
+                    Ok(_)=>{},
+                    Err(e) if e.kind()==ErrorKind::UnexpectedEof=> {
+                        result.push(OpcUaResponse::Eof);
+                        break;
+                    },
+                    Err(_)=> {
+                        result.push(OpcUaResponse::NoResp);
+                        break;
+                    },
+                }
                 msg.extend_from_slice(&buffer);
                 result.push(OpcUaResponse::Some(msg));
             } else {
@@ -481,20 +1099,26 @@ impl<'a> Handle<'a> {
 
     fn send_opcua(&mut self,msg: Msg, security_policy: &mut SecurityPolicy, stream: &mut TcpStream) -> std::io::Result<usize> {
         self.update_internal(&msg);
-        let mut buffer_to_send=msg.serialize();
-        //update present
-        if let Msg::NullSize(_)=msg{
-            self.pre_send_update(&mut buffer_to_send);
-            let _=msg::set_padding_signature(self.security_mode,security_policy,&mut buffer_to_send);
-            let _=msg::encrypt_msg(self.security_mode,security_policy, &mut buffer_to_send);
-            msg::set_size_custom(&mut buffer_to_send, 0 as usize);
-            stream.write(&buffer_to_send)
-        }else{
-            self.pre_send_update(&mut buffer_to_send);
-            let _=msg::set_padding_signature(self.security_mode,security_policy,&mut buffer_to_send);
-            let _=msg::encrypt_msg(self.security_mode,security_policy, &mut buffer_to_send);
-            stream.write(&buffer_to_send)
+        let null_size=matches!(msg,Msg::NullSize(_));
+        self.send_opcua_bytes(msg.serialize(), null_size, security_policy, stream)
+    }
+
+    ///sign, encrypt and send an already encoded message, with a null size in the header of a NullSize message (and its mutants)
+    fn send_opcua_bytes(&mut self,mut buffer_to_send: Vec<u8>, null_size: bool, security_policy: &mut SecurityPolicy, stream: &mut TcpStream) -> std::io::Result<usize> {
+        self.pre_send_update(&mut buffer_to_send);
+        let _=msg::set_padding_signature(self.security_mode,security_policy,&mut buffer_to_send);
+        let _=msg::encrypt_msg(self.security_mode,security_policy, &mut buffer_to_send);
+        if null_size{
+            msg::set_size_custom(&mut buffer_to_send, 0_usize);
+        }
+        self.write_opcua(&buffer_to_send, stream)
+    }
+
+    fn write_opcua(&mut self,buffer_to_send: &[u8], stream: &mut TcpStream) -> std::io::Result<usize> {
+        if let Some(capture)=self.capture.as_mut(){
+            capture.record(Direction::Sent,buffer_to_send);
         }
+        stream.write(buffer_to_send)
     }
 
     pub (crate) fn update_from_msg(&mut self, msg: &Msg,security_policy:&mut  SecurityPolicy) {
@@ -514,13 +1138,14 @@ impl<'a> Handle<'a> {
 
             },
             Msg::OpenSecureChannelResponse(m) => {
-                self.secure_channel_id = m.secure_channel_id;
-                self.token_id = m.token_id;
+                self.secure_channel_id = m.security_token.channel_id;
+                self.token_id = m.security_token.token_id;
                 if self.security_mode!=MessageSecurityMode::NONE{
                     self.server_nonce= m.server_nonce.clone();
                     
                     //derive key
-                    security_policy.derive_symmetric_client(&self.client_nonce, &self.server_nonce).expect("haaaaaaaa");
+                    //a malformed nonce leaves the keys underived, following messages are then rejected by the server
+                    let _ = security_policy.derive_symmetric_client(&self.client_nonce, &self.server_nonce);
                 }
 
             },
@@ -533,14 +1158,22 @@ impl<'a> Handle<'a> {
                 //derive key
                 self.server_nonce= m.server_nonce.clone();
                 self.authentication_token= m.authentication_token.clone();
-                let endpoint_array=m.endpoints.clone();
+                let endpoint_array=m.server_endpoints.clone();
                 for i in endpoint_array{
                     //we check if we have security policies
-                    if security_policy.policy_uri!=i.security_policy_uri.value().as_ref().unwrap() || i.security_mode!=self.security_mode{
+                    if i.security_policy_uri.value().as_deref()!=Some(security_policy.policy_uri) || i.security_mode!=self.security_mode{
                         continue;
                     }
+                    if let Some(policy_id)=&self.scan_policy_id{
+                        if !i.user_identity_tokens.iter().any(|t| &t.policy_id==policy_id){
+                            continue;
+                        }
+                    }
                     //we have the right security policy so we want to add the uri of credentials
                     for user_token_policy in &i.user_identity_tokens{
+                        if self.scan_policy_id.as_ref().is_some_and(|id| id!=&user_token_policy.policy_id){
+                            continue;
+                        }
                         if user_token_policy.token_type==UserTokenType::USERNAME{
                             self.policy_id_user=user_token_policy.policy_id.clone();
                             let sec_uri:&str;
@@ -573,6 +1206,14 @@ impl<'a> Handle<'a> {
                 }
 
             },
+            Msg::ReadResponse(m) if self.reading_namespace_array => {
+                self.reading_namespace_array=false;
+                let namespaces=m.results.iter().next().and_then(|v| v.value.as_ref()).map(|v| v.value.iter().filter_map(|n| match n{
+                    Value::String(uri)=>Some(uri.clone()),
+                    _=>None,
+                }).collect());
+                self.namespace_array_read=namespaces;
+            },
             _ => {}
         }
     }
@@ -600,10 +1241,10 @@ impl<'a> Handle<'a> {
                 }
                 Handle::R_OPEN_REPOK.to_string()
             },
-            Msg::GetEndPointsRequest(_m)=>{
+            Msg::GetEndpointsRequest(_m)=>{
                 Handle::R_GEP_REQ.to_string()
             },
-            Msg::GetEndPointsResponse(m)=>{
+            Msg::GetEndpointsResponse(m)=>{
                 if m.response_header.service_result.get_value()!=StatusCode::Good{
                     return Handle::R_GEP_REPNOK.to_string()
                 }
@@ -633,10 +1274,10 @@ impl<'a> Handle<'a> {
                 }
                 Handle::R_CLO_SESS_REPOK.to_string()
             },
-            Msg::ActiveSessionRequest(_m)=>{
+            Msg::ActivateSessionRequest(_m)=>{
                 Handle::R_ACTIVE_SESS_REQ.to_string()
             },
-            Msg::ActiveSessionResponse(m)=>{
+            Msg::ActivateSessionResponse(m)=>{
                 if m.response_header.service_result.get_value()!=StatusCode::Good{
                     return Handle::R_ACTIVE_SESS_REPNOK.to_string()
                 }
@@ -663,10 +1304,47 @@ impl<'a> Handle<'a> {
                 }
                 Handle::R_WRITE_REPOK.to_string()
             },
+            //the other services have no symbol, they are named after their message: BrowseReq, BrowseRepOK...
+            _=>{
+                let name=msg.name().replace("Request","Req").replace("Response","Rep");
+                match msg.service_result(){
+                    Some(status) if status.get_value()!=StatusCode::Good=>format!("{}NOK,",name),
+                    Some(_)=>format!("{}OK,",name),
+                    None=>format!("{},",name),
+                }
+            },
+        }
+    }
+
 
+    ///output of the response to write_req_status, write_req_source...: the service may succeed while the write is rejected,
+    ///e.g. BadWriteNotSupported for a status or a timestamp, the result is then given: `WriteRepBadWriteNotSupported,`.
+    ///None for the other responses, they have the output of the abstraction.
+    pub (crate) fn write_fields_response(msg: &Msg)->Option<String> {
+        match msg{
+            Msg::WriteResponse(m) if m.response_header.service_result.get_value()==StatusCode::Good=>{
+                let result=m.results.iter().map(|r| StatusCode::new(*r)).find(StatusCode::is_bad)?;
+                Some(format!("{}{},",Handle::R_WRITE_REP,result))
+            },
+            _=>None,
         }
     }
 
+    ///output of the response to read_items or write_items with the result of each item: `ReadRepOK[Good|BadUserAccessDenied],`.
+    ///The results are separated by `|` since the outputs of a symbol are separated by commas.
+    pub (crate) fn update_items_response(msg: &Msg)->String {
+        let (output,results):(&str,Vec<u32>)=match msg{
+            Msg::ReadResponse(m) if m.response_header.service_result.get_value()==StatusCode::Good=>{
+                (Handle::R_READ_REPOK,m.results.iter().map(|v| v.status.unwrap_or(StatusCode::Good)).collect())
+            },
+            Msg::WriteResponse(m) if m.response_header.service_result.get_value()==StatusCode::Good=>{
+                (Handle::R_WRITE_REPOK,m.results.iter().copied().collect())
+            },
+            _=>return Handle::update_response(msg),
+        };
+        let results:Vec<String>=results.iter().map(|r| StatusCode::new(*r).to_string()).collect();
+        format!("{}[{}],",output.trim_end_matches(','),results.join("|"))
+    }
 
     pub fn pre_send_update(&mut self, msg:&mut Vec<u8>){
         let msg_type=msg::get_type(msg);
@@ -717,8 +1395,104 @@ impl<'a> Handle<'a> {
         }
     }
 
-    pub (crate) fn translate_from_abstract_to_object(&mut self,endpoint_url: &UaString, message: &str, security_policy:&mut  SecurityPolicy,channel_timeout:&u32,session_timeout:&f64,receiver_certificate_thumbprint:&ByteString, server_public_key:Option<&PublicKey>,server_certificate:Option<&ByteString>) -> Option<Msg> {
-        match message {
+    ///name of an abstract symbol, without its additional parameters
+    pub (crate) fn symbol_name(symbol:&str)->&str{
+        symbol.split('[').next().unwrap_or(symbol)
+    }
+
+    ///An abstract symbol may give the parameters of the additional header of its request: `read_req[ECDHPolicyUri=uri;Count:6=42]`.
+    ///See AdditionalParametersType::parse for the syntax of the parameters.
+    pub (crate) fn split_symbol(symbol:&str)->MapperResult<(&str,Option<AdditionalParametersType>)>{
+        match symbol.strip_suffix(']').and_then(|s| s.split_once('[')){
+            Some((name,parameters))=>match AdditionalParametersType::parse(parameters){
+                Ok(parameters)=>Ok((name,Some(parameters))),
+                Err(e)=>Err(MapperError::new(MapperErrorKind::UnexpectedValue,&format!("invalid additional parameters in {}: {:?}",symbol,e))),
+            },
+            None=>Ok((symbol,None)),
+        }
+    }
+
+    ///Check that a symbol can be sent: its name is known, its additional parameters are valid and its message has a request header.
+    ///The certificate defect and chain symbols need the certificates generated by the mapper.
+    pub fn check_symbol(&self,symbol:&str)->MapperResult<()>{
+        let (name,parameters)=Handle::split_symbol(symbol)?;
+        let defect=name.strip_prefix(Handle::OPN_CERT).or_else(|| name.strip_prefix(Handle::ACTIVE_SESS_USER_CERT)).and_then(Defect::from_name);
+        let chain=name.strip_prefix(Handle::OPN_CHAIN).or_else(|| name.strip_prefix(Handle::CREATE_SESS_CHAIN)).and_then(Chain::from_name);
+        if !Handle::SYMBOLS.contains(&name) && Handle::write_fields(name).is_none() && defect.is_none() && chain.is_none(){
+            return Err(MapperError::new(MapperErrorKind::UnexpectedValue,&format!("unknown symbol {}",symbol)));
+        }
+        if parameters.is_some() && matches!(name,Handle::HELLO|Handle::SET_SEC_MODE_NONE){
+            return Err(MapperError::new(MapperErrorKind::UnexpectedValue,&format!("{} has no request header for additional parameters",name)));
+        }
+        if let Some(defect)=defect{
+            if !self.defect_credentials.iter().any(|(d,_)| *d==defect){
+                return Err(MapperError::new(MapperErrorKind::MissingKey,&format!("no certificate with the defect {} in {}, the certificates of the mapper must be generated",defect.name(),name)));
+            }
+        }
+        if let Some(chain)=chain{
+            if !self.chains.iter().any(|(c,_)| *c==chain){
+                return Err(MapperError::new(MapperErrorKind::MissingKey,&format!("no certificate chain {} in {}, the certificates of the mapper must be generated",chain.name(),name)));
+            }
+        }
+        Ok(())
+    }
+
+    ///None is returned for the symbols that only update the state of the mapper, an error for the symbols that cannot be sent (see check_symbol).
+    pub (crate) fn translate_from_abstract_to_object(&mut self,endpoint_url: &UaString, message: &str, security_policy:&mut  SecurityPolicy,channel_timeout:&u32,session_timeout:&f64,receiver_certificate_thumbprint:&ByteString, server_public_key:Option<&PublicKey>,server_certificate:Option<&ByteString>) -> MapperResult<Option<Msg>> {
+        self.check_symbol(message)?;
+        let (message,parameters)=Handle::split_symbol(message)?;
+        let mut msg=match self.translate_symbol(endpoint_url,message,security_policy,channel_timeout,session_timeout,receiver_certificate_thumbprint,server_public_key,server_certificate)?{
+            Some(m)=>m,
+            None=>return Ok(None),
+        };
+        if let Some(header)=msg.request_header_mut(){
+            header.return_diagnostic=self.return_diagnostics;
+            if let Some(parameters)=parameters{
+                header.additional_header=parameters.to_extension_object();
+            }
+        }
+        Ok(Some(msg))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn translate_symbol(&mut self,endpoint_url: &UaString, message: &str, security_policy:&mut  SecurityPolicy,channel_timeout:&u32,session_timeout:&f64,receiver_certificate_thumbprint:&ByteString, server_public_key:Option<&PublicKey>,server_certificate:Option<&ByteString>) -> MapperResult<Option<Msg>> {
+        self.items_requested=message==Handle::READ_ITEMS || message==Handle::WRITE_ITEMS;
+        self.write_fields_requested=Handle::write_fields(message).is_some_and(|fields| fields!=0);
+        if let Some(fields)=Handle::write_fields(message){
+            let data_value=self.target_node_value.with_fields(fields);
+            return Ok(Some(Msg::WriteRequest(WriteRequest::build(&self.authentication_token,&self.target_node,&data_value))));
+        }
+        if let Some(defect)=message.strip_prefix(Handle::OPN_CERT).and_then(Defect::from_name){
+            self.security_false=true;
+            self.channel_defect=Some(defect);
+            return Ok(Some(Msg::OpenSecureChannelRequest(OpenSecureChannelRequest::build(
+                &self.defect_credentials(defect).certificate,
+                receiver_certificate_thumbprint,
+                self.security_mode,
+                security_policy,
+                *channel_timeout,
+            ))));
+        }
+        if let Some(chain)=message.strip_prefix(Handle::OPN_CHAIN).and_then(Chain::from_name){
+            return Ok(Some(Msg::OpenSecureChannelRequest(OpenSecureChannelRequest::build(
+                self.chain(chain),
+                receiver_certificate_thumbprint,
+                self.security_mode,
+                security_policy,
+                *channel_timeout,
+            ))));
+        }
+        if let Some(chain)=message.strip_prefix(Handle::CREATE_SESS_CHAIN).and_then(Chain::from_name){
+            return Ok(Some(Msg::CreateSessionRequest(CreateSessionRequest::build(endpoint_url,self.chain(chain),*session_timeout))));
+        }
+        if let Some(defect)=message.strip_prefix(Handle::ACTIVE_SESS_USER_CERT).and_then(Defect::from_name){
+            let credentials=self.defect_credentials(defect);
+            if let (Some(server_public_key),Some(server_certificate)) = (server_public_key,server_certificate){
+                return Ok(Some(Msg::ActivateSessionRequest(ActivateSessionRequest::build(server_public_key,&credentials.private_key,&self.server_nonce,server_certificate,&credentials.user_certificate,&self.authentication_token, security_policy,self.security_policy_uri_token_cert,&self.policy_id_cert,false,false,true))));
+            }
+            panic!("you must obtain a certificate for you target before sending messages to her. Please use get certificate")
+        }
+        let msg=match message {
             Handle::HELLO => Some(Msg::HelloMessage(HelloMessage::build(endpoint_url))),
             Handle::OPN_REQ => Some(Msg::OpenSecureChannelRequest(OpenSecureChannelRequest::build(
                 &self.sender_certificate,
@@ -747,7 +1521,7 @@ impl<'a> Handle<'a> {
                 )))
             }
             Handle::CLO_REQ => Some(Msg::CloseSecureChannelRequest(CloseSecureChannelRequest::build())),
-            Handle::GET_ENDPOINT_REQ => Some(Msg::GetEndPointsRequest(GetEndPointsRequest::build(
+            Handle::GET_ENDPOINT_REQ => Some(Msg::GetEndpointsRequest(GetEndpointsRequest::build(
                 self.secure_channel_id,
                 self.token_id,
             ))),
@@ -763,7 +1537,7 @@ impl<'a> Handle<'a> {
             }
             Handle::ACTIVE_SESS => {
                 if let (Some(server_public_key),Some(server_certificate)) = (server_public_key,server_certificate){
-                    Some(Msg::ActiveSessionRequest(ActiveSessionRequest::build(server_public_key,&self.private_key,&self.server_nonce,server_certificate,&self.user_certificate,&self.authentication_token, security_policy,self.security_policy_uri_token_user,&self.policy_id_user,false,true,false)))
+                    Some(Msg::ActivateSessionRequest(ActivateSessionRequest::build(server_public_key,&self.private_key,&self.server_nonce,server_certificate,&self.user_certificate,&self.authentication_token, security_policy,self.security_policy_uri_token_user,&self.policy_id_user,false,true,false)))
                 }else{
                     panic!("you must obtain a certificate for you target before sending messages to her. Please use get certificate")
                 }
@@ -773,7 +1547,7 @@ impl<'a> Handle<'a> {
                 self.shift_secure_token_id=true;
                 
                 if let (Some(server_public_key),Some(server_certificate)) = (server_public_key,server_certificate){
-                    Some(Msg::ActiveSessionRequest(ActiveSessionRequest::build(server_public_key,&self.private_key,&self.server_nonce,server_certificate,&self.user_certificate,&self.authentication_token, security_policy,self.security_policy_uri_token_user,&self.policy_id_user,false,true,false)))
+                    Some(Msg::ActivateSessionRequest(ActivateSessionRequest::build(server_public_key,&self.private_key,&self.server_nonce,server_certificate,&self.user_certificate,&self.authentication_token, security_policy,self.security_policy_uri_token_user,&self.policy_id_user,false,true,false)))
                 }else{
                     panic!("you must obtain a certificate for you target before sending messages to her. Please use get certificate")
                 }
@@ -784,7 +1558,7 @@ impl<'a> Handle<'a> {
             },
             Handle::ACTIVE_SESS_ANON =>{
                 if let (Some(server_public_key),Some(server_certificate)) = (server_public_key,server_certificate){
-                    Some(Msg::ActiveSessionRequest(ActiveSessionRequest::build(server_public_key,&self.private_key,&self.server_nonce,server_certificate,&self.user_certificate,&self.authentication_token, security_policy,self.security_policy_uri_token_anon,&self.policy_id_anon,true,false,false)))
+                    Some(Msg::ActivateSessionRequest(ActivateSessionRequest::build(server_public_key,&self.private_key,&self.server_nonce,server_certificate,&self.user_certificate,&self.authentication_token, security_policy,self.security_policy_uri_token_anon,&self.policy_id_anon,true,false,false)))
                 }else{
                     panic!("you must obtain a certificate for you target before sending messages to her. Please use get certificate")
                 }
@@ -792,7 +1566,7 @@ impl<'a> Handle<'a> {
             },
             Handle::ACTIVE_SESS_WRONG_USER =>{
                 if let (Some(server_public_key),Some(server_certificate)) = (server_public_key,server_certificate){
-                    Some(Msg::ActiveSessionRequest(ActiveSessionRequest::build(server_public_key,&self.private_key,&self.server_nonce,server_certificate,&self.user_certificate,&self.authentication_token, security_policy,self.security_policy_uri_token_user,&self.policy_id_user,false,false,false)))
+                    Some(Msg::ActivateSessionRequest(ActivateSessionRequest::build(server_public_key,&self.private_key,&self.server_nonce,server_certificate,&self.user_certificate,&self.authentication_token, security_policy,self.security_policy_uri_token_user,&self.policy_id_user,false,false,false)))
                 }else{
                     panic!("you must obtain a certificate for you target before sending messages to her. Please use get certificate")
                 }
@@ -800,7 +1574,7 @@ impl<'a> Handle<'a> {
             },
             Handle::ACTIVE_SESS_CERT =>{
                 if let (Some(server_public_key),Some(server_certificate)) = (server_public_key,server_certificate){
-                    Some(Msg::ActiveSessionRequest(ActiveSessionRequest::build(server_public_key,&self.private_key,&self.server_nonce,server_certificate,&self.user_certificate,&self.authentication_token, security_policy,self.security_policy_uri_token_cert,&self.policy_id_cert,false,false,true)))
+                    Some(Msg::ActivateSessionRequest(ActivateSessionRequest::build(server_public_key,&self.private_key,&self.server_nonce,server_certificate,&self.user_certificate,&self.authentication_token, security_policy,self.security_policy_uri_token_cert,&self.policy_id_cert,false,false,true)))
                 }else{
                     panic!("you must obtain a certificate for you target before sending messages to her. Please use get certificate")
                 }
@@ -808,7 +1582,7 @@ impl<'a> Handle<'a> {
             },
             Handle::ACTIVE_SESS_WRONG_CERT =>{
                 if let (Some(server_public_key),Some(server_certificate)) = (server_public_key,server_certificate){
-                    Some(Msg::ActiveSessionRequest(ActiveSessionRequest::build(server_public_key,&self.private_key_false,&self.server_nonce,server_certificate,&self.user_false_certificate,&self.authentication_token, security_policy,self.security_policy_uri_token_cert,&self.policy_id_cert,false,false,true)))
+                    Some(Msg::ActivateSessionRequest(ActivateSessionRequest::build(server_public_key,&self.private_key_false,&self.server_nonce,server_certificate,&self.user_false_certificate,&self.authentication_token, security_policy,self.security_policy_uri_token_cert,&self.policy_id_cert,false,false,true)))
                 }else{
                     panic!("you must obtain a certificate for you target before sending messages to her. Please use get certificate")
                 }  
@@ -816,8 +1590,15 @@ impl<'a> Handle<'a> {
             Handle::READ_REQ =>{
                 Some(Msg::ReadRequest(ReadRequest::build(&self.authentication_token,&self.target_node)))
             },
-            Handle::WRITE_REQ =>{
-                Some(Msg::WriteRequest(WriteRequest::build(&self.authentication_token,&self.target_node,&self.target_node_value)))
+            Handle::READ_ITEMS =>{
+                Some(Msg::ReadRequest(ReadRequest::build_items(&self.authentication_token,&self.read_items)))
+            },
+            Handle::WRITE_ITEMS =>{
+                Some(Msg::WriteRequest(WriteRequest::build_items(&self.authentication_token,&self.write_items)))
+            },
+            Handle::READ_NAMESPACE_ARRAY =>{
+                self.reading_namespace_array=true;
+                Some(Msg::ReadRequest(ReadRequest::build(&self.authentication_token,&NodeId::new_numeric(0,VariableIds::Server_NamespaceArray))))
             },
             Handle::NULL_SIZE=>{
                 Some(Msg::NullSize(NullSize::build(
@@ -827,8 +1608,9 @@ impl<'a> Handle<'a> {
                     *channel_timeout,
                 )))
             }
-            _ => panic!("unreconized message"),
-        }
+            _ => return Err(MapperError::new(MapperErrorKind::UnexpectedValue,&format!("unknown symbol {}",message))),
+        };
+        Ok(msg)
     }
 
 }
@@ -837,26 +1619,86 @@ impl<'a> Handle<'a> {
 pub (crate) mod python {
     extern crate cpython;
     use std::cell::RefCell;
-    use cpython::{py_class, FromPyObject, PyResult, Python};
+    use cpython::{exc, py_class, FromPyObject, PyBytes, PyErr, PyList, PyObject, PyResult, Python, PythonObject, ToPyObject};
+    use crate::crypto::certgen::{CertificateAuthority, Chain, ClientCertificates, Defect, DefectCertificates};
     use super::Handle;
 
     pub struct RefCellHandle(pub(crate) RefCell<Handle<'static>>);
+    //keys and certificates given by their path or their DER content (bytes), optionally followed by the certificates of the defects
+    //(list of (defect name, key, certificate, user certificate) given by generate_certificates) and the chains (list of (chain name, chain)),
+    //and by the security mode
     impl<'a> FromPyObject<'a> for RefCellHandle {
         fn extract(py: Python, obj: &'a cpython::PyObject) -> PyResult<Self> {
             let arg = obj.extract::<cpython::PyTuple>(py)?;
-            let key_path = arg.get_item(py, 0).extract::<String>(py)?;//"uaexpert_key.der";
-            let false_key_path = arg.get_item(py, 1).extract::<String>(py)?;//"uaexpert_key.der";
-            let own_cert_path = arg.get_item(py, 2).extract::<String>(py)?;//"cert_server_ctt.der";
-            let own_false_cert_path = arg.get_item(py, 3).extract::<String>(py)?;//"cert_server_ctt.der";
-            let usr_cert_path = arg.get_item(py, 4).extract::<String>(py)?;//"cert_server_ctt.der";
-            let usr_false_cert_path = arg.get_item(py, 5).extract::<String>(py)?;//"cert_server_ctt.der";
-            let security_mode = arg.get_item(py, 6).extract::<u32>(py)?;//"cert_server_ctt.der";
+            let content = |index:usize| -> PyResult<Vec<u8>> {
+                let item = arg.get_item(py, index);
+                if let Ok(data) = item.extract::<PyBytes>(py) {
+                    return Ok(data.data(py).to_vec());
+                }
+                let path = item.extract::<String>(py)?;
+                std::fs::read(&path).map_err(|e| PyErr::new::<exc::IOError, _>(py, format!("{}: {}", path, e)))
+            };
+            let certificates = ClientCertificates {
+                private_key: content(0)?,//"uaexpert_key.der"
+                false_private_key: content(1)?,
+                certificate: content(2)?,//"uaexpert.der"
+                false_certificate: content(3)?,
+                user_certificate: content(4)?,
+                user_false_certificate: content(5)?,
+                defects: Vec::new(),
+                chains: Vec::new(),
+            };
+            let mut certificates = certificates;
+            if arg.len(py) > 7 {
+                for (name, private_key, certificate, user_certificate) in arg.get_item(py, 6).extract::<Vec<(String, PyBytes, PyBytes, PyBytes)>>(py)? {
+                    let defect = Defect::from_name(&name).ok_or_else(|| PyErr::new::<exc::ValueError, _>(py, format!("unknown certificate defect {}", name)))?;
+                    certificates.defects.push((defect, DefectCertificates {
+                        private_key: private_key.data(py).to_vec(),
+                        certificate: certificate.data(py).to_vec(),
+                        user_certificate: user_certificate.data(py).to_vec(),
+                    }));
+                }
+            }
+            if arg.len(py) > 8 {
+                for (name, chain) in arg.get_item(py, 7).extract::<Vec<(String, PyBytes)>>(py)? {
+                    let chain_name = Chain::from_name(&name).ok_or_else(|| PyErr::new::<exc::ValueError, _>(py, format!("unknown certificate chain {}", name)))?;
+                    certificates.chains.push((chain_name, chain.data(py).to_vec()));
+                }
+            }
+            let security_mode = arg.get_item(py, arg.len(py) - 1).extract::<u32>(py)?;
             Ok(RefCellHandle(RefCell::new(
-                Handle::new_basic256_sha256(&key_path,&false_key_path,&own_cert_path,&own_false_cert_path,&usr_cert_path,&usr_false_cert_path,security_mode),
+                Handle::from_certificates(&certificates,security_mode),
             )))
         }
     }
 
+    ///Keys and certificates issued by a new CA for the mapper, the false ones with the defects (names separated by commas):
+    ///[key, false key, certificate, false certificate, user certificate, user false certificate, CA certificate, CA revocation list,
+    ///list of (defect name, key, certificate, user certificate) for each defect, list of (chain name, chain) for each chain,
+    ///intermediate CA certificate, intermediate CA revocation list].
+    ///The first six, the list of the defects and the list of the chains are the arguments of Mapper.
+    pub fn generate_certificates(py: Python, application_uri: String, host: String, defects: String) -> PyResult<PyList> {
+        let value_error = |e: crate::MapperError| PyErr::new::<exc::ValueError, _>(py, format!("{:?}", e));
+        let defects = Defect::parse_list(&defects).map_err(value_error)?;
+        let mut ca = CertificateAuthority::new("mapper CA").map_err(value_error)?;
+        let mut intermediate = ca.intermediate("mapper intermediate CA").map_err(value_error)?;
+        let c = ClientCertificates::generate(&mut ca, &mut intermediate, &application_uri, &host, &defects).map_err(value_error)?;
+        let (ca_certificate, crl) = (ca.certificate_der().map_err(value_error)?, ca.crl().map_err(value_error)?);
+        let mut generated: Vec<PyObject> = [&c.private_key, &c.false_private_key, &c.certificate, &c.false_certificate, &c.user_certificate, &c.user_false_certificate, &ca_certificate, &crl]
+            .iter()
+            .map(|d| PyBytes::new(py, d).into_object())
+            .collect();
+        let defects: Vec<PyObject> = c.defects.iter()
+            .map(|(defect, d)| (defect.name(), PyBytes::new(py, &d.private_key), PyBytes::new(py, &d.certificate), PyBytes::new(py, &d.user_certificate)).to_py_object(py).into_object())
+            .collect();
+        generated.push(PyList::new(py, &defects).into_object());
+        let chains: Vec<PyObject> = c.chains.iter().map(|(chain, d)| (chain.name(), PyBytes::new(py, d)).to_py_object(py).into_object()).collect();
+        generated.push(PyList::new(py, &chains).into_object());
+        generated.push(PyBytes::new(py, &intermediate.certificate_der().map_err(value_error)?).into_object());
+        generated.push(PyBytes::new(py, &intermediate.crl().map_err(value_error)?).into_object());
+        Ok(PyList::new(py, &generated))
+    }
+
     py_class!(pub class Mapper |py| {
         data handle: RefCellHandle;
 
@@ -864,7 +1706,18 @@ pub (crate) mod python {
             Mapper::create_instance(py, arg)
         }
 
+        //a ValueError is raised for the symbols that cannot be sent (invalid additional parameters...)
+        def check_symbol(&self,symbol:String)->PyResult<bool>{
+            match self.handle(py).0.borrow().check_symbol(&symbol){
+                Ok(())=>Ok(true),
+                Err(e)=>Err(PyErr::new::<exc::ValueError,_>(py,format!("{:?}",e))),
+            }
+        }
+
         def submit_word(&self ,socket_addr:String,messages:Vec<String>,target_index:usize,timeout:u64,nb_messages:Vec<usize>,known_no_resp:Vec<usize>) -> PyResult<Vec<String>> {
+            for symbol in &messages{
+                self.check_symbol(py,symbol.clone())?;
+            }
             let messages:Vec<&str> = messages.iter().map(|s| &**s).collect();
             let res=self.handle(py).0.borrow_mut().submit_word(socket_addr,messages,target_index,timeout,nb_messages,known_no_resp);
             Ok(res)
@@ -879,20 +1732,136 @@ pub (crate) mod python {
             self.handle(py).0.borrow_mut().set_target_node(type_,namespace,id);
             Ok(0)
         }
+        def set_target_node_id(&self,id:&str)->PyResult<usize>{
+            if let Err(e)=self.handle(py).0.borrow_mut().set_target_node_id(id){
+                return Err(PyErr::new::<exc::ValueError,_>(py,format!("invalid node id {}: {:?}",id,e)));
+            }
+            Ok(0)
+        }
         def set_target_node_value(&self,type_:u8,value:&str)->PyResult<usize>{
-            self.handle(py).0.borrow_mut().set_target_node_value(type_,value);
+            match self.handle(py).0.borrow_mut().set_target_node_value(type_,value){
+                Ok(())=>Ok(0),
+                Err(e)=>Err(PyErr::new::<exc::ValueError,_>(py,format!("invalid value {}: {:?}",value,e))),
+            }
+        }
+        def set_read_items(&self,items:Vec<String>)->PyResult<usize>{
+            match self.handle(py).0.borrow_mut().set_read_items(&items.iter().map(String::as_str).collect::<Vec<&str>>()){
+                Ok(())=>Ok(0),
+                Err(e)=>Err(PyErr::new::<exc::ValueError,_>(py,format!("{:?}",e))),
+            }
+        }
+        def set_write_items(&self,items:Vec<String>)->PyResult<usize>{
+            match self.handle(py).0.borrow_mut().set_write_items(&items.iter().map(String::as_str).collect::<Vec<&str>>()){
+                Ok(())=>Ok(0),
+                Err(e)=>Err(PyErr::new::<exc::ValueError,_>(py,format!("{:?}",e))),
+            }
+        }
+        def set_target_node_value_fields(&self,status:Option<String>,source_timestamp:Option<String>,server_timestamp:Option<String>)->PyResult<usize>{
+            match self.handle(py).0.borrow_mut().set_target_node_value_fields(status.as_deref(),source_timestamp.as_deref(),server_timestamp.as_deref()){
+                Ok(())=>Ok(0),
+                Err(e)=>Err(PyErr::new::<exc::ValueError,_>(py,format!("invalid status code or timestamp: {:?}",e))),
+            }
+        }
+
+        def set_decode_limits(&self,max_string_length:usize,max_array_length:usize,max_depth:usize,max_message_size:usize)->PyResult<usize>{
+            self.handle(py).0.borrow_mut().set_decode_limits(max_string_length,max_array_length,max_depth,max_message_size);
+            Ok(0)
+        }
+
+        //report of the endpoints of the target as JSON and text
+        def scan_endpoints(&self,socket_addr:String,target_index:usize,timeout:u64)->PyResult<(String,String)>{
+            let report=match self.handle(py).0.borrow_mut().scan_endpoints(socket_addr,target_index,timeout){
+                Ok(report)=>report,
+                Err(e)=>return Err(PyErr::new::<exc::ValueError, _>(py, e.to_string())),
+            };
+            Ok((report.to_json(),report.to_string()))
+        }
+
+        //analysis of the certificates of the target as a JSON array and text
+        def analyze_server_certificates(&self,target_index:usize)->PyResult<(String,String)>{
+            let reports=match self.handle(py).0.borrow().analyze_server_certificates(target_index){
+                Ok(reports)=>reports,
+                Err(e)=>return Err(PyErr::new::<exc::ValueError, _>(py, e.to_string())),
+            };
+            let json=reports.iter().map(|r| r.to_json()).collect::<Vec<String>>().join(",");
+            let text=reports.iter().map(|r| r.to_string()).collect::<Vec<String>>().join("\n");
+            Ok((format!("[{}]",json),text))
+        }
+
+        def set_trust_list(&self,dir:String)->PyResult<usize>{
+            match self.handle(py).0.borrow_mut().set_trust_list(&dir){
+                Ok(())=>Ok(0),
+                Err(e)=>Err(PyErr::new::<exc::ValueError,_>(py,format!("{:?}",e))),
+            }
+        }
+
+        def set_max_clock_skew(&self,seconds:u64)->PyResult<usize>{
+            self.handle(py).0.borrow_mut().set_max_clock_skew(seconds);
+            Ok(0)
+        }
+
+        def set_return_diagnostics(&self,mask:u32)->PyResult<usize>{
+            self.handle(py).0.borrow_mut().set_return_diagnostics(mask);
+            Ok(0)
+        }
+
+        def set_output_abstraction(&self,spec:String)->PyResult<usize>{
+            match self.handle(py).0.borrow_mut().set_output_abstraction(&spec){
+                Ok(())=>Ok(0),
+                Err(e)=>Err(PyErr::new::<exc::ValueError,_>(py,format!("{:?}",e))),
+            }
+        }
+
+        def set_liveness_check(&self,get_endpoints:bool,timeout:u64,findings_dir:Option<String>)->PyResult<usize>{
+            self.handle(py).0.borrow_mut().set_liveness_check(get_endpoints,timeout,findings_dir.as_deref());
             Ok(0)
         }
 
+        def fuzz(&self,socket_addr:String,prefix:Vec<String>,target:String,target_index:usize,timeout:u64)->PyResult<Vec<(usize,String,String,String)>>{
+            for symbol in prefix.iter().chain(std::iter::once(&target)){
+                self.check_symbol(py,symbol.clone())?;
+            }
+            let prefix:Vec<&str> = prefix.iter().map(|s| &**s).collect();
+            let res=self.handle(py).0.borrow_mut().fuzz(socket_addr,prefix,&target,target_index,timeout);
+            Ok(res.into_iter().map(|r| (r.index,r.path,r.description,r.outcome.to_string())).collect())
+        }
+
+        def parse_errors(&self)->PyResult<Vec<(usize,String)>>{
+            Ok(self.handle(py).0.borrow().parse_errors().to_vec())
+        }
+        def liveness(&self)->PyResult<Option<(usize,String)>>{
+            Ok(self.handle(py).0.borrow().liveness().map(|(i,state)| (i,state.to_string())))
+        }
+        def service_results(&self)->PyResult<Vec<(usize,String)>>{
+            Ok(self.handle(py).0.borrow().service_results().iter().map(|(i,s)| (*i,s.to_string())).collect())
+        }
+        def diagnostics(&self)->PyResult<Vec<(usize,String)>>{
+            Ok(self.handle(py).0.borrow().diagnostics().to_vec())
+        }
+        //index of the symbol, rule and description of the findings of the conformance oracle
+        def conformance_findings(&self)->PyResult<Vec<(usize,String,String)>>{
+            Ok(self.handle(py).0.borrow().conformance_findings().iter().map(|f| (f.index,f.rule.to_string(),f.description.clone())).collect())
+        }
+
+        //decode a message received from a server, a ValueError gives the field that could not be decoded
+        def parse(&self,data:PyBytes)->PyResult<String>{
+            self.handle(py).0.borrow().decode_limits.apply();
+            match crate::msg::parser::parse(data.data(py)){
+                Ok(msg)=>Ok(format!("{:?}",msg)),
+                Err(e)=>Err(PyErr::new::<exc::ValueError,_>(py,format!("{:?}",e))),
+            }
+        }
+
     });
 }
 
 //Wrapping for learnlib
 #[cfg(feature = "java")]
 pub(crate) mod java{
+    use crate::crypto::certgen::{CertificateAuthority, ClientCertificates, Defect};
     use jni::JNIEnv;
     use jni::objects::{JClass, JString, JObject, JValue};
-    use jni::sys::{jlong,jint, jintArray,jobjectArray};
+    use jni::sys::{jlong,jint, jintArray,jobjectArray,jboolean};
     use super::Handle;
     type JavaResult<T>=Result<T,jni::errors::Error>;
     pub fn get_raw<T>(v:T)->i64{
@@ -967,6 +1936,21 @@ pub(crate) mod java{
 
     }
 
+    //the certificates are issued by a new CA, the false ones with the defects (names separated by commas), the chains by an intermediate CA
+    #[no_mangle]
+    #[allow(non_snake_case)]
+    pub extern "system" fn Java_learner_OracleMapper_init_1generated(env: JNIEnv,_class: JClass, application_uri:JString, host:JString, defects:JString,mode :jint)->jlong{
+        let application_uri: String =env.get_string(application_uri).expect("Couldn't get java string!").into();
+        let host: String =env.get_string(host).expect("Couldn't get java string!").into();
+        let defects: String =env.get_string(defects).expect("Couldn't get java string!").into();
+        let defects=Defect::parse_list(&defects).unwrap_or_else(|e| panic!("invalid certificate defects: {:?}",e));
+        let mut ca=CertificateAuthority::new("mapper CA").expect("Couldn't generate the CA");
+        let mut intermediate=ca.intermediate("mapper intermediate CA").expect("Couldn't generate the intermediate CA");
+        let certificates=ClientCertificates::generate(&mut ca,&mut intermediate,&application_uri,&host,&defects).expect("Couldn't generate the certificates");
+        let mapper = Handle::from_certificates(&certificates,mode as u32);
+        get_raw(mapper)
+    }
+
     #[no_mangle]
     #[allow(non_snake_case)]
     pub extern "system" fn Java_learner_OracleMapper_get_1server_1certificate(env: JNIEnv,obj: JObject, dest:JString, timeout:jint){
@@ -1002,4 +1986,82 @@ pub(crate) mod java{
         
     }
 
+    #[no_mangle]
+    #[allow(non_snake_case)]
+    pub extern "system" fn Java_learner_OracleMapper_set_1liveness_1check(env: JNIEnv,obj: JObject, get_endpoints:jboolean, timeout:jint, findings_dir:JString){
+        let findings_dir:Option<String>= if findings_dir.is_null(){
+            None
+        }else{
+            Some(env.get_string(findings_dir).expect("Couldn't get java string!").into())
+        };
+        let handle:*mut Handle=get_handler(&env, &obj);
+        unsafe{
+            if handle.is_null(){
+                panic!("mapper handler is empty in set liveness check");
+            }else{
+                (*handle).set_liveness_check(get_endpoints!=0,timeout as u64,findings_dir.as_deref());
+            }
+        }
+    }
+
+    #[no_mangle]
+    #[allow(non_snake_case)]
+    pub extern "system" fn Java_learner_OracleMapper_set_1output_1abstraction(env: JNIEnv,obj: JObject, spec:JString){
+        let spec:String=env.get_string(spec).expect("Couldn't get java string!").into();
+        let handle:*mut Handle=get_handler(&env, &obj);
+        unsafe{
+            if handle.is_null(){
+                panic!("mapper handler is empty in set output abstraction");
+            }else{
+                //the exception is thrown in java when the native method returns
+                if let Err(e)=(*handle).set_output_abstraction(&spec){
+                    let _=env.throw_new("java/lang/IllegalArgumentException",format!("invalid output abstraction {}: {:?}",spec,e));
+                }
+            }
+        }
+    }
+
+    #[no_mangle]
+    #[allow(non_snake_case)]
+    pub extern "system" fn Java_learner_OracleMapper_set_1trust_1list(env: JNIEnv,obj: JObject, dir:JString){
+        let dir:String=env.get_string(dir).expect("Couldn't get java string!").into();
+        let handle:*mut Handle=get_handler(&env, &obj);
+        unsafe{
+            if handle.is_null(){
+                panic!("mapper handler is empty in set trust list");
+            }else{
+                //the exception is thrown in java when the native method returns
+                if let Err(e)=(*handle).set_trust_list(&dir){
+                    let _=env.throw_new("java/lang/IllegalArgumentException",format!("invalid trust list {}: {:?}",dir,e));
+                }
+            }
+        }
+    }
+
+    #[no_mangle]
+    #[allow(non_snake_case)]
+    pub extern "system" fn Java_learner_OracleMapper_set_1return_1diagnostics(env: JNIEnv,obj: JObject, mask:jint){
+        let handle:*mut Handle=get_handler(&env, &obj);
+        unsafe{
+            if handle.is_null(){
+                panic!("mapper handler is empty in set return diagnostics");
+            }else{
+                (*handle).set_return_diagnostics(mask as u32);
+            }
+        }
+    }
+
+    #[no_mangle]
+    #[allow(non_snake_case)]
+    pub extern "system" fn Java_learner_OracleMapper_set_1max_1clock_1skew(env: JNIEnv,obj: JObject, seconds:jint){
+        let handle:*mut Handle=get_handler(&env, &obj);
+        unsafe{
+            if handle.is_null(){
+                panic!("mapper handler is empty in set max clock skew");
+            }else{
+                (*handle).set_max_clock_skew(seconds as u64);
+            }
+        }
+    }
+
 }
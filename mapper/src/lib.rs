@@ -7,9 +7,14 @@
 //! "open connection" to real message. Thus it can be used with Lstar
 //! algorithm to detect vulnerabilities in OPC-UA implementation.
 
+pub mod abstraction;
+pub mod conformance;
 pub mod crypto;
+pub mod fuzz;
 pub mod handle;
+pub mod liveness;
 pub mod msg;
+pub mod scan;
 pub mod uatypes;
 mod error;
 
@@ -20,8 +25,56 @@ use error::{MapperError, MapperErrorKind};
 
 
 pub(crate) type MapperResult<T>= Result<T,MapperError>;
+/// Destination of an encoding. Values are written in order, without intermediate buffers.
+pub trait BufMut {
+    fn put_slice(&mut self, src: &[u8]);
+
+    fn put_u8(&mut self, value: u8) {
+        self.put_slice(&[value]);
+    }
+}
+
+impl BufMut for Vec<u8> {
+    fn put_slice(&mut self, src: &[u8]) {
+        self.extend_from_slice(src);
+    }
+
+    fn put_u8(&mut self, value: u8) {
+        self.push(value);
+    }
+}
+
+//only counts the bytes written, used to compute the encoded length without allocation
+#[derive(Default)]
+pub(crate) struct LengthCounter(pub(crate) usize);
+
+impl BufMut for LengthCounter {
+    fn put_slice(&mut self, src: &[u8]) {
+        self.0 += src.len();
+    }
+
+    fn put_u8(&mut self, _value: u8) {
+        self.0 += 1;
+    }
+}
+
 pub trait Serialize {
-    fn serialize(&self) -> Vec<u8>;
+    /// Write the encoding at the end of `buf`.
+    fn serialize_into(&self, buf: &mut impl BufMut);
+
+    /// Length of the encoding in bytes.
+    fn encoded_len(&self) -> usize {
+        let mut counter = LengthCounter::default();
+        self.serialize_into(&mut counter);
+        counter.0
+    }
+
+    /// Encoding in a new buffer, allocated once with the right size.
+    fn serialize(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.encoded_len());
+        self.serialize_into(&mut buf);
+        buf
+    }
 }
 
 pub trait Deserialize {
@@ -37,15 +90,19 @@ pub trait Deserialize {
     }
 }
 #[cfg(feature="python")]
-use crate::handle::python::Mapper;
+use crate::handle::python::{generate_certificates, Mapper};
 #[cfg(feature="python")]
-use cpython::py_module_initializer;
+use cpython::{py_fn, py_module_initializer};
 #[cfg(feature="python")]
 use crate::uatypes::prelude::*;
 #[cfg(feature="python")]
 py_module_initializer!(mapper, |py, m| {
     m.add_class::<Mapper>(py)?;
     m.add(py,"__doc__","OPC UA mapper class")?;
+    //the argument parsing generated by py_fn strips the prefixes by hand
+    #[allow(clippy::manual_strip)]
+    let generate=py_fn!(py,generate_certificates(application_uri:String,host:String,defects:String));
+    m.add(py,"generate_certificates",generate)?;
     m.add(py,"bool",DataTypeId::BOOLEAN)?;
     m.add(py,"int8",DataTypeId::SBYTE)?;
     m.add(py,"uint8",DataTypeId::BYTE)?;
@@ -57,6 +114,16 @@ py_module_initializer!(mapper, |py, m| {
     m.add(py,"uint64",DataTypeId::UINT_64)?;
     m.add(py,"float",DataTypeId::FLOAT)?;
     m.add(py,"double",DataTypeId::DOUBLE)?;
+    m.add(py,"string",DataTypeId::STRING)?;
+    m.add(py,"datetime",DataTypeId::DATETIME)?;
+    m.add(py,"guid",DataTypeId::GUID)?;
+    m.add(py,"bytestring",DataTypeId::BYTESTRING)?;
+    m.add(py,"xmlelement",DataTypeId::XMLELEMENT)?;
+    m.add(py,"nodeid",DataTypeId::NODEID)?;
+    m.add(py,"expandednodeid",DataTypeId::EXPANDED_NODEID)?;
+    m.add(py,"statuscode",DataTypeId::STATUS_CODE)?;
+    m.add(py,"qualifiedname",DataTypeId::QUALIFIE_NAME)?;
+    m.add(py,"localizedtext",DataTypeId::LOCALIZED_TEXT)?;
     m.add(py,"NodeIdNumeric",crate::uatypes::node_id::EncodingValue::NUMERIC)?;
     m.add(py,"NodeIdString",crate::uatypes::node_id::EncodingValue::STRING)?;
     m.add(py,"NodeIdGuid",crate::uatypes::node_id::EncodingValue::GUID)?;
@@ -67,9 +134,11 @@ py_module_initializer!(mapper, |py, m| {
 
 pub mod encoding_prelude{
     pub(crate) use crate::Serialize;
+    pub(crate) use crate::BufMut;
     pub(crate) use crate::Deserialize;
     pub(crate) use crate::MapperResult;
-    pub(crate) use derive_macro::{Deserialize, Serialize};
+    pub(crate) use derive_macro::{Deserialize, Serialize, Mutate};
+    pub(crate) use crate::fuzz::Mutate;
 }
 
 pub mod result_prelude{
@@ -0,0 +1,115 @@
+//! Liveness oracle.
+//!
+//! `Eof,` only tells that the server closed the connection, not that the server process died.
+//! When a [`LivenessCheck`] is set on the [`Handle`](crate::handle::Handle), a fresh connection
+//! is opened after each word (Hello/Ack and optionally GetEndpoints) to tell a crash
+//! (connection refused or closed) from a hang (no answer before the timeout).
+//! Words leading to a crash or a hang can be saved with their pcap in a findings directory.
+//! The state is given by [`Handle::liveness`](crate::handle::Handle::liveness) and not in the outputs, the learner
+//! counts the messages of each symbol with the commas of its output.
+
+pub mod pcap;
+#[cfg(test)]
+mod tests;
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::fuzz::Mutation;
+use crate::handle::Handle;
+use crate::uatypes::status_code::StatusCode;
+use pcap::Capture;
+
+const DEFAULT_PORT: u16 = 4840;
+
+/// State of the server after a word.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Liveness {
+    Alive,
+    ///the server does not accept connection anymore or closes them without answering
+    Crash,
+    ///the server accepts the connection but does not answer before the timeout
+    Hang,
+}
+
+impl fmt::Display for Liveness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Liveness::Alive => write!(f, "Alive"),
+            Liveness::Crash => write!(f, "Crash"),
+            Liveness::Hang => write!(f, "Hang"),
+        }
+    }
+}
+
+/// Configuration of the check done after each word.
+#[derive(Debug, Clone)]
+pub struct LivenessCheck {
+    ///also open a secure channel (security mode None) and send a GetEndpoints request
+    pub(crate) get_endpoints: bool,
+    ///read timeout of the check in ms
+    pub(crate) timeout: u64,
+    ///where the words leading to a crash or a hang are saved, nothing is saved if None
+    pub(crate) findings_dir: Option<PathBuf>,
+}
+
+impl LivenessCheck {
+    pub fn new(get_endpoints: bool, timeout: u64, findings_dir: Option<&str>) -> LivenessCheck {
+        LivenessCheck {
+            get_endpoints,
+            timeout,
+            findings_dir: findings_dir.map(PathBuf::from),
+        }
+    }
+}
+
+/// Index of the symbol after which the state of the server is reported: the first `Eof` (where the connection
+/// has been lost), or the last symbol if the connection was never lost.
+pub(crate) fn lost_at(outputs: &[String]) -> usize {
+    outputs.iter().position(|o| o.ends_with(Handle::R_EOF) || o == "Eof").unwrap_or(outputs.len().saturating_sub(1))
+}
+
+fn port_of(socket_addr: &str) -> u16 {
+    socket_addr.rsplit(':').next().and_then(|p| p.parse().ok()).unwrap_or(DEFAULT_PORT)
+}
+
+/// Save a word leading to a crash or a hang in its own directory of `findings_dir`:
+/// `word.txt` holds the symbols and outputs (with the reason of each `ParseErr` and the state of the server after the
+/// symbol where it is reported), `trace.pcap` the traffic of the word.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn save_finding(findings_dir: &Path, socket_addr: &str, liveness: Liveness, messages: &[&str], outputs: &[String], parse_errors: &[(usize, String)], service_results: &[(usize, StatusCode)], diagnostics: &[(usize, String)], mutation: Option<&Mutation>, capture: &Capture) -> std::io::Result<PathBuf> {
+    let millis = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis()).unwrap_or(0);
+    let mut dir = findings_dir.join(format!("{}_{}", liveness.to_string().to_lowercase(), millis));
+    let mut cpt = 1;
+    while dir.exists() {
+        dir = findings_dir.join(format!("{}_{}_{}", liveness.to_string().to_lowercase(), millis, cpt));
+        cpt += 1;
+    }
+    fs::create_dir_all(&dir)?;
+
+    let mut word = format!("target: {}\nliveness: {}\n", socket_addr, liveness);
+    if let Some(m) = mutation {
+        word.push_str(&format!("mutation: {} ({})\n", m.path, m.description));
+    }
+    let lost = lost_at(outputs);
+    for (i, symbol) in messages.iter().enumerate() {
+        word.push_str(&format!("{} -> {}\n", symbol, outputs.get(i).map(|s| s.as_str()).unwrap_or("")));
+        if i == lost {
+            word.push_str(&format!("    {}\n", liveness));
+        }
+        for (_, error) in parse_errors.iter().filter(|(index, _)| *index == i) {
+            word.push_str(&format!("    {}\n", error));
+        }
+        for (_, status) in service_results.iter().filter(|(index, _)| *index == i) {
+            word.push_str(&format!("    {}\n", status));
+        }
+        for (_, diagnostic) in diagnostics.iter().filter(|(index, _)| *index == i) {
+            word.push_str(&format!("    {}\n", diagnostic));
+        }
+    }
+    fs::write(dir.join("word.txt"), word)?;
+    fs::write(dir.join("trace.pcap"), capture.to_pcap(port_of(socket_addr)))?;
+    Ok(dir)
+}
@@ -0,0 +1,139 @@
+//! Minimal pcap writer for the traffic of one word.
+//! The TCP/IP headers are synthetic (raw IPv4, linktype 101), only the payloads and their
+//! order are real. It is enough for wireshark to dissect the OPC UA messages.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const PCAP_MAGIC: u32 = 0xa1b2c3d4;
+const LINKTYPE_RAW: u32 = 101;
+const SNAPLEN: u32 = 262144;
+//payload size of one segment, the ip total length is on 16 bits
+const MAX_SEGMENT: usize = 60000;
+const CLIENT_IP: [u8; 4] = [10, 0, 0, 1];
+const SERVER_IP: [u8; 4] = [10, 0, 0, 2];
+const CLIENT_PORT: u16 = 49152;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    Sent,
+    Received,
+}
+
+#[derive(Debug, Clone)]
+struct Packet {
+    direction: Direction,
+    timestamp: u64, //microseconds since epoch
+    payload: Vec<u8>,
+}
+
+/// Bytes exchanged with the server, in order.
+#[derive(Debug, Clone, Default)]
+pub struct Capture {
+    packets: Vec<Packet>,
+}
+
+fn now_micros() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_micros() as u64).unwrap_or(0)
+}
+
+//ones' complement checksum used by the ip header
+fn checksum(data: &[u8]) -> u16 {
+    let mut sum: u32 = 0;
+    for chunk in data.chunks(2) {
+        let word = if chunk.len() == 2 { u16::from_be_bytes([chunk[0], chunk[1]]) } else { u16::from_be_bytes([chunk[0], 0]) };
+        sum += word as u32;
+    }
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+impl Capture {
+    pub fn new() -> Capture {
+        Capture { packets: vec![] }
+    }
+
+    pub fn record(&mut self, direction: Direction, payload: &[u8]) {
+        self.packets.push(Packet {
+            direction,
+            timestamp: now_micros(),
+            payload: payload.to_vec(),
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.packets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.packets.is_empty()
+    }
+
+    /// Encode the capture as a pcap file. `server_port` is used for the server side of the
+    /// synthetic TCP connection so that the OPC UA dissector is selected.
+    pub fn to_pcap(&self, server_port: u16) -> Vec<u8> {
+        let mut result = Vec::new();
+        result.extend_from_slice(&PCAP_MAGIC.to_le_bytes());
+        result.extend_from_slice(&2_u16.to_le_bytes());
+        result.extend_from_slice(&4_u16.to_le_bytes());
+        result.extend_from_slice(&0_i32.to_le_bytes()); //thiszone
+        result.extend_from_slice(&0_u32.to_le_bytes()); //sigfigs
+        result.extend_from_slice(&SNAPLEN.to_le_bytes());
+        result.extend_from_slice(&LINKTYPE_RAW.to_le_bytes());
+
+        let mut client_seq: u32 = 1;
+        let mut server_seq: u32 = 1;
+        let mut ip_id: u16 = 0;
+        for packet in &self.packets {
+            for segment in packet.payload.chunks(MAX_SEGMENT) {
+                let (src, dst, sport, dport, seq, ack) = match packet.direction {
+                    Direction::Sent => (CLIENT_IP, SERVER_IP, CLIENT_PORT, server_port, client_seq, server_seq),
+                    Direction::Received => (SERVER_IP, CLIENT_IP, server_port, CLIENT_PORT, server_seq, client_seq),
+                };
+                let frame = Capture::frame(src, dst, sport, dport, seq, ack, ip_id, segment);
+                ip_id = ip_id.wrapping_add(1);
+                match packet.direction {
+                    Direction::Sent => client_seq = client_seq.wrapping_add(segment.len() as u32),
+                    Direction::Received => server_seq = server_seq.wrapping_add(segment.len() as u32),
+                }
+                result.extend_from_slice(&((packet.timestamp / 1_000_000) as u32).to_le_bytes());
+                result.extend_from_slice(&((packet.timestamp % 1_000_000) as u32).to_le_bytes());
+                result.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+                result.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+                result.extend_from_slice(&frame);
+            }
+        }
+        result
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn frame(src: [u8; 4], dst: [u8; 4], sport: u16, dport: u16, seq: u32, ack: u32, ip_id: u16, payload: &[u8]) -> Vec<u8> {
+        let total_length = (20 + 20 + payload.len()) as u16;
+        let mut ip = Vec::with_capacity(20);
+        ip.push(0x45); //version 4, 5 words
+        ip.push(0);
+        ip.extend_from_slice(&total_length.to_be_bytes());
+        ip.extend_from_slice(&ip_id.to_be_bytes());
+        ip.extend_from_slice(&0x4000_u16.to_be_bytes()); //don't fragment
+        ip.push(64); //ttl
+        ip.push(6); //tcp
+        ip.extend_from_slice(&[0, 0]);
+        ip.extend_from_slice(&src);
+        ip.extend_from_slice(&dst);
+        let ip_checksum = checksum(&ip);
+        ip[10..12].copy_from_slice(&ip_checksum.to_be_bytes());
+
+        let mut frame = ip;
+        frame.extend_from_slice(&sport.to_be_bytes());
+        frame.extend_from_slice(&dport.to_be_bytes());
+        frame.extend_from_slice(&seq.to_be_bytes());
+        frame.extend_from_slice(&ack.to_be_bytes());
+        frame.push(0x50); //5 words, no option
+        frame.push(0x18); //PSH, ACK
+        frame.extend_from_slice(&65535_u16.to_be_bytes());
+        frame.extend_from_slice(&[0, 0, 0, 0]); //checksum (not computed) and urgent pointer
+        frame.extend_from_slice(payload);
+        frame
+    }
+}
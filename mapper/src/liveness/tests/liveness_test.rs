@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use crate::liveness::pcap::{Capture, Direction};
+    use crate::liveness::lost_at;
+
+    fn outputs(o: &[&str]) -> Vec<String> {
+        o.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn reported_at_first_eof() {
+        assert_eq!(lost_at(&outputs(&["Ack,", "OpnRepOK,Eof,", "Eof,"])), 1);
+        assert_eq!(lost_at(&outputs(&["Ack,", "Eof", "Eof"])), 1);
+    }
+
+    #[test]
+    fn reported_at_last_symbol_without_eof() {
+        assert_eq!(lost_at(&outputs(&["Ack,", "OpnRepOK,"])), 1);
+        assert_eq!(lost_at(&[]), 0);
+    }
+
+    #[test]
+    fn pcap_records() {
+        let mut capture = Capture::new();
+        capture.record(Direction::Sent, b"HELF\x08\x00\x00\x00");
+        capture.record(Direction::Received, b"ACKF\x08\x00\x00\x00");
+        let pcap = capture.to_pcap(4840);
+        assert_eq!(&pcap[..4], &[0xd4, 0xc3, 0xb2, 0xa1]);
+        assert_eq!(&pcap[20..24], &101_u32.to_le_bytes());
+        //global header + 2 * (record header + ip + tcp + payload)
+        assert_eq!(pcap.len(), 24 + 2 * (16 + 20 + 20 + 8));
+        let first = &pcap[24 + 16..24 + 16 + 48];
+        //ports
+        assert_eq!(&first[20..22], &49152_u16.to_be_bytes());
+        assert_eq!(&first[22..24], &4840_u16.to_be_bytes());
+        assert_eq!(&first[40..], b"HELF\x08\x00\x00\x00");
+        let second = &pcap[24 + 16 + 48 + 16..];
+        assert_eq!(&second[20..22], &4840_u16.to_be_bytes());
+        //the ack of the server acknowledges the bytes of the client
+        assert_eq!(&second[28..32], &9_u32.to_be_bytes());
+    }
+}
@@ -0,0 +1 @@
+mod liveness_test;
@@ -1,25 +1,12 @@
 use crate::crypto::pkey::{PublicKey, PrivateKey};
 use crate::crypto::security_policy::{SecurityPolicy};
 use crate::uatypes::prelude::*;
-use crate::encoding_prelude::*;
+use super::services::*;
 
 use super::header::prelude::*;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ActiveSessionRequest {
-    pub(crate) message_header: MessageHeader,
-    pub(crate) security_header: SymmetricSecurityHeader,
-    pub(crate) sequence_header: SequenceHeader,
-    pub(crate) node_id: NodeId,
-    pub(crate) request_header: RequestHeader,
-    pub(crate) client_signature: SignatureData,
-    pub(crate) future_use: i32,
-    pub(crate) locale_ids: Vec::<UaString>,
-    pub(crate) user_identity_token: ExtensionObject,
-    pub(crate) user_token_signature: SignatureData,
-}
 
-impl ActiveSessionRequest {
+impl ActivateSessionRequest {
     pub fn build(server_public_key: &PublicKey,private_key:&PrivateKey,server_nonce: &ByteString,server_cert: &ByteString,user_cert: &ByteString,session_node_id:&NodeId,security_policy: &mut SecurityPolicy,policy_token_uri:&str,policy_id:&UaString,is_anon:bool,is_user:bool,is_cert:bool) -> Self {
         let message_header = MessageHeader {
             message_type: super::message_type::MessageType::MSG,
@@ -33,7 +20,7 @@ impl ActiveSessionRequest {
             sequence_number: 0,
             request_id: 0,
         };
-        let node_id: NodeId = NodeId::new_numeric(0, 467);
+        let node_id: NodeId = NodeId::new_numeric(0, ObjectIds::ActivateSessionRequest_Encoding_DefaultBinary);
         let request_header = RequestHeader {
             authentication_token: session_node_id.clone(),
             timestamp: DateTime::new_now(),
@@ -41,11 +28,11 @@ impl ActiveSessionRequest {
             return_diagnostic: 0,
             audit_entry: UaString::new(),
             timout_hint: 0,
-            additional_header: None,
+            additional_header: ExtensionObject::null(),
         };
         let client_signature=SignatureData::from(security_policy,server_cert,Some(server_nonce));
-        let future_use=0;
-        let locale_ids = vec![UaString::from("en-US")];
+        let client_software_certificates=UaArray::empty();
+        let locale_ids = UaArray::from(vec![UaString::from("en-US")]);
         let user_identity_token : ExtensionObject;
         match (is_anon,is_user,is_cert){
             (true,_,_)=>user_identity_token = ExtensionObject::anon(policy_id),
@@ -61,14 +48,14 @@ impl ActiveSessionRequest {
         }else{
             user_token_signature =SignatureData::new();
         }
-        ActiveSessionRequest {
+        ActivateSessionRequest {
             message_header,
             security_header,
             sequence_header,
             node_id,
             request_header,
             client_signature,
-            future_use,
+            client_software_certificates,
             locale_ids,
             user_identity_token,
             user_token_signature,
@@ -76,14 +63,3 @@ impl ActiveSessionRequest {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ActiveSessionResponse{
-    pub(crate) message_header: MessageHeader,
-    pub(crate) security_header: SymmetricSecurityHeader,
-    pub(crate) sequence_header: SequenceHeader,
-    pub(crate) node_id: NodeId,
-    pub(crate) response_header: ResponseHeader,
-    pub(crate) server_nonce: ByteString,
-    pub(crate) results: Vec::<StatusCode>,
-    pub(crate) diagnosti_info: Vec::<DiagnosticInfo>,
-}
@@ -0,0 +1,41 @@
+//! Sample requests for benches/serialize_bench.rs, the messages are not public.
+
+use crate::msg::prelude::*;
+use crate::msg::Msg;
+use crate::uatypes::prelude::*;
+use crate::Serialize;
+
+/// A request and its name.
+pub struct Sample {
+    pub name: &'static str,
+    msg: Msg,
+}
+
+impl Sample {
+    pub fn serialize(&self) -> Vec<u8> {
+        self.msg.serialize()
+    }
+
+    pub fn serialize_into(&self, buf: &mut Vec<u8>) {
+        self.msg.serialize_into(buf)
+    }
+
+    pub fn encoded_len(&self) -> usize {
+        self.msg.encoded_len()
+    }
+}
+
+/// Hello, GetEndpoints, CreateSession (1 kB certificate), Read and Write requests.
+pub fn samples() -> Vec<Sample> {
+    let session = NodeId::new_numeric(1, 42);
+    let target = NodeId::new_string(2, UaString::from("target"));
+    let value = DataValue::from_value(DataTypeId::DOUBLE, "43.5").unwrap();
+    let endpoint_url = UaString::from("opc.tcp://localhost:4840");
+    vec![
+        Sample { name: "Hello", msg: Msg::HelloMessage(HelloMessage::build(&endpoint_url)) },
+        Sample { name: "GetEndpoints", msg: Msg::GetEndpointsRequest(GetEndpointsRequest::build(1, 1)) },
+        Sample { name: "CreateSession", msg: Msg::CreateSessionRequest(CreateSessionRequest::build(&endpoint_url, &ByteString::from(vec![0; 1024]), 3600000.0)) },
+        Sample { name: "Read", msg: Msg::ReadRequest(ReadRequest::build(&session, &target)) },
+        Sample { name: "Write", msg: Msg::WriteRequest(WriteRequest::build(&session, &target, &value)) },
+    ]
+}
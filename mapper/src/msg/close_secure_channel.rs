@@ -1,16 +1,8 @@
 use super:: message_type::MessageType;
 use super::header::prelude::*;
 use crate::uatypes::prelude::*;
-use crate::encoding_prelude::*;
+use super::services::*;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub (crate)  struct CloseSecureChannelRequest {
-    pub(crate) message_header: MessageHeader,
-    pub(crate) security_header: SymmetricSecurityHeader,
-    pub(crate) sequence_header: SequenceHeader,
-    pub(crate) node_id: NodeId,
-    pub(crate) request_header: RequestHeader,
-}
 
 impl CloseSecureChannelRequest {
     pub (crate) fn build() -> Self {
@@ -23,7 +15,7 @@ impl CloseSecureChannelRequest {
             sequence_number: 3,
             request_id: 3,
         };
-        let node_id: NodeId = NodeId::new_numeric(0, 452);
+        let node_id: NodeId = NodeId::new_numeric(0, ObjectIds::CloseSecureChannelRequest_Encoding_DefaultBinary);
         let request_header = RequestHeader {
             authentication_token: NodeId::new_numeric(0, 0),
             timestamp: DateTime::new_now(),
@@ -31,7 +23,7 @@ impl CloseSecureChannelRequest {
             return_diagnostic: 0,
             audit_entry: UaString::new(),
             timout_hint: 0,
-            additional_header: None,
+            additional_header: ExtensionObject::null(),
         };
 
         CloseSecureChannelRequest {
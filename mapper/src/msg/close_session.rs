@@ -1,19 +1,9 @@
 use crate::uatypes::prelude::*;
-use crate::encoding_prelude::*;
+use super::services::*;
 use std::num::ParseIntError;
 
 use super::header::prelude::*;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct CloseSessionRequest {
-    pub(crate) message_header: MessageHeader,
-    pub(crate) security_header: SymmetricSecurityHeader,
-    pub(crate) sequence_header: SequenceHeader,
-    pub(crate) node_id: NodeId,
-    pub(crate) request_header: RequestHeader,
-    pub(crate) delete_subscription: bool,
-   
-}
 
 impl CloseSessionRequest {
     pub fn build(authentication_token:&NodeId) -> Self {
@@ -30,7 +20,7 @@ impl CloseSessionRequest {
             sequence_number: 26,
             request_id: 26,
         };
-        let node_id: NodeId = NodeId::new_numeric(0, 473);
+        let node_id: NodeId = NodeId::new_numeric(0, ObjectIds::CloseSessionRequest_Encoding_DefaultBinary);
         let request_header = RequestHeader {
             authentication_token: authentication_token.clone(),
             timestamp: DateTime::new_now(),
@@ -38,7 +28,7 @@ impl CloseSessionRequest {
             return_diagnostic: 0,
             audit_entry: UaString::new(),
             timout_hint: 0,
-            additional_header: None,
+            additional_header: ExtensionObject::null(),
         };
         CloseSessionRequest {
             message_header,
@@ -46,7 +36,7 @@ impl CloseSessionRequest {
             sequence_header,
             node_id,
             request_header,
-            delete_subscription : false,
+            delete_subscriptions : false,
         }
     }
     fn decode_hex(s: &str) -> Result<Vec<u8>, ParseIntError> {
@@ -57,11 +47,3 @@ impl CloseSessionRequest {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct CloseSessionResponse{
-    pub(crate) message_header: MessageHeader,
-    pub(crate) security_header: SymmetricSecurityHeader,
-    pub(crate) sequence_header: SequenceHeader,
-    pub(crate) node_id: NodeId,
-    pub(crate) response_header: ResponseHeader,
-}
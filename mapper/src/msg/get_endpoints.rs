@@ -1,8 +1,6 @@
-use crate::encoding_prelude::*;
-
 use crate::{
     uatypes::{
-        date_time::DateTime, endpoint_description::EndpointDescription, node_id::NodeId,
+        array::UaArray, date_time::DateTime, extension_object::ExtensionObject, node_id::NodeId, node_ids::ObjectIds,
         string::UaString,
     }
 };
@@ -10,26 +8,16 @@ use crate::{
 use super::{
     header::{
         message_header::MessageHeader, request_header::RequestHeader,
-        response_header::ResponseHeader, security_header::SymmetricSecurityHeader,
+        security_header::SymmetricSecurityHeader,
         sequence_header::SequenceHeader,
     },
     message_type::MessageType,
+    services::GetEndpointsRequest,
 };
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct GetEndPointsRequest {
-    pub(crate) message_header: MessageHeader,
-    pub(crate) security_header: SymmetricSecurityHeader,
-    pub(crate) sequence_header: SequenceHeader,
-    pub(crate) node_id: NodeId,
-    pub(crate) request_header: RequestHeader,
-    pub(crate) endpoint_url: UaString,
-    pub(crate) local_id: Vec::<UaString>,
-    pub(crate) profile_uris: Vec::<UaString>,
-}
 
-impl GetEndPointsRequest {
-    pub fn build(secure_channel_id: u32, token_id: u32) -> GetEndPointsRequest {
+impl GetEndpointsRequest {
+    pub fn build(secure_channel_id: u32, token_id: u32) -> GetEndpointsRequest {
         let mut message_header: MessageHeader = Default::default();
         message_header.secure_channel_id = secure_channel_id;
         message_header.message_type = MessageType::MSG;
@@ -39,7 +27,7 @@ impl GetEndPointsRequest {
             sequence_number: 2,
             request_id: 2,
         };
-        let node_id: NodeId = NodeId::new_numeric(0, 428);
+        let node_id: NodeId = NodeId::new_numeric(0, ObjectIds::GetEndpointsRequest_Encoding_DefaultBinary);
         let request_header = RequestHeader {
             authentication_token: NodeId::new_numeric(0, 0),
             timestamp: DateTime::new_now(),
@@ -47,30 +35,21 @@ impl GetEndPointsRequest {
             return_diagnostic: 0,
             audit_entry: UaString::new(),
             timout_hint: 0,
-            additional_header: None,
+            additional_header: ExtensionObject::null(),
         };
         let endpoint_url = UaString::from("opc.tcp://localhost:4840");
-        let local_id = Vec::new();
-        let profile_uris = Vec::new();
-        GetEndPointsRequest {
+        let locale_ids = UaArray::empty();
+        let profile_uris = UaArray::empty();
+        GetEndpointsRequest {
             message_header,
             security_header,
             sequence_header,
             node_id,
             request_header,
             endpoint_url,
-            local_id,
+            locale_ids,
             profile_uris,
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct GetEndPointsResponse {
-    pub(crate) message_header: MessageHeader,
-    pub(crate) security_header: SymmetricSecurityHeader,
-    pub(crate) sequence_header: SequenceHeader,
-    pub(crate) node_id: NodeId,
-    pub(crate) response_header: ResponseHeader,
-    pub(crate) endpoints: Vec::<EndpointDescription>,
-}
@@ -1,117 +1,75 @@
-use derive_macro::Deserialize;
+//! Additional header of the request and response headers.
+//! The header is an ExtensionObject, OPC UA 1.05 defines its content as an `AdditionalParametersType`:
+//! a list of `KeyValuePair` with a `Variant` value (e.g. `ECDHPolicyUri` and `ECDHKey` in the responses
+//! of the ECC security policies). The structures are generated from the schema in `uatypes::generated`.
 
-use crate::{
-    uatypes::{
-        byte_string::ByteString, date_time::DateTime, diagnostic_info::DiagnosticInfo,
-        expanded_node_id::ExpandedNodeId, guid::Guid, localized_text::LocalizedText,
-        node_id::NodeId, qualified_name::QualifiedName, status_code::StatusCode, string::UaString,
-        xml_element::XmlElement,
-    },
-    Deserialize, Serialize,MapperResult
-};
-//This file is random do not even try to read it before this commment disappear.
-//it would be a good to waste time
-#[derive(Debug)]
-pub (crate)  struct AdditionalHeaders {
-    pub(crate) content: Vec<KeyValuePair>,
-}
+use crate::result_prelude::*;
+use crate::uatypes::generated::{AdditionalParametersType, KeyValuePair};
+use crate::uatypes::prelude::*;
 
-#[derive(Debug,Deserialize)]
-pub (crate)  struct KeyValuePair {
-    pub(crate) key: QualifiedName,
-    pub(crate) value: BaseDataType,
+///Names of the parameters defined by the specification
+pub struct AdditionalParameterName;
+impl AdditionalParameterName {
+    pub const ECDH_POLICY_URI: &'static str = "ECDHPolicyUri";
+    pub const ECDH_KEY: &'static str = "ECDHKey";
 }
 
-impl AdditionalHeaders {
-    pub fn deug() -> AdditionalHeaders {
-        AdditionalHeaders {
-            content: vec![KeyValuePair {
-                key: QualifiedName {
-                    namespace_index: 0,
-                    name: UaString::from("oui"),
-                },
-                value: BaseDataType::String(UaString::from("non")),
-            }],
-        }
+impl AdditionalParametersType {
+    pub fn new() -> AdditionalParametersType {
+        AdditionalParametersType { parameters: UaArray::empty() }
     }
-}
 
-#[derive(Debug)]
-///<https://reference.opcfoundation.org/v104/Core/DataTypes/BaseDataType/>
-pub (crate)  enum BaseDataType {
-    Boolean(bool),
-    String(UaString),
-    DateTime(DateTime),
-    Guid(Guid),
-    ByteString(ByteString),
-    XmlElement(XmlElement),
-    NodeId(NodeId),
-    ExpandedNodeId(ExpandedNodeId),
-    StatusCode(StatusCode),
-    QualifiedName(QualifiedName),
-    LocalizedText(LocalizedText),
-    // DataValue(DataValue<T>),
-    DiagnosticInfo(DiagnosticInfo),
-    // RsaEncryptedSecret(RsaEncryptedSecret),
-    // EccEncryptedSecret(EccEncryptedSecret),
-}
-
-impl Serialize for BaseDataType {
-    fn serialize(&self) -> Vec<u8> {
-        match self {
-            BaseDataType::Boolean(bool) => bool.serialize(),
-            BaseDataType::String(ua_string) => ua_string.serialize(),
-            BaseDataType::DateTime(date_time) => date_time.serialize(),
-            BaseDataType::Guid(guid) => guid.serialize(),
-            BaseDataType::ByteString(byte_string) => byte_string.serialize(),
-            BaseDataType::XmlElement(xml_element) => xml_element.serialize(),
-            BaseDataType::NodeId(node_id) => node_id.serialize(),
-            BaseDataType::ExpandedNodeId(expanded_node_id) => expanded_node_id.serialize(),
-            BaseDataType::StatusCode(code) => code.serialize(),
-            BaseDataType::QualifiedName(qualified_name) => qualified_name.serialize(),
-            BaseDataType::LocalizedText(localized_text) => localized_text.serialize(),
-            // DataValue(DataValue<T>),
-            BaseDataType::DiagnosticInfo(diagnostif_info) => diagnostif_info.serialize(),
-            // RsaEncryptedSecret(RsaEncryptedSecret),
-            // EccEncryptedSecret(EccEncryptedSecret),
-        }
+    ///Add a parameter of namespace 0
+    pub fn push(&mut self, key: &str, value: Variant) {
+        self.parameters.values.get_or_insert_with(Vec::new).push(KeyValuePair {
+            key: QualifiedName::new(0, UaString::from(key)),
+            value,
+        });
     }
-}
 
-impl Deserialize for BaseDataType {
-    fn deserialize(data: &[u8]) -> MapperResult<(&[u8], Self)> {
-        Ok((data, BaseDataType::Boolean(true)))
+    ///Value of the first parameter named `key`
+    pub fn get(&self, key: &str) -> Option<&Variant> {
+        self.parameters.iter().find(|p| p.key.name == UaString::from(key)).map(|p| &p.value)
     }
-}
 
-impl Serialize for KeyValuePair {
-    fn serialize(&self) -> Vec<u8> {
-        let mut result = self.key.serialize();
-        result.extend_from_slice(&self.value.serialize());
-        result
+    /// Parameters given with an abstract symbol: `key=value` separated by semicolons (commas separate the symbols of a vocabulary).
+    /// Values are strings, a data type id can be given after the key to change the type of the value: `key:6=42` gives an Int32.
+    pub fn parse(parameters: &str) -> MapperResult<AdditionalParametersType> {
+        let mut result = AdditionalParametersType::new();
+        for parameter in parameters.split(';').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = parameter
+                .split_once('=')
+                .ok_or_else(|| MapperError::new(MapperErrorKind::UnexpectedValue, "additional parameter without value"))?;
+            let (key, value) = match key.split_once(':') {
+                Some((key, type_)) => {
+                    let type_ = type_
+                        .parse::<u8>()
+                        .map_err(|_| MapperError::new(MapperErrorKind::UnexpectedValue, "invalid data type of additional parameter"))?;
+                    (key, Variant::from_datatype(type_, value)?)
+                }
+                None => (key, Variant::from(value)),
+            };
+            result.push(key, value);
+        }
+        Ok(result)
     }
-}
 
+    ///Additional header of a request
+    pub fn to_extension_object(&self) -> ExtensionObject {
+        ExtensionObject::from_body(self)
+    }
 
-impl Serialize for AdditionalHeaders {
-    fn serialize(&self) -> Vec<u8> {
-        let mut result = Vec::new();
-        for el in self.content.iter() {
-            result.extend_from_slice(&el.serialize());
+    ///Content of an additional header, None if it is not an AdditionalParametersType
+    pub fn from_extension_object(header: &ExtensionObject) -> Option<AdditionalParametersType> {
+        if header.type_id != NodeId::new_numeric(0, ObjectIds::AdditionalParametersType_Encoding_DefaultBinary) {
+            return None;
         }
-        result
+        header.decode::<AdditionalParametersType>().ok()
     }
 }
 
-impl Deserialize for AdditionalHeaders {
-    fn deserialize(data: &[u8]) -> MapperResult<(&[u8], Self)> {
-        // let (data,key) = QualifiedName::deserialize(data);
-        // let (data,value) = BaseDataType::deserialize(data);
-        Ok((
-            data,
-            AdditionalHeaders {
-                content: Vec::new(),
-            },
-        ))
+impl Default for AdditionalParametersType {
+    fn default() -> Self {
+        AdditionalParametersType::new()
     }
 }
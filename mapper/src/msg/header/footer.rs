@@ -1,4 +1,4 @@
-use crate::{Deserialize, Serialize, MapperResult};
+use crate::{BufMut, Deserialize, Serialize, MapperResult};
 
 //Not usefull for now
 pub (crate)  enum Footer {
@@ -19,8 +19,8 @@ pub (crate)  struct UnAuthenticatedEncryptionFooter {
 }
 
 impl Serialize for AuthenticatedEncryptionFooter {
-    fn serialize(&self) -> Vec<u8> {
-        self.signature.clone()
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        buf.put_slice(&self.signature);
     }
 }
 impl AuthenticatedEncryptionFooter {
@@ -35,14 +35,13 @@ impl AuthenticatedEncryptionFooter {
     }
 }
 impl Serialize for UnAuthenticatedEncryptionFooter {
-    fn serialize(&self) -> Vec<u8> {
-        let mut result = vec![self.padding_size];
-        result.extend_from_slice(&self.padding);
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        buf.put_u8(self.padding_size);
+        buf.put_slice(&self.padding);
         if !self.is_key_less_than_2048bits {
-            result.extend_from_slice(&self.extra_padding_size.serialize());
+            self.extra_padding_size.serialize_into(buf);
         }
-        result.extend_from_slice(&self.signature);
-        result
+        buf.put_slice(&self.signature);
     }
 }
 
@@ -63,9 +62,9 @@ impl Deserialize for AuthenticatedEncryptionFooter {
 
 impl Deserialize for UnAuthenticatedEncryptionFooter {
     fn deserialize(data: &[u8]) -> MapperResult<(&[u8], Self)> {
-        let (data, padding_size) = u8::deserialize(data)?;
+        let (data, padding_size) = u8::deserialize(data).map_err(|e| e.in_field("UnAuthenticatedEncryptionFooter", "padding_size", data.len()))?;
         let (signature, padding) =
-            UnAuthenticatedEncryptionFooter::take_count(data, padding_size as usize)?;
+            UnAuthenticatedEncryptionFooter::take_count(data, padding_size as usize).map_err(|e| e.in_field("UnAuthenticatedEncryptionFooter", "padding", data.len()))?;
         let signature = signature.to_vec();
         // let (signature,extra_padding)= UnAuthenticatedEncryptionFooter::take_count(&data,1).unwrap();
         // let padding_size=extra_padding[0];
@@ -1,11 +1,9 @@
-use crate::{
-    uatypes::{date_time::DateTime, node_id::NodeId, string::UaString},
-    Deserialize, Serialize, MapperResult,
-};
+use crate::encoding_prelude::*;
+use crate::uatypes::{date_time::DateTime, extension_object::ExtensionObject, node_id::NodeId, string::UaString};
 
-use super::additional_header::AdditionalHeaders;
+//mutations are written by hand to add headers of unknown types
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub (crate)  struct RequestHeader {
     pub(crate) authentication_token: NodeId,
     pub(crate) timestamp: DateTime,
@@ -13,46 +11,24 @@ pub (crate)  struct RequestHeader {
     pub(crate) return_diagnostic: u32,
     pub(crate) audit_entry: UaString,
     pub(crate) timout_hint: u32,
-    pub(crate) additional_header: Option<AdditionalHeaders>,
+    pub(crate) additional_header: ExtensionObject,
 }
 
-impl Serialize for RequestHeader {
-    fn serialize(&self) -> Vec<u8> {
-        let mut result = self.authentication_token.serialize();
-        result.extend_from_slice(&self.timestamp.serialize());
-        result.extend_from_slice(&self.request_handle.serialize());
-        result.extend_from_slice(&self.return_diagnostic.serialize());
-        result.extend_from_slice(&self.audit_entry.serialize());
-        result.extend_from_slice(&self.timout_hint.serialize());
-        match &self.additional_header {
-            None => result.extend_from_slice(&[0; 3]), //no additional header we add an empty one
-            Some(add_headers) => result.extend_from_slice(&add_headers.serialize()),
-        }
-
-        result
-    }
-}
-//we ignore additional headers for now but de not derive because we will cop with additionnal headers
-impl Deserialize for RequestHeader {
-    fn deserialize(data: &[u8]) -> MapperResult<(&[u8], Self)> {
-        let (data, authentication_token) = NodeId::deserialize(data)?;
-        let (data, timestamp) = DateTime::deserialize(data)?;
-        let (data, request_handle) = u32::deserialize(data)?;
-        let (data, return_diagnostic) = u32::deserialize(data)?;
-        let (data, audit_entry) = UaString::deserialize(data)?;
-        let (data, timout_hint) = u32::deserialize(data)?;
-        let (data, _) = RequestHeader::take_count(data, 3)?;
-        Ok((
-            data,
-            RequestHeader {
-                authentication_token,
-                timestamp,
-                request_handle,
-                return_diagnostic,
-                audit_entry,
-                timout_hint,
-                additional_header: None,
-            },
-        ))
-    }
+///Bits of the return_diagnostic mask of the request header: the diagnostics the server should return
+///for the service and for each operation.
+pub struct ReturnDiagnostics;
+impl ReturnDiagnostics {
+    pub const SERVICE_SYMBOLIC_ID: u32 = 0x01;
+    pub const SERVICE_LOCALIZED_TEXT: u32 = 0x02;
+    pub const SERVICE_ADDITIONAL_INFO: u32 = 0x04;
+    pub const SERVICE_INNER_STATUS_CODE: u32 = 0x08;
+    pub const SERVICE_INNER_DIAGNOSTICS: u32 = 0x10;
+    pub const OPERATION_SYMBOLIC_ID: u32 = 0x20;
+    pub const OPERATION_LOCALIZED_TEXT: u32 = 0x40;
+    pub const OPERATION_ADDITIONAL_INFO: u32 = 0x80;
+    pub const OPERATION_INNER_STATUS_CODE: u32 = 0x100;
+    pub const OPERATION_INNER_DIAGNOSTICS: u32 = 0x200;
+    pub const SERVICE_ALL: u32 = 0x1F;
+    pub const OPERATION_ALL: u32 = 0x3E0;
+    pub const ALL: u32 = 0x3FF;
 }
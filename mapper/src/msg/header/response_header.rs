@@ -1,9 +1,9 @@
 use crate::{
     uatypes::{
-        date_time::DateTime, diagnostic_info::DiagnosticInfo, extension_object::ExtensionObject,
-        status_code::StatusCode, string::UaString,
+        array::UaArray, date_time::DateTime, diagnostic_info::DiagnosticInfo, extension_object::ExtensionObject,
+        generated::AdditionalParametersType, status_code::StatusCode, string::UaString,
     },
-    Deserialize, Serialize, MapperResult,
+    BufMut, Deserialize, Serialize, MapperError, MapperResult,
 };
 
 #[derive(Debug)]
@@ -13,29 +13,37 @@ pub (crate)  struct ResponseHeader {
     pub(crate) request_handle: u32,
     pub(crate) service_result: StatusCode,
     pub(crate) service_diagnostic: DiagnosticInfo,
-    pub(crate) string_table: UaString,
+    pub(crate) string_table: UaArray<UaString>,
     pub(crate) additional_header: ExtensionObject,
 }
 
+impl ResponseHeader {
+    ///Parameters of the additional header, None when the server sent no AdditionalParametersType
+    pub(crate) fn additional_parameters(&self) -> Option<AdditionalParametersType> {
+        AdditionalParametersType::from_extension_object(&self.additional_header)
+    }
+}
+
 impl Serialize for ResponseHeader {
-    fn serialize(&self) -> Vec<u8> {
-        let mut result = self.timestamp.serialize();
-        result.extend_from_slice(&self.request_handle.serialize());
-        result.extend_from_slice(&self.service_result.serialize());
-        result.extend_from_slice(&self.service_diagnostic.serialize());
-        result.extend_from_slice(&self.string_table.serialize());
-        result
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        self.timestamp.serialize_into(buf);
+        self.request_handle.serialize_into(buf);
+        self.service_result.serialize_into(buf);
+        self.service_diagnostic.serialize_into(buf);
+        self.string_table.serialize_into(buf);
+        self.additional_header.serialize_into(buf);
     }
 }
 
 impl Deserialize for ResponseHeader {
     fn deserialize(data: &[u8]) -> MapperResult<(&[u8], Self)> {
-        let (data, timestamp) = DateTime::deserialize(data)?;
-        let (data, request_handle) = u32::deserialize(data)?;
-        let (data, service_result) = StatusCode::deserialize(data)?;
-        let (data, service_diagnostic) = DiagnosticInfo::deserialize(data)?;
-        let (data, string_table) = UaString::deserialize(data)?;
-        let (data, additional_header) = ExtensionObject::deserialize(data)?;
+        let in_field = |field: &'static str, remaining: usize| move |e: MapperError| e.in_field("ResponseHeader", field, remaining);
+        let (data, timestamp) = DateTime::deserialize(data).map_err(in_field("timestamp", data.len()))?;
+        let (data, request_handle) = u32::deserialize(data).map_err(in_field("request_handle", data.len()))?;
+        let (data, service_result) = StatusCode::deserialize(data).map_err(in_field("service_result", data.len()))?;
+        let (data, service_diagnostic) = DiagnosticInfo::deserialize(data).map_err(in_field("service_diagnostic", data.len()))?;
+        let (data, string_table) = UaArray::<UaString>::deserialize(data).map_err(in_field("string_table", data.len()))?;
+        let (data, additional_header) = ExtensionObject::deserialize(data).map_err(in_field("additional_header", data.len()))?;
         Ok((
             data,
             ResponseHeader {
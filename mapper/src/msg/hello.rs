@@ -5,7 +5,7 @@ use crate::encoding_prelude::*;
 
 use super::header::connection_message_header::ConnectionMessageHeader;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Mutate)]
 pub (crate) struct HelloMessage {
     pub(crate) connection_header: ConnectionMessageHeader,
     pub(crate) protocol_version: u32,
@@ -39,8 +39,8 @@ impl MessageType {
 }
 
 impl Serialize for MessageType {
-    fn serialize(&self) -> Vec<u8> {
-        self.value().to_vec()
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        buf.put_slice(self.value());
     }
 }
 impl Deserialize for MessageType {
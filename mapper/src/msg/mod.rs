@@ -9,16 +9,24 @@ use crate::crypto::encryption::EncryptionType;
 use crate::crypto::security_policy::SecurityPolicy;
 use crate::crypto::security_policy::SecurityPolicyUri;
 use crate::uatypes::security_mode::MessageSecurityMode;
+use crate::uatypes::diagnostic_info::DiagnosticInfo;
+use crate::uatypes::status_code::StatusCode;
 use crate::Deserialize;
 use crate::Serialize;
 use crate::result_prelude::*;
 
 use prelude::*;
+use header::request_header::RequestHeader;
+use header::response_header::ResponseHeader;
 
-use self::write::WriteRequest;
-use self::write::WriteResponse;
+use crate::fuzz::{Mutate, Mutation};
+use crate::uatypes::node_ids::ObjectIds;
 
 pub mod acknowledge;
+//only built for the bench and the tests, the messages are not part of the api
+#[cfg(any(test, feature = "bench"))]
+#[doc(hidden)]
+pub mod bench;
 pub mod close_secure_channel;
 pub mod create_session;
 pub mod active_sessions;
@@ -32,26 +40,22 @@ pub mod parser;
 pub mod reverse_hello;
 pub mod close_session;
 pub mod service_fault;
+pub mod services;
 pub mod read;
 pub mod nullsize;
 pub mod write;
+#[cfg(test)]
+mod tests;
 
 pub mod prelude {
     pub(crate) use crate::msg::acknowledge::*;
-    pub(crate) use crate::msg::active_sessions::*;
-    pub(crate) use crate::msg::close_secure_channel::*;
-    pub(crate) use crate::msg::create_session::*;
     pub(crate) use crate::msg::error::*;
     pub(crate) use crate::msg::message_type::*;
-    pub(crate) use crate::msg::get_endpoints::*;
     pub(crate) use crate::msg::hello::*;
-    pub(crate) use crate::msg::open_secure_channel::*;
     pub(crate) use crate::msg::reverse_hello::*;
     pub(crate) use crate::msg::service_fault::*;
-    pub(crate) use crate::msg::close_session::*;
-    pub(crate) use crate::msg::read::*;
+    pub(crate) use crate::msg::services::*;
     pub(crate) use crate::msg::nullsize::*;
-    pub(crate) use crate::msg::write::*;
 }
 
 //set the size of the message with an serialize message.
@@ -77,7 +81,7 @@ pub fn set_secure_channel_id(msg: &mut Vec<u8>, secure_channel_id: u32) {
 //Get the size of a serialized message.
 // It is usefull for parsing purpose
 pub fn get_size(msg: &[u8]) -> crate::MapperResult<u32> {
-    let size = &msg[4..8];
+    let size = msg.get(4..8).ok_or_else(|| MapperError::new(MapperErrorKind::ParsingError,"message too short to contain a size"))?;
     Ok(u32::deserialize(size)?.1)
 }
 
@@ -318,7 +322,10 @@ pub (crate) fn encrypt_msg(security_mode: u32,security_policy: &mut SecurityPoli
 
 fn decrypt_msg_asymmetric(security_policy: &mut SecurityPolicy, body:&mut Vec<u8>,offset: usize){
     let decrypted=match &mut security_policy.asymmetric_encryption {
-        Some(cipher) => cipher.decrypt(&body[offset..]),
+        Some(cipher) => match cipher.try_decrypt(&body[offset..]){
+            Ok(d)=>d,
+            Err(_)=>return,//left as is, the parsing will fail
+        },
         None => return,
     };
     body.truncate(offset);
@@ -329,7 +336,10 @@ fn decrypt_msg_asymmetric(security_policy: &mut SecurityPolicy, body:&mut Vec<u8
 pub fn decrypt_msg_symmetric_client(security_policy: &mut SecurityPolicy, body:&mut Vec<u8>,offset: usize){
     
     let decrypted=match &mut security_policy.symmetric_encryption_server {
-        Some(cipher) => cipher.decrypt(&body[offset..]),
+        Some(cipher) => match cipher.try_decrypt(&body[offset..]){
+            Ok(d)=>d,
+            Err(_)=>return,//left as is, the parsing will fail
+        },
         None => return,
     };
     
@@ -344,6 +354,9 @@ pub (crate) fn decrypt_msg(security_mode: u32,security_policy: &mut SecurityPoli
         return Ok(());
     }
     let offset = get_offset_to_encrypt(body)?;
+    if offset > body.len(){
+        return Err(MapperError::new(MapperErrorKind::ParsingError,"message shorter than its headers"));
+    }
     match type_required_encryption(&body){
         EncryptionType::Asymmetric=>decrypt_msg_asymmetric(security_policy, body,offset),
         EncryptionType::Symmetric if security_mode==MessageSecurityMode::SIGN_AND_ENCRYPT=>decrypt_msg_symmetric_client(security_policy, body,offset),
@@ -360,33 +373,7 @@ pub(crate) fn write_bytes(stream: &mut Vec<u8>, value: u8, count: usize) {
     stream.extend(vec![value; count])
 }
 
-///List of supported message.
-#[derive(Debug,DebugOutputEnum,Serialize)]
-pub (crate) enum Msg {
-    HelloMessage(HelloMessage),
-    AckowledgeMessage(AckowledgeMessage),
-    OpenSecureChannelRequest(OpenSecureChannelRequest),
-    OpenSecureChannelResponse(OpenSecureChannelResponse),
-    GetEndPointsRequest(GetEndPointsRequest),
-    GetEndPointsResponse(GetEndPointsResponse),
-    CloseSecureChannelRequest(CloseSecureChannelRequest),
-    CreateSessionRequest(CreateSessionRequest),
-    RevHelloMessage(RevHelloMessage),
-    ErrorMessage(ErrorMessage),
-    CreateSessionResponse(CreateSessionResponse),
-    CloseSessionRequest(CloseSessionRequest),
-    CloseSessionResponse(CloseSessionResponse),
-    ServiceFault(ServiceFault),
-    ActiveSessionRequest(ActiveSessionRequest),
-    ActiveSessionResponse(ActiveSessionResponse),
-    ReadRequest(ReadRequest),
-    ReadResponse(ReadResponse),
-    NullSize(NullSize),
-    WriteRequest(WriteRequest),
-    WriteResponse(WriteResponse),
-    
-}
-
+include!(concat!(env!("OUT_DIR"), "/messages.rs"));
 
 impl Msg {
     pub fn get_nonce(&self) -> &Vec<u8> {
@@ -396,6 +383,26 @@ impl Msg {
             _ => panic!("no nonce here"),
         }
     }
+
+    ///status of the responses: the service result of their header or the error of an Error message, None for the other messages
+    pub(crate) fn service_result(&self) -> Option<StatusCode> {
+        match self {
+            Msg::ErrorMessage(m) => Some(m.error),
+            _ => self.response_header().map(|h| h.service_result),
+        }
+    }
+
+    ///details of the status: the reason of an Error message or the diagnostic of the response header,
+    ///None when the message has none
+    pub(crate) fn diagnostic(&self) -> Option<String> {
+        match self {
+            Msg::ErrorMessage(m) => m.reason.value.clone().filter(|r| !r.is_empty()),
+            _ => self
+                .response_header()
+                .filter(|h| h.service_diagnostic != DiagnosticInfo::new())
+                .map(|h| h.service_diagnostic.describe(&h.string_table)),
+        }
+    }
 }
 
 
@@ -408,18 +415,20 @@ pub fn get_type(encoded_messages: &Vec<u8>) -> MessageType {
 
 ///this function return the the first bytes position that required encryption.
 pub fn get_offset_to_encrypt(encoded_messages: &Vec<u8>) -> crate::MapperResult<usize> {
-    let message_type = MessageType::from(&encoded_messages[..3])?;
+    let message_type = parser::get_message_type(encoded_messages)?;
     match message_type {
         MessageType::MSG => Ok(16),
         MessageType::OPN => {
             let mut offset = 12; //message header size
             for _i in 0..3{
-                let add_offset=match i32::deserialize(&encoded_messages[offset..]){
-                    Ok((_,-1))=>0,
-                    Ok((_,int))=>int,
-                    Err(_)=> 0,
+                let add_offset=match encoded_messages.get(offset..).map(i32::deserialize){
+                    Some(Ok((_,int))) if int>=0 =>int as usize,
+                    _=> 0,
                 };
-                offset = offset + add_offset as usize + 4; 
+                offset = offset.saturating_add(add_offset).saturating_add(4);
+            }
+            if offset>encoded_messages.len(){
+                return Err(MapperError::new(MapperErrorKind::ParsingError,"security header longer than the message"));
             }
              //loop i is equivalent but we cop with the case of an -1 meaning null Bytestring (only the size is encoded)   
             // offset = offset + i32::deserialize(&encoded_messages[offset..])?.1 as usize + 4; //4 is the size of the bytestring encoded legnth sec uri
@@ -1,10 +1,11 @@
 use crate::encoding_prelude::*;
+use crate::uatypes::generated::SecurityTokenRequestType;
 
 use super::header::prelude::*;
 use crate::uatypes::prelude::*;
 use crate::crypto::security_policy::SecurityPolicyUri;
 
-#[derive(Debug,Serialize,Deserialize)]
+#[derive(Debug,Serialize,Deserialize, Mutate)]
 pub struct NullSize {
     pub(crate) message_header: MessageHeader,
     pub(crate) security_header: AsymmetricSecurityHeader,
@@ -51,7 +52,7 @@ impl NullSize {
             sequence_number: 0,
             request_id: 0,
         };
-        let node_id: NodeId = NodeId::new_numeric(0, 446);
+        let node_id: NodeId = NodeId::new_numeric(0, ObjectIds::OpenSecureChannelRequest_Encoding_DefaultBinary);
         let request_header = RequestHeader {
             authentication_token: NodeId::new_numeric(0, 0),
             timestamp: DateTime::new_now(),
@@ -59,7 +60,7 @@ impl NullSize {
             return_diagnostic: 0,
             audit_entry: UaString::new(),
             timout_hint: 0,
-            additional_header: None,
+            additional_header: ExtensionObject::null(),
         };
         let client_protocol_version: u32 = 0;
         let request_type = SecurityTokenRequestType::ISSUE;
@@ -81,9 +82,3 @@ impl NullSize {
 }
 
 
-
-pub struct SecurityTokenRequestType(u32);
-impl SecurityTokenRequestType {
-    pub const ISSUE: u32 = 0;
-    pub const RENEW: u32 = 1;
-}
@@ -1,5 +1,6 @@
 use crate::crypto::security_policy::SecurityPolicy;
-use crate::encoding_prelude::*;
+use super::services::*;
+use crate::uatypes::generated::SecurityTokenRequestType;
 
 use super::header::prelude::*;
 use crate::uatypes::prelude::*;
@@ -7,19 +8,6 @@ use crate::{
     crypto::security_policy::SecurityPolicyUri
 };
 
-#[derive(Debug,Serialize,Deserialize)]
-pub struct OpenSecureChannelRequest {
-    pub(crate) message_header: MessageHeader,
-    pub(crate) security_header: AsymmetricSecurityHeader,
-    pub(crate) sequence_header: SequenceHeader,
-    pub(crate) node_id: NodeId,
-    pub(crate) request_header: RequestHeader,
-    pub(crate) client_protocol_version: u32,
-    pub(crate) request_type: u32,  //SecurityTokenRequestType,
-    pub(crate) security_mode: u32, //MessageSecurityMode,
-    pub(crate) client_nonce: ByteString,
-    pub(crate) requested_lifetime: u32,
-}
 
 impl Default for OpenSecureChannelRequest {
     fn default() -> Self {
@@ -29,7 +17,7 @@ impl Default for OpenSecureChannelRequest {
             sequence_number: 1,
             request_id: 1,
         };
-        let node_id: NodeId = NodeId::new_numeric(0, 446);
+        let node_id: NodeId = NodeId::new_numeric(0, ObjectIds::OpenSecureChannelRequest_Encoding_DefaultBinary);
         let request_header = RequestHeader {
             authentication_token: NodeId::new_numeric(0, 0),
             timestamp: DateTime::new_now(),
@@ -37,7 +25,7 @@ impl Default for OpenSecureChannelRequest {
             return_diagnostic: 0,
             audit_entry: UaString::new(),
             timout_hint: 0,
-            additional_header: None,
+            additional_header: ExtensionObject::null(),
         };
         let client_protocol_version: u32 = 0;
         let request_type = SecurityTokenRequestType::ISSUE;
@@ -92,7 +80,7 @@ impl OpenSecureChannelRequest {
             sequence_number: 0,
             request_id: 0,
         };
-        let node_id: NodeId = NodeId::new_numeric(0, 446);
+        let node_id: NodeId = NodeId::new_numeric(0, ObjectIds::OpenSecureChannelRequest_Encoding_DefaultBinary);
         let request_header = RequestHeader {
             authentication_token: NodeId::new_numeric(0, 0),
             timestamp: DateTime::new_now(),
@@ -100,7 +88,7 @@ impl OpenSecureChannelRequest {
             return_diagnostic: 0,
             audit_entry: UaString::new(),
             timout_hint: 0,
-            additional_header: None,
+            additional_header: ExtensionObject::null(),
         };
         let client_protocol_version: u32 = 0;
         let request_type = SecurityTokenRequestType::ISSUE;
@@ -123,23 +111,4 @@ impl OpenSecureChannelRequest {
 
 
 
-pub struct SecurityTokenRequestType(u32);
-impl SecurityTokenRequestType {
-    pub const ISSUE: u32 = 0;
-    pub const RENEW: u32 = 1;
-}
 
-#[derive(Debug,Serialize,Deserialize)]
-pub struct OpenSecureChannelResponse {
-    pub(crate) message_header: MessageHeader,
-    pub(crate) security_header: AsymmetricSecurityHeader,
-    pub(crate) sequence_header: SequenceHeader,
-    pub(crate) node_id: NodeId,
-    pub(crate) response_header: ResponseHeader,
-    pub(crate) server_protocol_version: u32,
-    pub(crate) secure_channel_id: u32,
-    pub(crate) token_id: u32,
-    pub(crate) created_at: DateTime,
-    pub(crate) revised_lifetime: u32,
-    pub(crate) server_nonce: ByteString,
-}
\ No newline at end of file
@@ -3,8 +3,13 @@ use crate::encoding_prelude::*;
 use super::{prelude::*, Msg};
 ///This function parse message base on their type. For message MSG (regular message such as client request we need to parse the NodeId)
 /// This function is usefull when the message is not encrypted. The type could be determine with operation on message header (never encrypted).
+/// Decoding errors give the path of the field that failed and its offset in the message.
 pub (crate) fn parse(data: &[u8]) -> MapperResult<Msg> {
-    let message_type = MessageType::from(&data[..3])?;
+    parse_message(data).map_err(|e| e.locate(data.len()))
+}
+
+fn parse_message(data: &[u8]) -> MapperResult<Msg> {
+    let message_type = get_message_type(data)?;
     let msg=match message_type {
         MessageType::HEL => Msg::HelloMessage(HelloMessage::deserialize(data)?.1),
         MessageType::ACK => Msg::AckowledgeMessage(AckowledgeMessage::deserialize(data)?.1),
@@ -19,23 +24,25 @@ pub (crate) fn parse(data: &[u8]) -> MapperResult<Msg> {
 //get the id number from node id. It is usefull only for MSG, CLO and OPN because
 //others message are identify by their type
 fn get_node_id(data: &[u8])->MapperResult<u32>{
-    let message_type = MessageType::from(&data[..3])?;
+    let message_type = get_message_type(data)?;
     let node_id=match message_type {
         //we take the node_id after the message header + security header (symmetric)+ sequence header (12+4+8=24)
-        MessageType::MSG => NodeId::deserialize(&data[24..])?.1,
-        MessageType::CLO => NodeId::deserialize(&data[24..])?.1,
+        MessageType::MSG => NodeId::deserialize(get_from(data,24)?)?.1,
+        MessageType::CLO => NodeId::deserialize(get_from(data,24)?)?.1,
         MessageType::OPN => {
                 let mut offset = 12; //message header size
+                //security policy uri, sender certificate and receiver thumbprint
                 for _i in 0..3{
-                    let add_offset=match i32::deserialize(&data[offset..]){
-                        Ok((_,-1))=>0,
-                        Ok((_,int))=>int,
-                        Err(_)=> 0,
+                    let (_,length)=i32::deserialize(get_from(data,offset)?)?;
+                    let add_offset= match length{
+                        -1=>0,
+                        l if l>=0 =>l as usize,
+                        _=> return Err(MapperError::new(MapperErrorKind::ParsingError,"Unknown_node_id: negative length in security header")),
                     };
-                    offset = offset + add_offset as usize + 4; 
+                    offset = offset.checked_add(add_offset+4).ok_or_else(|| MapperError::new(MapperErrorKind::ParsingError,"Unknown_node_id: security header too long"))?;
                 }
                 offset +=8; //sequence header size
-                NodeId::deserialize(&data[offset..])?.1
+                NodeId::deserialize(get_from(data,offset)?)?.1
             },
         _ => return Err(MapperError::new(MapperErrorKind::ParsingError,"Unknown_node_id: deserialisation failed")),
     };
@@ -47,28 +54,21 @@ fn get_node_id(data: &[u8])->MapperResult<u32>{
     }
 }
 
-//Get the right message deserialization from the node_id
-//This will evolved with message implemented
+///message type from the first three bytes, without panicking on short buffers
+pub (crate) fn get_message_type(data: &[u8])->MapperResult<MessageType>{
+    match data.get(..3){
+        Some(t)=>MessageType::from(t),
+        None=>Err(MapperError::new(MapperErrorKind::ParsingError,"message too short to contain a message type")),
+    }
+}
+
+//bounded equivalent of &data[offset..]
+fn get_from(data: &[u8],offset:usize)->MapperResult<&[u8]>{
+    data.get(offset..).ok_or_else(|| MapperError::new(MapperErrorKind::ParsingError,"message too short"))
+}
+
+//Get the right message deserialization from the node_id, the service messages are generated
 fn parse_node_id(data: &[u8]) -> MapperResult<Msg> {
     let id =get_node_id(data)?;
-    let msg=match id {
-        446 => Msg::OpenSecureChannelRequest(OpenSecureChannelRequest::deserialize(data)?.1),
-        449 => Msg::OpenSecureChannelResponse(OpenSecureChannelResponse::deserialize(data)?.1),
-        428 => Msg::GetEndPointsRequest(GetEndPointsRequest::deserialize(data)?.1),
-        431 => Msg::GetEndPointsResponse(GetEndPointsResponse::deserialize(data)?.1),
-        461 => Msg::CreateSessionRequest(CreateSessionRequest::deserialize(data)?.1),
-        464 => Msg::CreateSessionResponse(CreateSessionResponse::deserialize(data)?.1),
-        473 => Msg::CloseSessionRequest(CloseSessionRequest::deserialize(data)?.1),
-        476 => Msg::CloseSessionResponse(CloseSessionResponse::deserialize(data)?.1),
-        397 => Msg::ServiceFault(ServiceFault::deserialize(data)?.1),
-        467 => Msg::ActiveSessionRequest(ActiveSessionRequest::deserialize(data)?.1),
-        470 => Msg::ActiveSessionResponse(ActiveSessionResponse::deserialize(data)?.1),
-        452 => Msg::CloseSecureChannelRequest(CloseSecureChannelRequest::deserialize(data)?.1),
-        631 => Msg::ReadRequest(ReadRequest::deserialize(data)?.1),
-        634 => Msg::ReadResponse(ReadResponse::deserialize(data)?.1),
-        673 => Msg::WriteRequest(WriteRequest::deserialize(data)?.1),
-        676 => Msg::WriteResponse(WriteResponse::deserialize(data)?.1),
-        _ => return Err(MapperError::new(MapperErrorKind::ParsingError,"bad unknown type or not implemented yet")),
-    };
-    Ok(msg)
+    Msg::deserialize_service(id,data)
 }
\ No newline at end of file
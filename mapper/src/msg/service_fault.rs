@@ -1,4 +1,5 @@
 use crate::uatypes::node_id::NodeId;
+use crate::MapperError;
 use crate::encoding_prelude::*;
 
 use super::header::{prelude::*};
@@ -15,13 +16,14 @@ pub struct ServiceFault{
 
 impl Deserialize for ServiceFault{
     fn deserialize(data: &[u8]) -> MapperResult<(&[u8], Self)> where Self: Sized {
-        let (data_s,message_header)=MessageHeader::deserialize(data)?;
-        let (data,security_header)=SymmetricSecurityHeader::deserialize(data_s)?;
-        let (data,sequence_header)=SequenceHeader::deserialize(data)?;
+        let in_field=|field:&'static str,remaining:usize| move |e:MapperError| e.in_field("ServiceFault",field,remaining);
+        let (data_s,message_header)=MessageHeader::deserialize(data).map_err(in_field("message_header",data.len()))?;
+        let (data,security_header)=SymmetricSecurityHeader::deserialize(data_s).map_err(in_field("security_header",data_s.len()))?;
+        let (data,sequence_header)=SequenceHeader::deserialize(data).map_err(in_field("sequence_header",data.len()))?;
         let node_id=NodeId::deserialize(data);
         match node_id{
             Ok((data,node))=>{
-                let (data,response_header)=ResponseHeader::deserialize(data)?;
+                let (data,response_header)=ResponseHeader::deserialize(data).map_err(in_field("response_header",data.len()))?;
                 Ok((data,ServiceFault{
                     message_header ,
                     security_header:SecurityHeader::Symmetric(security_header) ,
@@ -31,10 +33,10 @@ impl Deserialize for ServiceFault{
                 }))
             },
             Err(_) =>{
-                let (data,security_header)=AsymmetricSecurityHeader::deserialize(data_s)?;
-                let (data,sequence_header)=SequenceHeader::deserialize(data)?;
-                let (data,node_id)=NodeId::deserialize(data)?;
-                let (data,response_header)=ResponseHeader::deserialize(data)?;
+                let (data,security_header)=AsymmetricSecurityHeader::deserialize(data_s).map_err(in_field("security_header",data_s.len()))?;
+                let (data,sequence_header)=SequenceHeader::deserialize(data).map_err(in_field("sequence_header",data.len()))?;
+                let (data,node_id)=NodeId::deserialize(data).map_err(in_field("node_id",data.len()))?;
+                let (data,response_header)=ResponseHeader::deserialize(data).map_err(in_field("response_header",data.len()))?;
                 Ok((data,ServiceFault{
                     message_header ,
                     security_header:SecurityHeader::Asymmetric(security_header) ,
@@ -0,0 +1,10 @@
+//! Service messages generated by build.rs from schema/Opc.Ua.Types.bsd: the structures of the schema
+//! starting with a request or a response header. Their body is preceded by the headers of the secure channel
+//! and by the encoding id of the body. The builders of the requests sent by the mapper are in the module of their service.
+
+use crate::encoding_prelude::*;
+use crate::msg::header::prelude::*;
+use crate::uatypes::generated::*;
+use crate::uatypes::prelude::*;
+
+include!(concat!(env!("OUT_DIR"), "/services.rs"));
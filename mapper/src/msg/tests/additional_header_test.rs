@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use crate::handle::Handle;
+    use crate::msg::header::additional_header::AdditionalParameterName;
+    use crate::msg::header::prelude::*;
+    use crate::msg::prelude::*;
+    use crate::msg::{parser, Msg};
+    use crate::uatypes::generated::{AdditionalParametersType, EphemeralKeyType};
+    use crate::uatypes::prelude::*;
+    use crate::{Deserialize, Serialize};
+
+    #[test]
+    fn parse_parameters() {
+        let parameters = AdditionalParametersType::parse("ECDHPolicyUri=http://opcfoundation.org/UA/SecurityPolicy#ECC_nistP256; Count:6=42").unwrap();
+        assert_eq!(parameters.parameters.len(), 2);
+        assert_eq!(parameters.get(AdditionalParameterName::ECDH_POLICY_URI).unwrap().serialize(), Variant::from("http://opcfoundation.org/UA/SecurityPolicy#ECC_nistP256").serialize());
+        assert_eq!(parameters.get("Count").unwrap().serialize(), Variant::from(42_i32).serialize());
+        assert!(parameters.get("Missing").is_none());
+        assert!(AdditionalParametersType::parse("").unwrap().parameters.is_empty());
+        assert!(AdditionalParametersType::parse("key").is_err());
+        assert!(AdditionalParametersType::parse("key:int=1").is_err());
+        assert!(AdditionalParametersType::parse("key:6=one").is_err());
+    }
+
+    #[test]
+    fn request_with_parameters() {
+        //without parameters the header is a null ExtensionObject
+        let mut request = ReadRequest::build(&NodeId::new_numeric(1, 42), &NodeId::new_numeric(0, 2258));
+        assert_eq!(request.request_header.additional_header.serialize(), vec![0; 3]);
+
+        let parameters = AdditionalParametersType::parse("Unexpected=value").unwrap();
+        request.request_header.additional_header = parameters.to_extension_object();
+        let encoded = request.serialize();
+        let msg = parser::parse(&encoded).unwrap();
+        assert_eq!(msg.serialize(), encoded);
+        match msg {
+            Msg::ReadRequest(m) => {
+                let decoded = AdditionalParametersType::from_extension_object(&m.request_header.additional_header).unwrap();
+                assert_eq!(decoded.get("Unexpected").unwrap().serialize(), Variant::from("value").serialize());
+            }
+            m => panic!("unexpected message {:?}", m),
+        }
+    }
+
+    #[test]
+    fn response_parameters() {
+        let key = EphemeralKeyType { public_key: ByteString::from(vec![4; 65]), signature: ByteString::from(vec![1; 64]) };
+        let mut parameters = AdditionalParametersType::new();
+        parameters.push(AdditionalParameterName::ECDH_POLICY_URI, Variant::from("http://opcfoundation.org/UA/SecurityPolicy#ECC_nistP256"));
+        parameters.push(AdditionalParameterName::ECDH_KEY, Variant::scalar(Value::ExtensionObject(ExtensionObject::from_body(&key))));
+        let header = ResponseHeader {
+            timestamp: DateTime::new(),
+            request_handle: 1,
+            service_result: StatusCode::new(0),
+            service_diagnostic: DiagnosticInfo::new(),
+            string_table: UaArray::new(),
+            additional_header: parameters.to_extension_object(),
+        };
+        let (_, decoded) = ResponseHeader::deserialize(&header.serialize()).unwrap();
+        let decoded = decoded.additional_parameters().unwrap();
+        assert!(decoded.get(AdditionalParameterName::ECDH_POLICY_URI).is_some());
+        let key = match &decoded.get(AdditionalParameterName::ECDH_KEY).unwrap().value[0] {
+            Value::ExtensionObject(object) => object.decode::<EphemeralKeyType>().unwrap(),
+            v => panic!("unexpected value {:?}", v),
+        };
+        assert_eq!(key.public_key, ByteString::from(vec![4; 65]));
+        //headers of other types are not additional parameters
+        let other = ResponseHeader { additional_header: ExtensionObject::null(), ..header };
+        assert!(other.additional_parameters().is_none());
+    }
+
+    #[test]
+    fn symbols_with_parameters() {
+        let (name, parameters) = Handle::split_symbol("read_req[ECDHPolicyUri=uri;Count:7=3]").unwrap();
+        assert_eq!(name, Handle::READ_REQ);
+        assert_eq!(parameters.unwrap().parameters.len(), 2);
+        let (name, parameters) = Handle::split_symbol(Handle::CLO_REQ).unwrap();
+        assert_eq!(name, Handle::CLO_REQ);
+        assert!(parameters.is_none());
+        assert_eq!(Handle::symbol_name("close_secure_channel_request[a=b]"), Handle::CLO_REQ);
+        //invalid parameters are an error, not a panic
+        assert!(Handle::split_symbol("read_req[Count]").is_err());
+        assert!(Handle::split_symbol("read_req[Count:x=3]").is_err());
+    }
+}
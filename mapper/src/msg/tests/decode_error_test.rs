@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use crate::crypto::security_policy::SecurityPolicyUri;
+    use crate::msg::header::prelude::*;
+    use crate::msg::prelude::*;
+    use crate::msg::{parser, set_size};
+    use crate::uatypes::extension_object::ExtensionObjectBody;
+    use crate::uatypes::prelude::*;
+    use crate::{Deserialize, Serialize};
+
+    fn endpoint(name: &str) -> EndpointDescription {
+        EndpointDescription {
+            endpoint_url: UaString::from("opc.tcp://localhost:4840"),
+            server: ApplicationDescription {
+                application_uri: UaString::from("urn:server"),
+                product_uri: UaString::new(),
+                application_name: LocalizedText::new(UaString::from("en"), UaString::from(name)),
+                application_type: 0,
+                gateway_server_uri: UaString::new(),
+                discovery_policy_uri: UaString::new(),
+                discovery_urls: UaArray::new(),
+            },
+            server_certificate: ByteString::new(),
+            security_mode: 1,
+            security_policy_uri: UaString::from(SecurityPolicyUri::None),
+            user_identity_tokens: UaArray::empty(),
+            transport_profile_uri: UaString::new(),
+            security_level: 0,
+        }
+    }
+
+    fn response() -> GetEndpointsResponse {
+        GetEndpointsResponse {
+            message_header: MessageHeader::build(MessageType::MSG, b'F', 1),
+            security_header: SymmetricSecurityHeader { token_id: 1 },
+            sequence_header: SequenceHeader { sequence_number: 2, request_id: 2 },
+            node_id: NodeId::new_numeric(0, 431),
+            response_header: ResponseHeader {
+                timestamp: DateTime::new(),
+                request_handle: 1,
+                service_result: StatusCode::new(0),
+                service_diagnostic: DiagnosticInfo::new(),
+                string_table: UaArray::new(),
+                additional_header: ExtensionObject {
+                    type_id: NodeId::new_numeric(0, 0),
+                    body: ExtensionObjectBody::None,
+                },
+            },
+            endpoints: UaArray::from(vec![endpoint("first"), endpoint("second"), endpoint("third")]),
+        }
+    }
+
+    #[test]
+    fn truncated_field() {
+        let response = response();
+        let endpoint = &response.endpoints[2];
+        //offset of the application name of the third endpoint
+        let offset = response.encoded_len() - endpoint.encoded_len()
+            + endpoint.endpoint_url.encoded_len()
+            + endpoint.server.application_uri.encoded_len()
+            + endpoint.server.product_uri.encoded_len();
+        let mut encoded = response.serialize();
+        encoded.truncate(offset + 3);
+        set_size(&mut encoded);
+
+        //the locale follows the encoding mask
+        let error = parser::parse(&encoded).unwrap_err();
+        assert_eq!(error.path().unwrap(), "GetEndpointsResponse.endpoints[2].server.application_name.locale");
+        assert_eq!(error.offset(), Some(offset + 1));
+        assert!(format!("{:?}", error).contains(&format!("GetEndpointsResponse.endpoints[2].server.application_name.locale: deserialize count failed at {:#x}", offset + 1)));
+    }
+
+    #[test]
+    fn outside_of_a_message() {
+        //errors of leaf types have no path
+        let error = u32::deserialize(&[1, 2]).unwrap_err();
+        assert!(error.path().is_none());
+        assert_eq!(format!("{:?}", error), "ParsingError info:deserialize count failed");
+        //without the message parser, the remaining length is given instead of the offset
+        let encoded = response().endpoints[0].serialize();
+        let error = EndpointDescription::deserialize(&encoded[..encoded.len() - 1]).unwrap_err();
+        assert_eq!(error.path().unwrap(), "EndpointDescription.security_level");
+        assert_eq!(error.offset(), None);
+        assert!(format!("{:?}", error).ends_with("with 0 bytes left"));
+        //arrays of leaf types
+        let error = Vec::<u32>::deserialize(&[2, 0, 0, 0, 1, 0, 0, 0, 2]).unwrap_err();
+        assert_eq!(error.path().unwrap(), "[1]");
+    }
+
+    #[test]
+    fn hand_written_decoders() {
+        let response = response();
+        //offset of the request handle
+        let offset = response.message_header.encoded_len()
+            + response.security_header.encoded_len()
+            + response.sequence_header.encoded_len()
+            + response.node_id.encoded_len()
+            + response.response_header.timestamp.encoded_len();
+        let mut encoded = response.serialize();
+        encoded.truncate(offset + 2);
+        set_size(&mut encoded);
+        let error = parser::parse(&encoded).unwrap_err();
+        assert_eq!(error.path().unwrap(), "GetEndpointsResponse.response_header.request_handle");
+        assert_eq!(error.offset(), Some(offset));
+
+        let error = NodeId::deserialize(&[0x07, 0, 0]).unwrap_err();
+        assert_eq!(error.path().unwrap(), "NodeId.encoding");
+        let error = NodeId::deserialize(&[0x03, 0, 0, 2, 0, 0, 0, b'a']).unwrap_err();
+        assert_eq!(error.path().unwrap(), "NodeId.identifier");
+        //an array of two Int32 given with the dimensions [3]
+        let error = Variant::deserialize(&[0xc6, 2, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 3, 0, 0, 0]).unwrap_err();
+        assert_eq!(error.path().unwrap(), "Variant.array_dimensions");
+        let error = Variant::deserialize(&[0x86, 2, 0, 0, 0, 1, 0, 0, 0]).unwrap_err();
+        assert_eq!(error.path().unwrap(), "Variant.value[1]");
+    }
+}
@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use crate::abstraction::{self, Coarse, OutputAbstraction, StatusName};
+    use crate::msg::header::prelude::*;
+    use crate::msg::prelude::*;
+    use crate::msg::parser;
+    use crate::uatypes::prelude::*;
+    use crate::Serialize;
+
+    #[test]
+    fn error_message_details() {
+        let error = ErrorMessage {
+            error: StatusCode::new(StatusCode::BadTcpMessageTypeInvalid),
+            reason: UaString::from("unknown message type"),
+            ..ErrorMessage::default()
+        };
+        let msg = parser::parse(&error.serialize()).unwrap();
+        assert_eq!(msg.service_result(), Some(StatusCode::new(StatusCode::BadTcpMessageTypeInvalid)));
+        assert_eq!(msg.diagnostic().as_deref(), Some("unknown message type"));
+        assert_eq!(Coarse.output(&msg), "Err,");
+        assert_eq!(StatusName.output(&msg), "Err_BadTcpMessageTypeInvalid,");
+        assert_eq!(abstraction::from_spec("class:reason").unwrap().output(&msg), "Err_Bad[reason],");
+        let error = ErrorMessage { reason: UaString::new(), ..ErrorMessage::default() };
+        assert_eq!(parser::parse(&error.serialize()).unwrap().diagnostic(), None);
+    }
+
+    #[test]
+    fn service_fault_diagnostics() {
+        let inner = DiagnosticInfo {
+            encoding_mask: 0x10,
+            additional_info: Some(UaString::from("no such node")),
+            ..DiagnosticInfo::new()
+        };
+        let diagnostic = DiagnosticInfo {
+            encoding_mask: 0x65,
+            symbolic_id: Some(0),
+            localized_text: Some(5),
+            inner_status_code: Some(StatusCode::BadInternalError),
+            inner_diagnostic_info: Some(Box::new(inner)),
+            ..DiagnosticInfo::new()
+        };
+        let fault = ServiceFault {
+            message_header: MessageHeader::build(MessageType::MSG, b'F', 1),
+            security_header: SecurityHeader::Symmetric(SymmetricSecurityHeader { token_id: 1 }),
+            sequence_header: SequenceHeader { sequence_number: 2, request_id: 2 },
+            node_id: NodeId::new_numeric(0, ObjectIds::ServiceFault_Encoding_DefaultBinary),
+            response_header: ResponseHeader {
+                timestamp: DateTime::new(),
+                request_handle: 0,
+                service_result: StatusCode::new(StatusCode::BadNodeIdUnknown),
+                service_diagnostic: diagnostic,
+                string_table: UaArray::from(vec![UaString::from("BadNodeIdUnknown")]),
+                additional_header: ExtensionObject::null(),
+            },
+        };
+        let msg = parser::parse(&fault.serialize()).unwrap();
+        assert_eq!(
+            msg.diagnostic().as_deref(),
+            Some("symbolic_id=BadNodeIdUnknown; localized_text=#5; inner_status=BadInternalError; inner=(additional_info=no such node)")
+        );
+        assert_eq!(Coarse.output(&msg), "Service_fault,");
+        assert_eq!(StatusName.output(&msg), "Service_fault_BadNodeIdUnknown,");
+        assert_eq!(abstraction::from_spec("name:diagnostics").unwrap().output(&msg), "Service_fault_BadNodeIdUnknown[diagnostics],");
+    }
+
+}
@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use crate::handle::Handle;
+    use crate::msg::header::prelude::*;
+    use crate::msg::prelude::*;
+    use crate::msg::{parser, Msg};
+    use crate::uatypes::prelude::*;
+    use crate::Serialize;
+
+    fn response_header(service_result: u32) -> ResponseHeader {
+        ResponseHeader {
+            timestamp: DateTime::new(),
+            request_handle: 0,
+            service_result: StatusCode::new(service_result),
+            service_diagnostic: DiagnosticInfo::new(),
+            string_table: UaArray::new(),
+            additional_header: ExtensionObject::null(),
+        }
+    }
+
+    #[test]
+    fn parse_items() {
+        assert_eq!(AttributeId::from_name("UserAccessLevel"), Some(AttributeId::USERACCESSLEVEL));
+        assert_eq!(AttributeId::from_name("AccessLevelEx"), Some(AttributeId::ACCESSLEVELEX));
+        assert_eq!(AttributeId::from_name("26"), Some(AttributeId::ACCESSRESTRICTIONS));
+        assert_eq!(AttributeId::from_name("Unknown"), None);
+        let item = ReadValueId::parse("ns=1;s=the.answer@RolePermissions").unwrap().1;
+        assert_eq!(item.node_id, NodeId::new_string(1, UaString::from("the.answer")));
+        assert_eq!(item.attribute_id, AttributeId::ROLEPERMISSIONS);
+        assert_eq!(ReadValueId::parse("i=2258").unwrap().1.attribute_id, AttributeId::VALUE);
+        assert!(ReadValueId::parse("i=2258@Unknown").is_err());
+        assert!(ReadValueId::parse("2258@Value").is_err());
+        let item = WriteValue::parse("ns=1;s=urn:a@DisplayName:21=en|Answer").unwrap().1;
+        assert_eq!(item.node_id, NodeId::new_string(1, UaString::from("urn:a")));
+        assert_eq!(item.attribute_id, AttributeId::DISPLAYNAME);
+        assert_eq!(item.value.serialize(), vec![0x01, 0x15, 0x03, 0x02, 0x00, 0x00, 0x00, b'e', b'n', 0x06, 0x00, 0x00, 0x00, b'A', b'n', b's', b'w', b'e', b'r']);
+        assert!(WriteValue::parse("i=1@Value=42").is_err());
+        assert!(WriteValue::parse("i=1:6=42").is_err());
+        assert!(WriteValue::parse("i=1@Value:6=x").is_err());
+        //string node ids and values with @
+        let (_, item) = ReadValueId::parse("ns=1;s=user@host").unwrap();
+        assert_eq!(item.node_id, NodeId::new_string(1, UaString::from("user@host")));
+        assert_eq!(item.attribute_id, AttributeId::VALUE);
+        let (_, item) = ReadValueId::parse("ns=1;s=user@host@DisplayName").unwrap();
+        assert_eq!(item.node_id, NodeId::new_string(1, UaString::from("user@host")));
+        assert_eq!(item.attribute_id, AttributeId::DISPLAYNAME);
+        let (_, item) = WriteValue::parse("ns=1;s=a@b@Value:12=c@Value:d").unwrap();
+        assert_eq!(item.node_id, NodeId::new_string(1, UaString::from("a@b")));
+        assert_eq!(item.attribute_id, AttributeId::VALUE);
+        //a namespace uri is kept to be resolved on each server
+        let (node_id, item) = ReadValueId::parse("nsu=urn:a;s=b@Value").unwrap();
+        assert_eq!(node_id.namespace_uri().map(String::as_str), Some("urn:a"));
+        assert_eq!(item.node_id, NodeId::new_string(0, UaString::from("b")));
+        let namespace_array = [UaString::from("http://opcfoundation.org/UA/"), UaString::from("urn:a")];
+        assert_eq!(node_id.resolve(&namespace_array).unwrap(), NodeId::new_string(1, UaString::from("b")));
+        let (node_id, _) = WriteValue::parse("nsu=urn:b;i=5@Value:6=42").unwrap();
+        assert!(node_id.resolve(&namespace_array).is_err());
+    }
+
+    #[test]
+    fn requests_with_items() {
+        let session = NodeId::new_numeric(1, 42);
+        let items = vec![ReadValueId::parse("ns=1;s=a@UserAccessLevel").unwrap().1, ReadValueId::parse("i=2258@AccessRestrictions").unwrap().1];
+        let request = ReadRequest::build_items(&session, &items).serialize();
+        match parser::parse(&request).unwrap() {
+            Msg::ReadRequest(m) => {
+                assert_eq!(m.nodes_to_read.len(), 2);
+                assert_eq!(m.nodes_to_read.iter().map(|i| i.attribute_id).collect::<Vec<u32>>(), vec![18, 26]);
+            }
+            m => panic!("unexpected message {:?}", m),
+        }
+        //the single node read is the Value attribute
+        assert_eq!(
+            ReadRequest::build(&session, &NodeId::new_numeric(0, 2258)).nodes_to_read.serialize(),
+            UaArray::from(vec![ReadValueId::parse("i=2258@Value").unwrap().1]).serialize()
+        );
+        let items = vec![WriteValue::parse("ns=1;s=a@DisplayName:21=Answer").unwrap().1, WriteValue::parse("ns=1;s=a@Value:6=42").unwrap().1];
+        match parser::parse(&WriteRequest::build_items(&session, &items).serialize()).unwrap() {
+            Msg::WriteRequest(m) => assert_eq!(m.nodes_to_write.len(), 2),
+            m => panic!("unexpected message {:?}", m),
+        }
+    }
+
+    #[test]
+    fn item_results() {
+        let mut denied = DataValue::empty();
+        denied.status = Some(StatusCode::BadUserAccessDenied);
+        let read = |service_result, result| {
+            Msg::ReadResponse(ReadResponse {
+                message_header: MessageHeader::build(MessageType::MSG, b'F', 1),
+                security_header: SymmetricSecurityHeader { token_id: 1 },
+                sequence_header: SequenceHeader { sequence_number: 2, request_id: 2 },
+                node_id: NodeId::new_numeric(0, ObjectIds::ReadResponse_Encoding_DefaultBinary),
+                response_header: response_header(service_result),
+                results: UaArray::from(result),
+                diagnostic_infos: UaArray::new(),
+            })
+        };
+        let response = read(StatusCode::Good, vec![DataValue::from_value(DataTypeId::BYTE, "3").unwrap(), denied]);
+        assert_eq!(Handle::update_items_response(&response), "ReadRepOK[Good|BadUserAccessDenied],");
+        //a single output for the learner
+        assert_eq!(Handle::update_items_response(&response).split(',').count(), 2);
+        assert_eq!(Handle::update_response(&response), Handle::R_READ_REPOK);
+        assert_eq!(Handle::update_items_response(&read(StatusCode::BadTooManyOperations, vec![])), Handle::R_READ_REPNOK);
+        let write = Msg::WriteResponse(WriteResponse {
+            message_header: MessageHeader::build(MessageType::MSG, b'F', 1),
+            security_header: SymmetricSecurityHeader { token_id: 1 },
+            sequence_header: SequenceHeader { sequence_number: 2, request_id: 2 },
+            node_id: NodeId::new_numeric(0, ObjectIds::WriteResponse_Encoding_DefaultBinary),
+            response_header: response_header(StatusCode::Good),
+            results: UaArray::from(vec![StatusCode::BadNotWritable, StatusCode::Good, 0x80FF0000]),
+            diagnostic_infos: UaArray::new(),
+        });
+        assert_eq!(Handle::update_items_response(&write), "WriteRepOK[BadNotWritable|Good|0x80FF0000],");
+    }
+}
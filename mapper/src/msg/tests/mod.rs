@@ -0,0 +1,7 @@
+mod additional_header_test;
+mod decode_error_test;
+mod fault_test;
+mod items_test;
+mod round_trip_test;
+mod serialize_test;
+mod write_test;
@@ -0,0 +1,237 @@
+#[cfg(test)]
+mod tests {
+    use crate::crypto::security_policy::{SecurityPolicy, SecurityPolicyUri};
+    use crate::msg::header::prelude::*;
+    use crate::msg::prelude::*;
+    use crate::msg::{parser, set_size};
+    use crate::uatypes::extension_object::ExtensionObjectBody;
+    use crate::uatypes::prelude::*;
+    use crate::{Deserialize, Serialize};
+
+    //decode a message as received and check that it is encoded again byte for byte
+    fn round_trip(encoded: &[u8]) {
+        let msg = parser::parse(encoded).unwrap();
+        assert_eq!(msg.serialize(), encoded, "{:?}", msg);
+    }
+
+    //same check for messages the parser does not recognize by their type
+    fn round_trip_as<T: Serialize + Deserialize + std::fmt::Debug>(encoded: &[u8]) {
+        let (rest, msg) = T::deserialize(encoded).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(msg.serialize(), encoded, "{:?}", msg);
+    }
+
+    //date with a sub-second part, as sent by servers
+    fn date(ticks: i64) -> DateTime {
+        DateTime::deserialize(&ticks.serialize()).unwrap().1
+    }
+
+    //three array encodings: null, empty and with content
+    fn arrays<T>(content: Vec<T>) -> Vec<UaArray<T>> {
+        vec![UaArray::new(), UaArray::empty(), UaArray::from(content)]
+    }
+
+    fn header(node_id: u32, string_table: UaArray<UaString>) -> Vec<u8> {
+        let mut result = MessageHeader::build(MessageType::MSG, b'F', 1).serialize();
+        result.extend_from_slice(&SymmetricSecurityHeader { token_id: 1 }.serialize());
+        result.extend_from_slice(&SequenceHeader { sequence_number: 2, request_id: 2 }.serialize());
+        result.extend_from_slice(&NodeId::new_numeric(0, node_id).serialize());
+        let response_header = ResponseHeader {
+            timestamp: date(133_000_000_001_234_567),
+            request_handle: 1,
+            service_result: StatusCode::new(0),
+            service_diagnostic: DiagnosticInfo::new(),
+            string_table,
+            additional_header: ExtensionObject {
+                type_id: NodeId::new_numeric(0, 0),
+                body: ExtensionObjectBody::None,
+            },
+        };
+        result.extend_from_slice(&response_header.serialize());
+        result
+    }
+
+    //response with the given encoded body
+    fn response(node_id: u32, string_table: UaArray<UaString>, body: &[Vec<u8>]) -> Vec<u8> {
+        let mut result = header(node_id, string_table);
+        for field in body {
+            result.extend_from_slice(field);
+        }
+        set_size(&mut result);
+        result
+    }
+
+    fn endpoint(user_identity_tokens: UaArray<UserTokenPolicy>, discovery_urls: UaArray<UaString>) -> EndpointDescription {
+        EndpointDescription {
+            endpoint_url: UaString::from("opc.tcp://localhost:4840"),
+            server: ApplicationDescription {
+                application_uri: UaString::from("urn:server"),
+                product_uri: UaString::new(),
+                application_name: LocalizedText::new(UaString::from("en"), UaString::from("server")),
+                application_type: 0,
+                gateway_server_uri: UaString::new(),
+                discovery_policy_uri: UaString::from(""),
+                discovery_urls,
+            },
+            server_certificate: ByteString::from(vec![]),
+            security_mode: 1,
+            security_policy_uri: UaString::from(SecurityPolicyUri::None),
+            user_identity_tokens,
+            transport_profile_uri: UaString::new(),
+            security_level: 0,
+        }
+    }
+
+    fn endpoints() -> Vec<UaArray<EndpointDescription>> {
+        let token = UserTokenPolicy {
+            policy_id: UaString::from("anonymous"),
+            token_type: 0,
+            issued_token_type: UaString::new(),
+            issuer_endpoint_url: UaString::new(),
+            security_policy_uri: UaString::new(),
+        };
+        let mut result = arrays(vec![]);
+        for tokens in arrays(vec![token]) {
+            for urls in arrays(vec![UaString::from("opc.tcp://localhost:4840")]) {
+                result.push(UaArray::from(vec![endpoint(tokens.clone(), urls)]));
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn null_and_empty_arrays() {
+        let null = UaArray::<u32>::new();
+        let empty = UaArray::<u32>::empty();
+        assert_eq!(null.serialize(), (-1_i32).serialize());
+        assert_eq!(empty.serialize(), 0_i32.serialize());
+        let (_, decoded) = UaArray::<u32>::deserialize(&null.serialize()).unwrap();
+        assert!(decoded.isnull());
+        let (_, decoded) = UaArray::<u32>::deserialize(&empty.serialize()).unwrap();
+        assert!(!decoded.isnull() && decoded.is_empty());
+        assert!(UaArray::<u32>::deserialize(&(-2_i32).serialize()).is_err());
+        //strings and byte strings
+        for encoded in [(-1_i32).serialize(), 0_i32.serialize()] {
+            assert_eq!(UaString::deserialize(&encoded).unwrap().1.serialize(), encoded);
+            assert_eq!(ByteString::deserialize(&encoded).unwrap().1.serialize(), encoded);
+        }
+    }
+
+    #[test]
+    fn connection_messages() {
+        round_trip(&HelloMessage::build(&UaString::from("opc.tcp://localhost:4840")).serialize());
+        round_trip(&HelloMessage::build(&UaString::new()).serialize());
+        round_trip(&AckowledgeMessage::default().serialize());
+        round_trip(&ErrorMessage::default().serialize());
+        round_trip(&RevHelloMessage::default().serialize());
+    }
+
+    #[test]
+    fn requests() {
+        let session = NodeId::new_numeric(1, 42);
+        let target = NodeId::new_string(2, UaString::from("target"));
+        let value = DataValue::from_value(DataTypeId::DOUBLE, "43.5").unwrap();
+        let policy = SecurityPolicy::new(SecurityPolicyUri::None);
+        round_trip(&OpenSecureChannelRequest::build(&ByteString::new(), &ByteString::new(), MessageSecurityMode::NONE, &policy, 3600000).serialize());
+        round_trip_as::<NullSize>(&NullSize::build(&ByteString::new(), &ByteString::new(), MessageSecurityMode::NONE, 3600000).serialize());
+        round_trip(&CloseSecureChannelRequest::build().serialize());
+        round_trip(&CreateSessionRequest::build(&UaString::from("opc.tcp://localhost:4840"), &ByteString::new(), 3600000.0).serialize());
+        round_trip(&CloseSessionRequest::build(&session).serialize());
+        round_trip(&WriteRequest::build(&session, &target, &value).serialize());
+        let mut request = GetEndpointsRequest::build(1, 1);
+        for locale_ids in arrays(vec![UaString::from("en")]) {
+            for profile_uris in arrays(vec![UaString::new(), UaString::from("")]) {
+                request.locale_ids = locale_ids.clone();
+                request.profile_uris = profile_uris;
+                round_trip(&request.serialize());
+            }
+        }
+        let mut request = ReadRequest::build(&session, &target);
+        for nodes in [UaArray::new(), UaArray::empty(), ReadRequest::build(&session, &target).nodes_to_read] {
+            request.nodes_to_read = nodes;
+            round_trip(&request.serialize());
+        }
+        let mut request = ActivateSessionRequest {
+            message_header: MessageHeader::build(MessageType::MSG, b'F', 1),
+            security_header: SymmetricSecurityHeader { token_id: 1 },
+            sequence_header: SequenceHeader { sequence_number: 2, request_id: 2 },
+            node_id: NodeId::new_numeric(0, 467),
+            request_header: CloseSessionRequest::build(&session).request_header,
+            client_signature: SignatureData::new(),
+            client_software_certificates: UaArray::new(),
+            locale_ids: UaArray::new(),
+            user_identity_token: ExtensionObject::anon(&UaString::from("anonymous")),
+            user_token_signature: SignatureData::new(),
+        };
+        for locale_ids in arrays(vec![UaString::from("en-US")]) {
+            request.locale_ids = locale_ids;
+            round_trip(&request.serialize());
+        }
+    }
+
+    #[test]
+    fn responses() {
+        let diagnostic = DiagnosticInfo::new();
+        for string_table in arrays(vec![UaString::from("reason")]) {
+            round_trip(&response(476, string_table.clone(), &[]));
+            round_trip(&response(397, string_table, &[]));
+        }
+        for endpoints in endpoints() {
+            round_trip(&response(431, UaArray::new(), &[endpoints.serialize()]));
+            round_trip(&response(464, UaArray::empty(), &[
+                NodeId::new_numeric(1, 1).serialize(),
+                NodeId::new_bytestring(0, ByteString::from(vec![1, 2, 3])).serialize(),
+                1200000.5_f64.serialize(),
+                ByteString::from(vec![0; 32]).serialize(),
+                ByteString::new().serialize(),
+                endpoints.serialize(),
+                (-1_i32).serialize(),
+                SignatureData::new().serialize(),
+                0_u32.serialize(),
+            ]));
+        }
+        //status codes are encoded as u32
+        for results in arrays(vec![0_u32, StatusCode::Bad]) {
+            for diagnostics in arrays(vec![diagnostic.clone()]) {
+                round_trip(&response(470, UaArray::new(), &[ByteString::from(vec![0; 32]).serialize(), results.serialize(), diagnostics.serialize()]));
+            }
+        }
+        let value = DataValue::from_value(DataTypeId::DOUBLE, "43.5").unwrap();
+        for values in arrays(vec![value, DataValue::empty()]) {
+            for diagnostics in arrays(vec![diagnostic.clone()]) {
+                round_trip(&response(634, UaArray::new(), &[values.serialize(), diagnostics.serialize()]));
+            }
+        }
+        //values that are null, empty and filled arrays of doubles
+        for array in arrays(vec![43.5_f64]) {
+            let mut value = vec![0x01, DataTypeId::DOUBLE | DataTypeId::ARRAY_VALUE_ENCODED];
+            value.extend_from_slice(&array.serialize());
+            round_trip(&response(634, UaArray::new(), &[1_i32.serialize(), value, (-1_i32).serialize()]));
+        }
+        for results in arrays(vec![0_u32, StatusCode::Bad]) {
+            for diagnostics in arrays(vec![diagnostic.clone()]) {
+                round_trip(&response(676, UaArray::new(), &[results.serialize(), diagnostics.serialize()]));
+            }
+        }
+    }
+
+    #[test]
+    fn open_secure_channel_response() {
+        let mut encoded = MessageHeader::default().serialize();
+        encoded.extend_from_slice(&AsymmetricSecurityHeader::default().serialize());
+        encoded.extend_from_slice(&SequenceHeader { sequence_number: 1, request_id: 1 }.serialize());
+        encoded.extend_from_slice(&NodeId::new_numeric(0, 449).serialize());
+        let header = header(449, UaArray::new());
+        //response header of the symmetric message built by header()
+        let symmetric_prefix = 24 + NodeId::new_numeric(0, 449).serialize().len();
+        encoded.extend_from_slice(&header[symmetric_prefix..]);
+        encoded.extend_from_slice(&0_u32.serialize());
+        encoded.extend_from_slice(&5_u32.serialize());
+        encoded.extend_from_slice(&1_u32.serialize());
+        encoded.extend_from_slice(&date(133_000_000_009_999_999).serialize());
+        encoded.extend_from_slice(&3600000_u32.serialize());
+        encoded.extend_from_slice(&ByteString::from(vec![]).serialize());
+        set_size(&mut encoded);
+        round_trip(&encoded);
+    }
+}
@@ -0,0 +1,16 @@
+#[cfg(test)]
+mod tests {
+    use crate::msg::bench;
+
+    //the allocations and the time are measured by benches/serialize_bench.rs
+    #[test]
+    fn encoded_len() {
+        let mut buf = Vec::with_capacity(4096);
+        for sample in bench::samples() {
+            assert_eq!(sample.encoded_len(), sample.serialize().len(), "{}", sample.name);
+            sample.serialize_into(&mut buf);
+            assert_eq!(buf, sample.serialize(), "{}", sample.name);
+            buf.clear();
+        }
+    }
+}
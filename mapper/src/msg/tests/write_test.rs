@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use crate::handle::Handle;
+    use crate::msg::header::prelude::*;
+    use crate::msg::prelude::*;
+    use crate::msg::Msg;
+    use crate::uatypes::data_value;
+    use crate::uatypes::prelude::*;
+    use crate::Serialize;
+
+    #[test]
+    fn data_value_fields() {
+        let value = DataValue::from_value(DataTypeId::INT_32, "43").unwrap();
+        //only the value by default
+        assert_eq!(value.with_fields(0).serialize(), vec![0x01, 0x06, 43, 0, 0, 0]);
+        let status = value.with_fields(data_value::EncodingValue::STATUS_CODE);
+        assert_eq!(status.serialize(), vec![0x03, 0x06, 43, 0, 0, 0, 0, 0, 0, 0]);
+        let mut full = value.clone();
+        full.status = Some(StatusCode::UncertainInitialValue);
+        full.source_time_stamp = Some(DateTime::parse("1970-01-01").unwrap());
+        let full = full.with_fields(
+            data_value::EncodingValue::STATUS_CODE | data_value::EncodingValue::SOURCE_TIME_STAMP | data_value::EncodingValue::SERVER_TIME_STAMP,
+        );
+        let encoded = full.serialize();
+        assert_eq!(encoded[0], 0x0f);
+        assert_eq!(encoded[6..10], StatusCode::UncertainInitialValue.to_le_bytes());
+        assert_eq!(encoded[10..18], 116444736000000000_i64.to_le_bytes());
+        //the server timestamp is the current time
+        assert!(full.server_time_stamp.unwrap().get() > &0);
+    }
+
+    #[test]
+    fn write_symbols() {
+        assert_eq!(Handle::write_fields(Handle::WRITE_REQ), Some(0));
+        assert_eq!(Handle::write_fields(Handle::WRITE_REQ_STATUS), Some(data_value::EncodingValue::STATUS_CODE));
+        assert_eq!(Handle::write_fields(Handle::WRITE_REQ_SOURCE_TIMESTAMP), Some(data_value::EncodingValue::SOURCE_TIME_STAMP));
+        assert_eq!(Handle::write_fields(Handle::WRITE_REQ_SERVER_TIMESTAMP), Some(data_value::EncodingValue::SERVER_TIME_STAMP));
+        assert_eq!(Handle::write_fields(Handle::WRITE_REQ_FULL), Some(0x0e));
+        assert_eq!(Handle::write_fields("write_req_server_status"), Some(0x0a));
+        assert_eq!(Handle::write_fields("write_req_"), None);
+        assert_eq!(Handle::write_fields("write_req_value"), None);
+        assert_eq!(Handle::write_fields(Handle::READ_REQ), None);
+    }
+
+    #[test]
+    fn write_response_results() {
+        let response = |service_result: u32, results: Vec<u32>| {
+            Msg::WriteResponse(WriteResponse {
+                message_header: MessageHeader::build(MessageType::MSG, b'F', 1),
+                security_header: SymmetricSecurityHeader { token_id: 1 },
+                sequence_header: SequenceHeader { sequence_number: 2, request_id: 2 },
+                node_id: NodeId::new_numeric(0, ObjectIds::WriteResponse_Encoding_DefaultBinary),
+                response_header: ResponseHeader {
+                    timestamp: DateTime::new(),
+                    request_handle: 0,
+                    service_result: StatusCode::new(service_result),
+                    service_diagnostic: DiagnosticInfo::new(),
+                    string_table: UaArray::new(),
+                    additional_header: ExtensionObject::null(),
+                },
+                results: UaArray::from(results),
+                diagnostic_infos: UaArray::new(),
+            })
+        };
+        assert_eq!(Handle::update_response(&response(StatusCode::Good, vec![StatusCode::Good])), Handle::R_WRITE_REPOK);
+        assert_eq!(Handle::update_response(&response(StatusCode::BadSessionIdInvalid, vec![])), Handle::R_WRITE_REPNOK);
+        //the coarse output of write_req only depends on the service result
+        assert_eq!(Handle::update_response(&response(StatusCode::Good, vec![StatusCode::BadWriteNotSupported])), Handle::R_WRITE_REPOK);
+        //write_req_status, write_req_source... give the result of the write
+        assert_eq!(
+            Handle::write_fields_response(&response(StatusCode::Good, vec![StatusCode::BadWriteNotSupported])).unwrap(),
+            "WriteRepBadWriteNotSupported,"
+        );
+        assert!(Handle::write_fields_response(&response(StatusCode::Good, vec![StatusCode::Good])).is_none());
+        assert!(Handle::write_fields_response(&response(StatusCode::BadSessionIdInvalid, vec![])).is_none());
+    }
+}
@@ -1,23 +1,18 @@
 
-use std::fmt::Debug;
 
 use crate::uatypes::prelude::*;
 use crate::msg::header::prelude::*;
-use crate::encoding_prelude::*;
+use super::services::*;
 
-#[derive(Debug,Serialize,Deserialize)]
-pub(crate) struct WriteRequest {
-    pub(crate) message_header: MessageHeader,
-    pub(crate) security_header: SymmetricSecurityHeader,
-    pub(crate) sequence_header: SequenceHeader,
-    pub(crate) node_id: NodeId,
-    pub(crate) request_header: RequestHeader,
-    pub(crate) nodes_to_write: Vec::<WriteValue>,
-}
 
 
 impl WriteRequest {
     pub fn build(session_node_id:&NodeId,node_id_to_write:&NodeId,data_value:&DataValue)-> WriteRequest{
+        WriteRequest::build_items(session_node_id,&[WriteValue::build(node_id_to_write,data_value)])
+    }
+
+    ///write of several attributes, of one or several nodes
+    pub(crate) fn build_items(session_node_id:&NodeId,items:&[WriteValue])-> WriteRequest{
         let message_header = MessageHeader {
             message_type: super::message_type::MessageType::MSG,
             is_final: b'F',
@@ -30,7 +25,7 @@ impl WriteRequest {
             sequence_number: 2,
             request_id: 2,
         };
-        let node_id: NodeId = NodeId::new_numeric(0, 673);
+        let node_id: NodeId = NodeId::new_numeric(0, ObjectIds::WriteRequest_Encoding_DefaultBinary);
         let request_header = RequestHeader {
             authentication_token: session_node_id.clone(),
             timestamp: DateTime::new_now(),
@@ -38,14 +33,9 @@ impl WriteRequest {
             return_diagnostic: 0,
             audit_entry: UaString::new(),
             timout_hint: 0,
-            additional_header: None,
+            additional_header: ExtensionObject::null(),
         };
-        let nodes_to_write= vec![WriteValue{
-            node_id:node_id_to_write.clone(),
-            attribute_id:AttributeId::VALUE,
-            index_range:UaString::new(),
-            value: data_value.clone()
-        }];
+        let nodes_to_write= UaArray::from(items.to_vec());
         WriteRequest{
             message_header: message_header,
             security_header: security_header,
@@ -59,14 +49,4 @@ impl WriteRequest {
 
 
 
-#[derive(Deserialize,Serialize,Debug)]
-pub(crate) struct WriteResponse{
-    pub(crate) message_header: MessageHeader,
-    pub(crate) security_header: SymmetricSecurityHeader,
-    pub(crate) sequence_header: SequenceHeader,
-    pub(crate) node_id: NodeId,
-    pub(crate) response_header: ResponseHeader,
-    pub(crate) results: Vec::<u32>,
-    pub(crate) diagnostic_info: Vec::<DiagnosticInfo>,
-}
 
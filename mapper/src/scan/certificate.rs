@@ -0,0 +1,339 @@
+//! Analysis of the server certificates.
+//!
+//! A [`CertificateReport`] gives the key, the signature algorithm, the validity period, the names of the
+//! subjectAltName, the key usages and the chain of a certificate sent by a server, and the [`findings`](CertificateReport::findings)
+//! against the requirements of OPC UA for application instance certificates (Part 6, 6.2.2).
+//! The certificate of a server may be a chain: the DER certificates are concatenated, the first one is the certificate of the server.
+
+use std::fmt;
+
+use openssl::asn1::Asn1Time;
+use openssl::pkey::Id;
+use openssl::x509::{X509NameRef, X509VerifyResult, X509};
+
+use super::json_string;
+use crate::crypto::x509::{oid_text, split_chain, tlv};
+use crate::result_prelude::*;
+
+/// Validity of a certificate at the time of the analysis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Validity {
+    Valid,
+    NotYetValid,
+    Expired,
+}
+
+/// Names of the key usages, in the order of the bits of the KeyUsage extension.
+const KEY_USAGES: [&str; 9] = [
+    "digitalSignature",
+    "nonRepudiation",
+    "keyEncipherment",
+    "dataEncipherment",
+    "keyAgreement",
+    "keyCertSign",
+    "cRLSign",
+    "encipherOnly",
+    "decipherOnly",
+];
+
+/// Key usages required for the RSA keys of application instance certificates.
+const REQUIRED_RSA_KEY_USAGES: [&str; 3] = ["digitalSignature", "keyEncipherment", "dataEncipherment"];
+
+const SERVER_AUTH: &str = "serverAuth";
+
+//DER of the object identifiers of the extensions
+const OID_KEY_USAGE: &[u8] = &[0x55, 0x1d, 0x0f];
+const OID_EXTENDED_KEY_USAGE: &[u8] = &[0x55, 0x1d, 0x25];
+const OID_BASIC_CONSTRAINTS: &[u8] = &[0x55, 0x1d, 0x13];
+
+/// Analysis of a server certificate.
+#[derive(Debug, Clone)]
+pub struct CertificateReport {
+    pub(crate) subject: String,
+    pub(crate) issuer: String,
+    ///RSA, EC, ...
+    pub(crate) key_type: String,
+    pub(crate) key_bits: u32,
+    pub(crate) signature_algorithm: String,
+    pub(crate) not_before: String,
+    pub(crate) not_after: String,
+    pub(crate) validity: Validity,
+    ///URIs of the subjectAltName
+    pub(crate) application_uris: Vec<String>,
+    pub(crate) dns_names: Vec<String>,
+    ///application uri of the endpoints using the certificate
+    pub(crate) expected_application_uri: Option<String>,
+    ///None without KeyUsage extension
+    pub(crate) key_usage: Option<Vec<&'static str>>,
+    ///None without ExtendedKeyUsage extension
+    pub(crate) extended_key_usage: Option<Vec<String>>,
+    pub(crate) ca: bool,
+    pub(crate) self_signed: bool,
+    ///subjects of the other certificates sent with the certificate
+    pub(crate) chain: Vec<String>,
+    ///the chain goes up to a self-signed certificate; None when it is complete, otherwise the missing issuer
+    pub(crate) missing_issuer: Option<String>,
+}
+
+impl CertificateReport {
+    ///analysis of a certificate (or a chain of DER certificates) of a server whose endpoints have the application uri `application_uri`
+    pub fn analyze(certificate: &[u8], application_uri: Option<&str>) -> MapperResult<CertificateReport> {
+        let error = |message: &str| MapperError::new(MapperErrorKind::ParsingError, message);
+        let certificates: Vec<X509> = split_chain(certificate)
+            .ok_or_else(|| error("invalid DER certificate chain"))?
+            .iter()
+            .map(|der| X509::from_der(der))
+            .collect::<Result<_, _>>()
+            .map_err(|_| error("invalid certificate"))?;
+        let x509 = certificates.first().ok_or_else(|| error("empty certificate"))?;
+        let public_key = x509.public_key().map_err(|_| error("invalid public key"))?;
+        let key_type = match public_key.id() {
+            Id::RSA => "RSA",
+            Id::EC => "EC",
+            Id::DSA => "DSA",
+            Id::ED25519 => "Ed25519",
+            _ => "unknown",
+        };
+        let signature_algorithm = x509.signature_algorithm().object().nid().long_name().unwrap_or("unknown").to_string();
+        let now = Asn1Time::days_from_now(0).map_err(|_| error("no current time"))?;
+        let validity = if x509.not_before() > now {
+            Validity::NotYetValid
+        } else if x509.not_after() < now {
+            Validity::Expired
+        } else {
+            Validity::Valid
+        };
+        let alt_names = x509.subject_alt_names();
+        let alt_names: Vec<_> = alt_names.iter().flatten().collect();
+        let application_uris = alt_names.iter().filter_map(|n| n.uri().map(String::from)).collect();
+        let dns_names = alt_names.iter().filter_map(|n| n.dnsname().map(String::from)).collect();
+        let der = x509.to_der().map_err(|_| error("invalid certificate"))?;
+        let extensions = extensions(&der).unwrap_or_default();
+        let extension = |oid: &[u8]| extensions.iter().find(|(id, _)| *id == oid).map(|(_, value)| *value);
+        let self_signed = x509.issued(x509) == X509VerifyResult::OK && x509.verify(&public_key).unwrap_or(false);
+        Ok(CertificateReport {
+            subject: name(x509.subject_name()),
+            issuer: name(x509.issuer_name()),
+            key_type: key_type.to_string(),
+            key_bits: public_key.bits(),
+            signature_algorithm,
+            not_before: x509.not_before().to_string(),
+            not_after: x509.not_after().to_string(),
+            validity,
+            application_uris,
+            dns_names,
+            expected_application_uri: application_uri.map(String::from),
+            key_usage: extension(OID_KEY_USAGE).map(key_usage),
+            extended_key_usage: extension(OID_EXTENDED_KEY_USAGE).map(extended_key_usage),
+            ca: extension(OID_BASIC_CONSTRAINTS).is_some_and(is_ca),
+            self_signed,
+            chain: certificates[1..].iter().map(|c| name(c.subject_name())).collect(),
+            missing_issuer: missing_issuer(&certificates),
+        })
+    }
+
+    ///audit findings: weak key or signature, validity, application uri, key usages and chain
+    pub fn findings(&self) -> Vec<String> {
+        let mut findings = Vec::new();
+        if self.key_type == "RSA" && self.key_bits < 2048 {
+            findings.push(format!("RSA key of {} bits, at least 2048 bits expected", self.key_bits));
+        }
+        let signature = self.signature_algorithm.to_lowercase();
+        if signature.contains("md5") || signature.contains("sha1") {
+            findings.push(format!("weak signature algorithm {}", self.signature_algorithm));
+        }
+        match self.validity {
+            Validity::Expired => findings.push(format!("expired on {}", self.not_after)),
+            Validity::NotYetValid => findings.push(format!("not valid before {}", self.not_before)),
+            Validity::Valid => {}
+        }
+        match (&self.expected_application_uri, self.application_uris.is_empty()) {
+            (_, true) => findings.push("no application uri in subjectAltName".to_string()),
+            (Some(uri), false) if !self.application_uris.contains(uri) => {
+                findings.push(format!("application uri {} of subjectAltName differs from the application uri {} of the endpoints", self.application_uris.join(" "), uri))
+            }
+            _ => {}
+        }
+        match &self.key_usage {
+            None => findings.push("no KeyUsage extension".to_string()),
+            Some(usages) => {
+                let required = if self.key_type == "RSA" { &REQUIRED_RSA_KEY_USAGES[..] } else { &REQUIRED_RSA_KEY_USAGES[..1] };
+                let missing: Vec<&str> = required.iter().filter(|u| !usages.contains(u)).copied().collect();
+                if !missing.is_empty() {
+                    findings.push(format!("key usage without {}", missing.join(" ")));
+                }
+                if self.self_signed && !usages.contains(&"keyCertSign") {
+                    findings.push("self-signed certificate without keyCertSign key usage".to_string());
+                }
+            }
+        }
+        match &self.extended_key_usage {
+            None => findings.push("no ExtendedKeyUsage extension".to_string()),
+            Some(usages) if !usages.iter().any(|u| u == SERVER_AUTH) => findings.push("extended key usage without serverAuth".to_string()),
+            _ => {}
+        }
+        if let Some(issuer) = &self.missing_issuer {
+            findings.push(format!("incomplete chain, the certificate of {} is not sent", issuer));
+        }
+        findings
+    }
+
+    pub fn to_json(&self) -> String {
+        let list = |values: &[String]| values.iter().map(|v| json_string(v)).collect::<Vec<String>>().join(",");
+        let optional_list = |values: Option<Vec<String>>| match values {
+            Some(values) => format!("[{}]", list(&values)),
+            None => "null".to_string(),
+        };
+        let optional = |value: Option<&String>| value.map_or("null".to_string(), |v| json_string(v));
+        format!(
+            concat!(
+                "{{\"subject\":{},\"issuer\":{},\"key_type\":{},\"key_bits\":{},\"signature_algorithm\":{},",
+                "\"not_before\":{},\"not_after\":{},\"validity\":{},\"application_uris\":[{}],\"dns_names\":[{}],",
+                "\"expected_application_uri\":{},\"key_usage\":{},\"extended_key_usage\":{},\"ca\":{},\"self_signed\":{},",
+                "\"chain\":[{}],\"missing_issuer\":{},\"findings\":[{}]}}"
+            ),
+            json_string(&self.subject),
+            json_string(&self.issuer),
+            json_string(&self.key_type),
+            self.key_bits,
+            json_string(&self.signature_algorithm),
+            json_string(&self.not_before),
+            json_string(&self.not_after),
+            json_string(&format!("{:?}", self.validity)),
+            list(&self.application_uris),
+            list(&self.dns_names),
+            optional(self.expected_application_uri.as_ref()),
+            optional_list(self.key_usage.as_ref().map(|u| u.iter().map(|s| s.to_string()).collect())),
+            optional_list(self.extended_key_usage.clone()),
+            self.ca,
+            self.self_signed,
+            list(&self.chain),
+            optional(self.missing_issuer.as_ref()),
+            list(&self.findings())
+        )
+    }
+}
+
+impl fmt::Display for CertificateReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let none = || "none".to_string();
+        writeln!(f, "subject: {}", self.subject)?;
+        writeln!(f, "issuer: {}{}", self.issuer, if self.self_signed { " (self-signed)" } else { "" })?;
+        writeln!(f, "key: {} {} bits, signature {}", self.key_type, self.key_bits, self.signature_algorithm)?;
+        writeln!(f, "validity: {} to {} ({:?})", self.not_before, self.not_after, self.validity)?;
+        writeln!(f, "application uri: {}", if self.application_uris.is_empty() { none() } else { self.application_uris.join(" ") })?;
+        writeln!(f, "dns names: {}", if self.dns_names.is_empty() { none() } else { self.dns_names.join(" ") })?;
+        writeln!(f, "key usage: {}", self.key_usage.as_ref().map_or_else(none, |u| u.join(" ")))?;
+        writeln!(f, "extended key usage: {}", self.extended_key_usage.as_ref().map_or_else(none, |u| u.join(" ")))?;
+        writeln!(f, "ca: {}", self.ca)?;
+        for subject in &self.chain {
+            writeln!(f, "chain: {}", subject)?;
+        }
+        for finding in self.findings() {
+            writeln!(f, "finding: {}", finding)?;
+        }
+        Ok(())
+    }
+}
+
+//C=fr, O=tsp, CN=server
+fn name(name: &X509NameRef) -> String {
+    name.entries()
+        .map(|e| {
+            let field = e.object().nid().short_name().unwrap_or("?");
+            let value = e.data().as_utf8().map(|v| v.to_string()).unwrap_or_default();
+            format!("{}={}", field, value)
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+//issuer missing to go from the first certificate up to a self-signed one with the other certificates, None if the chain is complete
+fn missing_issuer(certificates: &[X509]) -> Option<String> {
+    let mut current = certificates.first()?;
+    for _ in 0..certificates.len() {
+        if current.issued(current) == X509VerifyResult::OK {
+            return None;
+        }
+        let issuer = certificates.iter().find(|c| {
+            c.issued(current) == X509VerifyResult::OK && c.public_key().is_ok_and(|k| current.verify(&k).unwrap_or(false))
+        });
+        match issuer {
+            Some(issuer) => current = issuer,
+            None => break,
+        }
+    }
+    Some(name(current.issuer_name()))
+}
+
+//object identifier and value of the extensions of a DER certificate
+fn extensions(der: &[u8]) -> Option<Vec<(&[u8], &[u8])>> {
+    let (_, certificate, _) = tlv(der)?;
+    let (_, mut tbs, _) = tlv(certificate)?;
+    //the extensions are the explicit [3] field, after the subject public key info
+    let mut fields = None;
+    while !tbs.is_empty() {
+        let (tag, content, rest) = tlv(tbs)?;
+        if tag == 0xa3 {
+            fields = Some(tlv(content)?.1);
+        }
+        tbs = rest;
+    }
+    let mut data = fields?;
+    let mut extensions = Vec::new();
+    while !data.is_empty() {
+        let (_, mut extension, rest) = tlv(data)?;
+        data = rest;
+        let (_, oid, rest) = tlv(extension)?;
+        extension = rest;
+        let (mut tag, mut value, rest) = tlv(extension)?;
+        //critical flag
+        if tag == 0x01 {
+            (tag, value, _) = tlv(rest)?;
+        }
+        if tag == 0x04 {
+            extensions.push((oid, value));
+        }
+    }
+    Some(extensions)
+}
+
+//BIT STRING of the KeyUsage extension
+fn key_usage(value: &[u8]) -> Vec<&'static str> {
+    let bits = match tlv(value) {
+        Some((0x03, bits, _)) if !bits.is_empty() => &bits[1..],
+        _ => return Vec::new(),
+    };
+    KEY_USAGES.iter().enumerate().filter(|(i, _)| bits.get(i / 8).is_some_and(|b| b & (0x80 >> (i % 8)) != 0)).map(|(_, u)| *u).collect()
+}
+
+//SEQUENCE of object identifiers of the ExtendedKeyUsage extension
+fn extended_key_usage(value: &[u8]) -> Vec<String> {
+    let mut data = match tlv(value) {
+        Some((0x30, data, _)) => data,
+        _ => return Vec::new(),
+    };
+    let mut usages = Vec::new();
+    while let Some((_, oid, rest)) = tlv(data) {
+        usages.push(match oid {
+            [0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x01] => SERVER_AUTH.to_string(),
+            [0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x02] => "clientAuth".to_string(),
+            [0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x03] => "codeSigning".to_string(),
+            [0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x04] => "emailProtection".to_string(),
+            [0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x08] => "timeStamping".to_string(),
+            [0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x09] => "OCSPSigning".to_string(),
+            [0x55, 0x1d, 0x25, 0x00] => "anyExtendedKeyUsage".to_string(),
+            _ => oid_text(oid),
+        });
+        data = rest;
+    }
+    usages
+}
+
+//cA field of the BasicConstraints extension
+fn is_ca(value: &[u8]) -> bool {
+    match tlv(value) {
+        Some((0x30, content, _)) => matches!(tlv(content), Some((0x01, [flag], _)) if *flag != 0),
+        _ => false,
+    }
+}
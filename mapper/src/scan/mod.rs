@@ -0,0 +1,328 @@
+//! Endpoint and security configuration scanner.
+//!
+//! [`Handle::scan_endpoints`](crate::handle::Handle::scan_endpoints) tries each endpoint given by GetEndpoints:
+//! a secure channel with the security mode and policy of the endpoint, then a session activated with each of
+//! its user token policies. The [`ScanReport`] lists the accepted and rejected configurations and the weaknesses
+//! of the server configuration (deprecated policies, passwords sent without encryption, mismatched token policies).
+//! With a trust list, the certificate of each endpoint is validated as well.
+//! It is written as text (`Display`) or JSON ([`ScanReport::to_json`]).
+
+pub mod certificate;
+#[cfg(test)]
+mod tests;
+
+use std::fmt;
+
+use crate::crypto::security_policy::SecurityPolicyUri;
+use crate::uatypes::endpoint_description::EndpointDescription;
+use crate::uatypes::security_mode::MessageSecurityMode;
+use crate::uatypes::user_identity_token::UserTokenType;
+
+/// Transport profile of the mapper: OPC UA over TCP with the binary encoding.
+pub const UA_TCP_BINARY: &str = "http://opcfoundation.org/UA-Profile/Transport/uatcp-uasc-uabinary";
+
+/// Security policies the mapper can use for a secure channel.
+const SUPPORTED_POLICIES: [&str; 4] = [
+    SecurityPolicyUri::None,
+    SecurityPolicyUri::Basic128Rsa15,
+    SecurityPolicyUri::Basic256Sha256,
+    SecurityPolicyUri::Aes128_Sha256_RsaOaep,
+];
+
+/// Security policies deprecated by the specification.
+const DEPRECATED_POLICIES: [&str; 2] = [SecurityPolicyUri::Basic128Rsa15, SecurityPolicyUri::Basic256];
+
+const KNOWN_POLICIES: [&str; 12] = [
+    SecurityPolicyUri::None,
+    SecurityPolicyUri::Aes128_Sha256_RsaOaep,
+    SecurityPolicyUri::Basic256Sha256,
+    SecurityPolicyUri::Aes256_Sha256_RsaPss,
+    SecurityPolicyUri::ECC_curve25519,
+    SecurityPolicyUri::ECC_nistP256,
+    SecurityPolicyUri::ECC_nistP384,
+    SecurityPolicyUri::ECC_brainpoolP256r1,
+    SecurityPolicyUri::ECC_brainpoolP384r1,
+    SecurityPolicyUri::ECC_curve448,
+    SecurityPolicyUri::Basic128Rsa15,
+    SecurityPolicyUri::Basic256,
+];
+
+/// Security policy uri of the mapper for a policy uri of an endpoint, None if the mapper does not support it.
+pub(crate) fn supported_policy(uri: &str) -> Option<&'static str> {
+    SUPPORTED_POLICIES.iter().find(|p| **p == uri).copied()
+}
+
+//short name of a policy uri: Basic256Sha256
+fn policy_name(uri: &str) -> &str {
+    match uri.rsplit_once('#') {
+        Some((_, name)) => name,
+        None if uri.is_empty() => "-",
+        None => uri,
+    }
+}
+
+fn security_mode_name(mode: u32) -> &'static str {
+    match mode {
+        MessageSecurityMode::NONE => "None",
+        MessageSecurityMode::SIGN => "Sign",
+        MessageSecurityMode::SIGN_AND_ENCRYPT => "SignAndEncrypt",
+        _ => "Invalid",
+    }
+}
+
+fn token_type_name(token_type: u32) -> &'static str {
+    match token_type {
+        UserTokenType::ANONYMOUS => "Anonymous",
+        UserTokenType::USERNAME => "UserName",
+        UserTokenType::CERTIFICATE => "Certificate",
+        UserTokenType::ISSUEDTOKEN => "IssuedToken",
+        _ => "Unknown",
+    }
+}
+
+/// Result of a configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Accepted,
+    ///rejected with the status of the response, or the output when there is no status (`Eof`)
+    Rejected(String),
+    ///not tried, with the reason
+    NotTried(String),
+}
+
+impl Outcome {
+    fn name(&self) -> &'static str {
+        match self {
+            Outcome::Accepted => "accepted",
+            Outcome::Rejected(_) => "rejected",
+            Outcome::NotTried(_) => "not tried",
+        }
+    }
+
+    fn detail(&self) -> Option<&str> {
+        match self {
+            Outcome::Accepted => None,
+            Outcome::Rejected(detail) | Outcome::NotTried(detail) => Some(detail),
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.detail() {
+            Some(detail) => write!(f, "{} ({})", self.name(), detail),
+            None => write!(f, "{}", self.name()),
+        }
+    }
+}
+
+/// A user token policy of an endpoint and the result of the session activated with it.
+#[derive(Debug, Clone)]
+pub struct TokenScan {
+    pub(crate) policy_id: String,
+    pub(crate) token_type: u32,
+    ///security policy of the token as advertised, empty when the token uses the policy of the endpoint
+    pub(crate) security_policy_uri: String,
+    pub(crate) outcome: Outcome,
+}
+
+/// An endpoint, the result of a secure channel with its security and the results of its user token policies.
+#[derive(Debug, Clone)]
+pub struct EndpointScan {
+    pub(crate) endpoint_url: String,
+    pub(crate) security_mode: u32,
+    pub(crate) security_policy_uri: String,
+    pub(crate) transport_profile_uri: String,
+    pub(crate) security_level: u8,
+    pub(crate) channel: Outcome,
+    ///validation of the server certificate by the trust list, None without trust list
+    pub(crate) certificate: Option<Outcome>,
+    pub(crate) tokens: Vec<TokenScan>,
+}
+
+impl EndpointScan {
+    pub(crate) fn new(endpoint: &EndpointDescription) -> EndpointScan {
+        let text = |s: &crate::uatypes::string::UaString| s.value().clone().unwrap_or_default();
+        EndpointScan {
+            endpoint_url: text(&endpoint.endpoint_url),
+            security_mode: endpoint.security_mode,
+            security_policy_uri: text(&endpoint.security_policy_uri),
+            transport_profile_uri: text(&endpoint.transport_profile_uri),
+            security_level: endpoint.security_level,
+            channel: Outcome::NotTried("not scanned".to_string()),
+            certificate: None,
+            tokens: endpoint
+                .user_identity_tokens
+                .iter()
+                .map(|t| TokenScan {
+                    policy_id: text(&t.policy_id),
+                    token_type: t.token_type,
+                    security_policy_uri: text(&t.security_policy_uri),
+                    outcome: Outcome::NotTried("no secure channel".to_string()),
+                })
+                .collect(),
+        }
+    }
+
+    ///reason why the mapper cannot open a secure channel with this endpoint, None if it can
+    pub(crate) fn unsupported(&self) -> Option<String> {
+        if !self.transport_profile_uri.is_empty() && self.transport_profile_uri != UA_TCP_BINARY {
+            return Some(format!("transport profile {} not supported", self.transport_profile_uri));
+        }
+        if supported_policy(&self.security_policy_uri).is_none() {
+            return Some(format!("security policy {} not supported", policy_name(&self.security_policy_uri)));
+        }
+        if !(MessageSecurityMode::NONE..=MessageSecurityMode::SIGN_AND_ENCRYPT).contains(&self.security_mode) {
+            return Some(format!("invalid security mode {}", self.security_mode));
+        }
+        None
+    }
+
+    fn name(&self) -> String {
+        format!("{} {} {}", self.endpoint_url, security_mode_name(self.security_mode), policy_name(&self.security_policy_uri))
+    }
+
+    fn weaknesses(&self) -> Vec<String> {
+        let mut weaknesses = Vec::new();
+        let name = self.name();
+        if DEPRECATED_POLICIES.contains(&self.security_policy_uri.as_str()) {
+            weaknesses.push(format!("{}: deprecated security policy", name));
+        }
+        if let Some(Outcome::Rejected(reason)) = &self.certificate {
+            weaknesses.push(format!("{}: certificate rejected by the trust list: {}", name, reason));
+        }
+        let policy_none = self.security_policy_uri == SecurityPolicyUri::None;
+        match self.security_mode {
+            MessageSecurityMode::NONE if !policy_none => weaknesses.push(format!("{}: security mode None with a security policy", name)),
+            MessageSecurityMode::SIGN | MessageSecurityMode::SIGN_AND_ENCRYPT if policy_none => {
+                weaknesses.push(format!("{}: security mode {} without security policy", name, security_mode_name(self.security_mode)))
+            }
+            _ => {}
+        }
+        for (i, token) in self.tokens.iter().enumerate() {
+            let token_name = format!("{}: user token policy {} ({})", name, token.policy_id, token_type_name(token.token_type));
+            let policy = if token.security_policy_uri.is_empty() { &self.security_policy_uri } else { &token.security_policy_uri };
+            if !token.security_policy_uri.is_empty() && !KNOWN_POLICIES.contains(&token.security_policy_uri.as_str()) {
+                weaknesses.push(format!("{} has an unknown security policy {}", token_name, token.security_policy_uri));
+            } else if token.token_type == UserTokenType::USERNAME && policy == SecurityPolicyUri::None {
+                weaknesses.push(format!("{} sends the password without encryption", token_name));
+            } else if token.token_type != UserTokenType::ANONYMOUS && DEPRECATED_POLICIES.contains(&policy.as_str()) {
+                weaknesses.push(format!("{} uses the deprecated security policy {}", token_name, policy_name(policy)));
+            }
+            if self.tokens[..i].iter().any(|t| t.policy_id == token.policy_id) {
+                weaknesses.push(format!("{} has the policy id of another user token policy", token_name));
+            }
+            match &token.outcome {
+                Outcome::Accepted if token.token_type == UserTokenType::ANONYMOUS && self.security_mode == MessageSecurityMode::NONE => {
+                    weaknesses.push(format!("{} gives an anonymous session without security", token_name))
+                }
+                Outcome::Rejected(status) if token.token_type == UserTokenType::ANONYMOUS || status.starts_with("BadIdentityTokenInvalid") || status.starts_with("BadSecurityPolicyRejected") => {
+                    weaknesses.push(format!("{} is advertised but rejected: {}", token_name, status))
+                }
+                _ => {}
+            }
+        }
+        weaknesses
+    }
+}
+
+/// Endpoints of a server with the result of each configuration.
+#[derive(Debug, Clone)]
+pub struct ScanReport {
+    pub(crate) target: String,
+    pub(crate) endpoints: Vec<EndpointScan>,
+}
+
+impl ScanReport {
+    ///weak or inconsistent configurations of the server
+    pub fn weaknesses(&self) -> Vec<String> {
+        self.endpoints.iter().flat_map(EndpointScan::weaknesses).collect()
+    }
+
+    pub fn to_json(&self) -> String {
+        let outcome = |o: &Outcome| match o.detail() {
+            Some(detail) => format!("\"outcome\":{},\"detail\":{}", json_string(o.name()), json_string(detail)),
+            None => format!("\"outcome\":{}", json_string(o.name())),
+        };
+        let endpoints: Vec<String> = self
+            .endpoints
+            .iter()
+            .map(|e| {
+                let tokens: Vec<String> = e
+                    .tokens
+                    .iter()
+                    .map(|t| {
+                        format!(
+                            "{{\"policy_id\":{},\"token_type\":{},\"security_policy_uri\":{},{}}}",
+                            json_string(&t.policy_id),
+                            json_string(token_type_name(t.token_type)),
+                            json_string(&t.security_policy_uri),
+                            outcome(&t.outcome)
+                        )
+                    })
+                    .collect();
+                let certificate = e.certificate.as_ref().map_or(String::new(), |c| format!(",\"certificate\":{{{}}}", outcome(c)));
+                format!(
+                    "{{\"endpoint_url\":{},\"security_mode\":{},\"security_policy_uri\":{},\"transport_profile_uri\":{},\"security_level\":{},\"channel\":{{{}}}{},\"user_tokens\":[{}]}}",
+                    json_string(&e.endpoint_url),
+                    json_string(security_mode_name(e.security_mode)),
+                    json_string(&e.security_policy_uri),
+                    json_string(&e.transport_profile_uri),
+                    e.security_level,
+                    outcome(&e.channel),
+                    certificate,
+                    tokens.join(",")
+                )
+            })
+            .collect();
+        let weaknesses: Vec<String> = self.weaknesses().iter().map(|w| json_string(w)).collect();
+        format!(
+            "{{\"target\":{},\"endpoints\":[{}],\"weaknesses\":[{}]}}",
+            json_string(&self.target),
+            endpoints.join(","),
+            weaknesses.join(",")
+        )
+    }
+}
+
+impl fmt::Display for ScanReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "target: {}", self.target)?;
+        for e in &self.endpoints {
+            writeln!(f, "{} (security level {}): {}", e.name(), e.security_level, e.channel)?;
+            if let Some(certificate) = &e.certificate {
+                writeln!(f, "    certificate: {}", certificate)?;
+            }
+            for t in &e.tokens {
+                let policy = if t.security_policy_uri.is_empty() { String::new() } else { format!(" {}", policy_name(&t.security_policy_uri)) };
+                writeln!(f, "    {} {}{}: {}", token_type_name(t.token_type), t.policy_id, policy, t.outcome)?;
+            }
+        }
+        let weaknesses = self.weaknesses();
+        if !weaknesses.is_empty() {
+            writeln!(f, "weaknesses:")?;
+            for w in weaknesses {
+                writeln!(f, "    {}", w)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use crate::scan::certificate::{CertificateReport, Validity};
+    use openssl::asn1::Asn1Time;
+    use openssl::bn::BigNum;
+    use openssl::hash::MessageDigest;
+    use openssl::pkey::{PKey, Private};
+    use openssl::rsa::Rsa;
+    use openssl::x509::extension::{BasicConstraints, KeyUsage};
+    use openssl::x509::{X509Name, X509};
+
+    const CTT: &[u8] = include_bytes!("../../crypto/tests/cert_server_ctt.der");
+    const UAEXPERT: &[u8] = include_bytes!("../../crypto/tests/uaexpert.der");
+
+    fn certificate(common_name: &str, key: &PKey<Private>, issuer: Option<(&X509, &PKey<Private>)>, bits: u32) -> X509 {
+        let mut name = X509Name::builder().unwrap();
+        name.append_entry_by_text("CN", common_name).unwrap();
+        let name = name.build();
+        let mut builder = X509::builder().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_serial_number(&BigNum::from_u32(bits).unwrap().to_asn1_integer().unwrap()).unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_pubkey(key).unwrap();
+        builder.set_not_before(&Asn1Time::days_from_now(0).unwrap()).unwrap();
+        builder.set_not_after(&Asn1Time::days_from_now(30).unwrap()).unwrap();
+        match issuer {
+            Some((ca, ca_key)) => {
+                builder.set_issuer_name(ca.subject_name()).unwrap();
+                builder.sign(ca_key, MessageDigest::sha256()).unwrap();
+            }
+            None => {
+                builder.append_extension(BasicConstraints::new().critical().ca().build().unwrap()).unwrap();
+                builder.append_extension(KeyUsage::new().key_cert_sign().build().unwrap()).unwrap();
+                builder.set_issuer_name(&name).unwrap();
+                builder.sign(key, MessageDigest::sha256()).unwrap();
+            }
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn self_signed() {
+        let report = CertificateReport::analyze(CTT, Some("urn:open62541.server.application")).unwrap();
+        assert_eq!(report.key_type, "RSA");
+        assert_eq!(report.key_bits, 2048);
+        assert_eq!(report.signature_algorithm, "sha256WithRSAEncryption");
+        assert_eq!(report.validity, Validity::Expired);
+        assert_eq!(report.application_uris, vec!["urn:open62541.server.application".to_string()]);
+        assert_eq!(report.dns_names, vec!["localhost".to_string()]);
+        assert_eq!(
+            report.key_usage,
+            Some(vec!["digitalSignature", "nonRepudiation", "keyEncipherment", "dataEncipherment", "keyAgreement", "keyCertSign"])
+        );
+        assert_eq!(report.extended_key_usage, Some(vec!["serverAuth".to_string(), "clientAuth".to_string()]));
+        assert!(report.ca);
+        assert!(report.self_signed);
+        assert_eq!(report.subject, report.issuer);
+        assert!(report.chain.is_empty());
+        assert_eq!(report.missing_issuer, None);
+        let findings = report.findings();
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].starts_with("expired"));
+    }
+
+    #[test]
+    fn application_uri() {
+        let report = CertificateReport::analyze(UAEXPERT, Some("urn:server")).unwrap();
+        assert_eq!(report.application_uris, vec!["urn:arthur-computer:UnifiedAutomation:UaExpert".to_string()]);
+        assert!(report.findings().iter().any(|f| f.contains("differs from the application uri urn:server")));
+        let report = CertificateReport::analyze(UAEXPERT, Some("urn:arthur-computer:UnifiedAutomation:UaExpert")).unwrap();
+        assert!(!report.findings().iter().any(|f| f.contains("application uri")));
+    }
+
+    #[test]
+    fn chain() {
+        let ca_key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let ca = certificate("ca", &ca_key, None, 2048);
+        let key = PKey::from_rsa(Rsa::generate(1024).unwrap()).unwrap();
+        let leaf = certificate("server", &key, Some((&ca, &ca_key)), 1024);
+        let report = CertificateReport::analyze(&leaf.to_der().unwrap(), None).unwrap();
+        assert!(!report.self_signed);
+        assert!(!report.ca);
+        assert_eq!(report.validity, Validity::Valid);
+        assert_eq!(report.key_usage, None);
+        assert_eq!(report.missing_issuer, Some("CN=ca".to_string()));
+        let findings = report.findings();
+        for expected in ["RSA key of 1024 bits", "no application uri", "no KeyUsage", "no ExtendedKeyUsage", "incomplete chain"] {
+            assert!(findings.iter().any(|f| f.starts_with(expected)), "{} not in {:?}", expected, findings);
+        }
+        let mut chain = leaf.to_der().unwrap();
+        chain.extend(ca.to_der().unwrap());
+        let report = CertificateReport::analyze(&chain, None).unwrap();
+        assert_eq!(report.subject, "CN=server");
+        assert_eq!(report.chain, vec!["CN=ca".to_string()]);
+        assert_eq!(report.missing_issuer, None);
+        let report = CertificateReport::analyze(&ca.to_der().unwrap(), None).unwrap();
+        assert!(report.ca && report.self_signed);
+        assert_eq!(report.key_usage, Some(vec!["keyCertSign"]));
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(CertificateReport::analyze(&[], None).is_err());
+        assert!(CertificateReport::analyze(&CTT[..100], None).is_err());
+        let json = CertificateReport::analyze(CTT, None).unwrap().to_json();
+        assert!(json.starts_with("{\"subject\":") && json.contains("\"self_signed\":true"));
+    }
+}
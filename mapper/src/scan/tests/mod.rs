@@ -0,0 +1,2 @@
+mod certificate_test;
+mod scan_test;
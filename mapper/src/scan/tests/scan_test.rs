@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod tests {
+    use crate::crypto::security_policy::SecurityPolicyUri;
+    use crate::scan::{EndpointScan, Outcome, ScanReport, UA_TCP_BINARY};
+    use crate::uatypes::prelude::*;
+    use crate::uatypes::user_identity_token::{UserTokenPolicy, UserTokenType};
+
+    fn token(policy_id: &str, token_type: u32, security_policy_uri: &str) -> UserTokenPolicy {
+        UserTokenPolicy {
+            policy_id: UaString::from(policy_id),
+            token_type,
+            issued_token_type: UaString::new(),
+            issuer_endpoint_url: UaString::new(),
+            security_policy_uri: if security_policy_uri.is_empty() { UaString::new() } else { UaString::from(security_policy_uri) },
+        }
+    }
+
+    fn endpoint(security_mode: u32, security_policy_uri: &str, tokens: Vec<UserTokenPolicy>) -> EndpointDescription {
+        EndpointDescription {
+            endpoint_url: UaString::from("opc.tcp://localhost:4840"),
+            server: ApplicationDescription {
+                application_uri: UaString::from("urn:server"),
+                product_uri: UaString::new(),
+                application_name: LocalizedText::new(UaString::from("en"), UaString::from("server")),
+                application_type: 0,
+                gateway_server_uri: UaString::new(),
+                discovery_policy_uri: UaString::new(),
+                discovery_urls: UaArray::new(),
+            },
+            server_certificate: ByteString::new(),
+            security_mode,
+            security_policy_uri: UaString::from(security_policy_uri),
+            user_identity_tokens: UaArray::from(tokens),
+            transport_profile_uri: UaString::from(UA_TCP_BINARY),
+            security_level: 0,
+        }
+    }
+
+    #[test]
+    fn unsupported_endpoints() {
+        assert_eq!(EndpointScan::new(&endpoint(3, SecurityPolicyUri::Basic256Sha256, vec![])).unsupported(), None);
+        assert_eq!(
+            EndpointScan::new(&endpoint(3, SecurityPolicyUri::Aes256_Sha256_RsaPss, vec![])).unsupported(),
+            Some("security policy Aes256_Sha256_RsaPss not supported".to_string())
+        );
+        assert!(EndpointScan::new(&endpoint(0, SecurityPolicyUri::None, vec![])).unsupported().is_some());
+        let mut https = endpoint(1, SecurityPolicyUri::None, vec![]);
+        https.transport_profile_uri = UaString::from("http://opcfoundation.org/UA-Profile/Transport/https-uabinary");
+        assert!(EndpointScan::new(&https).unsupported().is_some());
+    }
+
+    #[test]
+    fn weaknesses() {
+        let mut none = EndpointScan::new(&endpoint(
+            1,
+            SecurityPolicyUri::None,
+            vec![token("anon", UserTokenType::ANONYMOUS, ""), token("user", UserTokenType::USERNAME, ""), token("user_enc", UserTokenType::USERNAME, SecurityPolicyUri::Basic256Sha256)],
+        ));
+        none.channel = Outcome::Accepted;
+        none.tokens[0].outcome = Outcome::Accepted;
+        none.tokens[1].outcome = Outcome::Rejected("BadUserAccessDenied".to_string());
+        none.tokens[2].outcome = Outcome::Accepted;
+        let mut rsa15 = EndpointScan::new(&endpoint(
+            3,
+            SecurityPolicyUri::Basic128Rsa15,
+            vec![token("anon", UserTokenType::ANONYMOUS, ""), token("anon", UserTokenType::CERTIFICATE, ""), token("x", UserTokenType::USERNAME, "urn:unknown")],
+        ));
+        rsa15.channel = Outcome::Accepted;
+        rsa15.tokens[0].outcome = Outcome::Rejected("BadIdentityTokenRejected".to_string());
+        let report = ScanReport { target: "127.0.0.1:4840".to_string(), endpoints: vec![none, rsa15] };
+        assert_eq!(
+            report.weaknesses(),
+            vec![
+                "opc.tcp://localhost:4840 None None: user token policy anon (Anonymous) gives an anonymous session without security",
+                "opc.tcp://localhost:4840 None None: user token policy user (UserName) sends the password without encryption",
+                "opc.tcp://localhost:4840 SignAndEncrypt Basic128Rsa15: deprecated security policy",
+                "opc.tcp://localhost:4840 SignAndEncrypt Basic128Rsa15: user token policy anon (Anonymous) is advertised but rejected: BadIdentityTokenRejected",
+                "opc.tcp://localhost:4840 SignAndEncrypt Basic128Rsa15: user token policy anon (Certificate) uses the deprecated security policy Basic128Rsa15",
+                "opc.tcp://localhost:4840 SignAndEncrypt Basic128Rsa15: user token policy anon (Certificate) has the policy id of another user token policy",
+                "opc.tcp://localhost:4840 SignAndEncrypt Basic128Rsa15: user token policy x (UserName) has an unknown security policy urn:unknown",
+            ]
+        );
+        let mismatch = EndpointScan::new(&endpoint(2, SecurityPolicyUri::None, vec![]));
+        assert_eq!(
+            ScanReport { target: String::new(), endpoints: vec![mismatch] }.weaknesses(),
+            vec!["opc.tcp://localhost:4840 Sign None: security mode Sign without security policy"]
+        );
+    }
+
+    #[test]
+    fn report_formats() {
+        let mut scan = EndpointScan::new(&endpoint(1, SecurityPolicyUri::None, vec![token("user \"1\"", UserTokenType::USERNAME, SecurityPolicyUri::Basic256Sha256)]));
+        scan.channel = Outcome::Accepted;
+        scan.tokens[0].outcome = Outcome::Rejected("BadUserAccessDenied".to_string());
+        let report = ScanReport { target: "127.0.0.1:4840".to_string(), endpoints: vec![scan] };
+        assert_eq!(
+            report.to_json(),
+            concat!(
+                "{\"target\":\"127.0.0.1:4840\",\"endpoints\":[{\"endpoint_url\":\"opc.tcp://localhost:4840\",\"security_mode\":\"None\",",
+                "\"security_policy_uri\":\"http://opcfoundation.org/UA/SecurityPolicy#None\",",
+                "\"transport_profile_uri\":\"http://opcfoundation.org/UA-Profile/Transport/uatcp-uasc-uabinary\",\"security_level\":0,",
+                "\"channel\":{\"outcome\":\"accepted\"},\"user_tokens\":[{\"policy_id\":\"user \\\"1\\\"\",\"token_type\":\"UserName\",",
+                "\"security_policy_uri\":\"http://opcfoundation.org/UA/SecurityPolicy#Basic256Sha256\",\"outcome\":\"rejected\",\"detail\":\"BadUserAccessDenied\"}]}],",
+                "\"weaknesses\":[]}"
+            )
+        );
+        assert_eq!(
+            report.to_string(),
+            "target: 127.0.0.1:4840\nopc.tcp://localhost:4840 None None (security level 0): accepted\n    UserName user \"1\" Basic256Sha256: rejected (BadUserAccessDenied)\n"
+        );
+    }
+
+    #[test]
+    fn certificate_trust() {
+        let mut scan = EndpointScan::new(&endpoint(3, SecurityPolicyUri::Basic256Sha256, vec![]));
+        scan.channel = Outcome::Accepted;
+        scan.certificate = Some(Outcome::Rejected("BadCertificateUntrusted: no certificate of the chain of CN=server is trusted".to_string()));
+        let report = ScanReport { target: "127.0.0.1:4840".to_string(), endpoints: vec![scan] };
+        assert_eq!(
+            report.weaknesses(),
+            vec!["opc.tcp://localhost:4840 SignAndEncrypt Basic256Sha256: certificate rejected by the trust list: BadCertificateUntrusted: no certificate of the chain of CN=server is trusted"]
+        );
+        assert!(report.to_json().contains("\"channel\":{\"outcome\":\"accepted\"},\"certificate\":{\"outcome\":\"rejected\",\"detail\":\"BadCertificateUntrusted: "));
+        assert!(report.to_string().contains("\n    certificate: rejected (BadCertificateUntrusted: "));
+    }
+}
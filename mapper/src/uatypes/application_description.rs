@@ -1,10 +1,10 @@
-use derive_macro::{Deserialize, Serialize};
+use derive_macro::{Deserialize, Serialize, Mutate};
 
-use crate::{Deserialize, Serialize, MapperResult};
+use crate::{Deserialize, Serialize, MapperResult, fuzz::Mutate};
 
-use super::{localized_text::LocalizedText, string::UaString};
+use super::{array::UaArray, localized_text::LocalizedText, string::UaString};
 
-#[derive(Debug, PartialEq, Deserialize, Serialize,Clone)]
+#[derive(Debug, PartialEq, Deserialize, Serialize,Clone, Mutate)]
 pub (crate) struct ApplicationDescription {
     pub(crate) application_uri: UaString,
     pub(crate) product_uri: UaString,
@@ -12,7 +12,7 @@ pub (crate) struct ApplicationDescription {
     pub(crate) application_type: u32,
     pub(crate) gateway_server_uri: UaString,
     pub(crate) discovery_policy_uri: UaString,
-    pub(crate) discovery_urls: Vec::<UaString>,
+    pub(crate) discovery_urls: UaArray<UaString>,
 }
 
 pub struct ApplicationType;
@@ -1,18 +1,105 @@
-use crate::Serialize;
+use std::ops::Deref;
 
-#[derive(Debug)]
-pub struct UaArray<T: Serialize> {
-    dimensions: Vec<i32>,
-    values: Vec<T>,
-}
+use crate::result_prelude::*;
+use crate::{BufMut, Deserialize, Serialize};
+use super::decode_limits::DecodeLimits;
+
+//Array need to be able to deal with null (length -1) which is not the same as an empty array.
+//`Vec<T>` decodes both as an empty vector, UaArray keeps the difference so that a decoded
+//message is encoded again byte for byte.
 //we restrict the code to work with array of one dimension
+#[derive(Debug, Clone, PartialEq)]
+pub struct UaArray<T> {
+    pub(crate) values: Option<Vec<T>>,
+}
+
+impl<T> UaArray<T> {
+    ///null array
+    pub fn new() -> UaArray<T> {
+        UaArray { values: None }
+    }
+    pub fn empty() -> UaArray<T> {
+        UaArray { values: Some(Vec::new()) }
+    }
+    pub fn from(values: Vec<T>) -> UaArray<T> {
+        UaArray { values: Some(values) }
+    }
+    pub fn values(&self) -> &Option<Vec<T>> {
+        &self.values
+    }
+    pub fn isnull(&self) -> bool {
+        self.values.is_none()
+    }
+}
+
+impl<T> Default for UaArray<T> {
+    fn default() -> Self {
+        UaArray::new()
+    }
+}
+
+impl<T> From<Vec<T>> for UaArray<T> {
+    fn from(values: Vec<T>) -> Self {
+        UaArray::from(values)
+    }
+}
+
+//a null array is read as an empty slice
+impl<T> Deref for UaArray<T> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        self.values.as_deref().unwrap_or(&[])
+    }
+}
+
+impl<T> IntoIterator for UaArray<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.unwrap_or_default().into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a UaArray<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl<T> Serialize for UaArray<T>
 where
     T: Serialize,
 {
-    fn serialize(&self) -> Vec<u8> {
-        let mut result = self.dimensions.serialize();
-        result.extend_from_slice(&self.values.serialize());
-        result
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        match &self.values {
+            Some(values) => values.serialize_into(buf),
+            None => (-1_i32).serialize_into(buf),
+        }
+    }
+}
+
+impl<T> Deserialize for UaArray<T>
+where
+    T: Deserialize,
+{
+    fn deserialize(data: &[u8]) -> MapperResult<(&[u8], Self)> {
+        let (mut data, len) = i32::deserialize(data)?;
+        if len == -1 {
+            return Ok((data, UaArray::new()));
+        }
+        if len < 0 {
+            return Err(MapperError::new(MapperErrorKind::ParsingError, "negative array length"));
+        }
+        DecodeLimits::check_array_length(len as usize)?;
+        //each element is encoded with at least one byte, the remaining data bounds the allocation
+        let mut values = Vec::with_capacity((len as usize).min(data.len()));
+        let mut el: T;
+        for i in 0..len as usize {
+            (data, el) = T::deserialize(data).map_err(|e| e.in_element(i, data.len()))?;
+            values.push(el);
+        }
+        Ok((data, UaArray::from(values)))
     }
 }
@@ -27,4 +27,19 @@ impl AttributeId{
 	pub(crate) const USERROLEPERMISSIONS:u32 =25;
 	pub(crate) const ACCESSRESTRICTIONS:u32 =26;
 	pub(crate) const ACCESSLEVELEX:u32 =27;
-}
\ No newline at end of file
+
+	//names of the specification, in order of the identifiers
+	const NAMES:[&'static str;27]=["NodeId","NodeClass","BrowseName","DisplayName","Description","WriteMask","UserWriteMask","IsAbstract",
+		"Symmetric","InverseName","ContainsNoLoops","EventNotifier","Value","DataType","ValueRank","ArrayDimensions","AccessLevel",
+		"UserAccessLevel","MinimumSamplingInterval","Historizing","Executable","UserExecutable","DataTypeDefinition","RolePermissions",
+		"UserRolePermissions","AccessRestrictions","AccessLevelEx"];
+
+	///identifier of an attribute from its name (`UserAccessLevel`) or its number
+	pub(crate) fn from_name(name:&str)->Option<u32>{
+		let name=name.trim();
+		match AttributeId::NAMES.iter().position(|n| *n==name){
+			Some(i)=>Some(i as u32+1),
+			None=>name.parse::<u32>().ok(),
+		}
+	}
+}
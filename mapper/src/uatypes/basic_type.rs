@@ -3,6 +3,8 @@
 // Standard UA types onto Rust types:
 
 use crate::{Deserialize, MapperResult};
+use crate::result_prelude::*;
+use super::decode_limits::DecodeLimits;
 
 // Boolean  -> bool
 // SByte    -> i8
@@ -15,11 +17,11 @@ use crate::{Deserialize, MapperResult};
 // UInt64   -> u64
 // Float    -> f32
 // Double   -> f64
-use super::{super::Serialize, ToVariant, prelude::Variant};
+use super::{super::{BufMut, Serialize}, ToVariant, prelude::Variant};
 
 impl Serialize for u8 {
-    fn serialize(&self) -> Vec<u8> {
-        self.to_le_bytes().to_vec()
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        buf.put_slice(&self.to_le_bytes());
     }
 }
 
@@ -33,15 +35,13 @@ impl Deserialize for u8 {
 
 impl ToVariant for u8{
     fn to_variant(&self)->super::prelude::Variant {
-        Variant{value:vec![super::prelude::Value::Byte(self.clone())],
-                array_dimension:vec![],
-            }
+        Variant::scalar(super::prelude::Value::Byte(*self))
     }
 }
 
 impl Serialize for u16 {
-    fn serialize(&self) -> Vec<u8> {
-        self.to_le_bytes().to_vec()
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        buf.put_slice(&self.to_le_bytes());
     }
 }
 
@@ -56,15 +56,13 @@ impl Deserialize for u16 {
 
 impl ToVariant for u16{
     fn to_variant(&self)->super::prelude::Variant {
-        Variant{value:vec![super::prelude::Value::Uint16(self.clone())],
-                array_dimension:vec![],
-            }
+        Variant::scalar(super::prelude::Value::Uint16(*self))
     }
 }
 
 impl Serialize for u32 {
-    fn serialize(&self) -> Vec<u8> {
-        self.to_le_bytes().to_vec()
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        buf.put_slice(&self.to_le_bytes());
     }
 }
 
@@ -78,15 +76,13 @@ impl Deserialize for u32 {
 
 impl ToVariant for u32{
     fn to_variant(&self)->super::prelude::Variant {
-        Variant{value:vec![super::prelude::Value::Uint32(self.clone())],
-                array_dimension:vec![],
-            }
+        Variant::scalar(super::prelude::Value::Uint32(*self))
     }
 }
 
 impl Serialize for u64 {
-    fn serialize(&self) -> Vec<u8> {
-        self.to_le_bytes().to_vec()
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        buf.put_slice(&self.to_le_bytes());
     }
 }
 
@@ -100,15 +96,13 @@ impl Deserialize for u64 {
 
 impl ToVariant for u64{
     fn to_variant(&self)->super::prelude::Variant {
-        Variant{value:vec![super::prelude::Value::Uint64(self.clone())],
-                array_dimension:vec![],
-            }
+        Variant::scalar(super::prelude::Value::Uint64(*self))
     }
 }
 
 impl Serialize for i8 {
-    fn serialize(&self) -> Vec<u8> {
-        self.to_le_bytes().to_vec()
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        buf.put_slice(&self.to_le_bytes());
     }
 }
 
@@ -123,15 +117,13 @@ impl Deserialize for i8 {
 
 impl ToVariant for i8{
     fn to_variant(&self)->super::prelude::Variant {
-        Variant{value:vec![super::prelude::Value::Sbyte(self.clone())],
-                array_dimension:vec![],
-            }
+        Variant::scalar(super::prelude::Value::Sbyte(*self))
     }
 }
 
 impl Serialize for i16 {
-    fn serialize(&self) -> Vec<u8> {
-        self.to_le_bytes().to_vec()
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        buf.put_slice(&self.to_le_bytes());
     }
 }
 
@@ -145,15 +137,13 @@ impl Deserialize for i16 {
 
 impl ToVariant for i16{
     fn to_variant(&self)->super::prelude::Variant {
-        Variant{value:vec![super::prelude::Value::Int16(self.clone())],
-                array_dimension:vec![],
-            }
+        Variant::scalar(super::prelude::Value::Int16(*self))
     }
 }
 
 impl Serialize for i32 {
-    fn serialize(&self) -> Vec<u8> {
-        self.to_le_bytes().to_vec()
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        buf.put_slice(&self.to_le_bytes());
     }
 }
 
@@ -167,16 +157,14 @@ impl Deserialize for i32 {
 
 impl ToVariant for i32{
     fn to_variant(&self)->super::prelude::Variant {
-        Variant{value:vec![super::prelude::Value::Int32(self.clone())],
-                array_dimension:vec![],
-            }
+        Variant::scalar(super::prelude::Value::Int32(*self))
     }
 }
 
 
 impl Serialize for i64 {
-    fn serialize(&self) -> Vec<u8> {
-        self.to_le_bytes().to_vec()
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        buf.put_slice(&self.to_le_bytes());
     }
 }
 
@@ -190,18 +178,16 @@ impl Deserialize for i64 {
 
 impl ToVariant for i64{
     fn to_variant(&self)->super::prelude::Variant {
-        Variant{value:vec![super::prelude::Value::Int64(self.clone())],
-                array_dimension:vec![],
-            }
+        Variant::scalar(super::prelude::Value::Int64(*self))
     }
 }
 
 impl Serialize for bool {
-    fn serialize(&self) -> Vec<u8> {
+    fn serialize_into(&self, buf: &mut impl BufMut) {
         if *self {
-            vec![1]
+            buf.put_u8(1);
         } else {
-            vec![0]
+            buf.put_u8(0);
         }
     }
 }
@@ -220,15 +206,13 @@ impl Deserialize for bool {
 
 impl ToVariant for bool{
     fn to_variant(&self)->super::prelude::Variant {
-        Variant{value:vec![super::prelude::Value::Boolean(self.clone())],
-                array_dimension:vec![],
-            }
+        Variant::scalar(super::prelude::Value::Boolean(*self))
     }
 }
 
 impl Serialize for f32 {
-    fn serialize(&self) -> Vec<u8> {
-        self.to_le_bytes().to_vec()
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        buf.put_slice(&self.to_le_bytes());
     }
 }
 
@@ -242,15 +226,13 @@ impl Deserialize for f32 {
 
 impl ToVariant for f32{
     fn to_variant(&self)->super::prelude::Variant {
-        Variant{value:vec![super::prelude::Value::Float(self.clone())],
-                array_dimension:vec![],
-            }
+        Variant::scalar(super::prelude::Value::Float(*self))
     }
 }
 
 impl Serialize for f64 {
-    fn serialize(&self) -> Vec<u8> {
-        self.to_le_bytes().to_vec()
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        buf.put_slice(&self.to_le_bytes());
     }
 }
 
@@ -264,42 +246,56 @@ impl Deserialize for f64 {
 
 impl ToVariant for f64{
     fn to_variant(&self)->super::prelude::Variant {
-        Variant{value:vec![super::prelude::Value::Double(self.clone())],
-                array_dimension:vec![],
-            }
+        Variant::scalar(super::prelude::Value::Double(*self))
     }
 }
 
 impl Serialize for String {
-    fn serialize(&self) -> Vec<u8> {
-        let mut serialization = (self.len() as u32).to_le_bytes().to_vec();
-        serialization.extend_from_slice(self.as_bytes());
-        serialization
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        buf.put_slice(&(self.len() as u32).to_le_bytes());
+        buf.put_slice(self.as_bytes());
     }
 }
 
 impl Deserialize for String {
     fn deserialize(data: &[u8]) -> MapperResult<(&[u8], Self)> {
         let (data, value) = u32::deserialize(data)?;
+        DecodeLimits::check_string_length(value as usize)?;
         let (data, value) = String::take_count(data, value as usize)?;
-        let value = String::from_utf8(value).unwrap();
+        let value = String::from_utf8(value).map_err(|_| MapperError::new(MapperErrorKind::ParsingError, "invalid utf8 string"))?;
         Ok((data, value))
     }
 }
 
 impl ToVariant for String{
     fn to_variant(&self)->super::prelude::Variant {
-        Variant{value:vec![super::prelude::Value::String(super::string::UaString::from(&self))],
-                array_dimension:vec![],
-            }
+        Variant::scalar(super::prelude::Value::String(super::string::UaString::from(self)))
     }
 }
 
 impl ToVariant for &str{
     fn to_variant(&self)->super::prelude::Variant {
-        Variant{value:vec![super::prelude::Value::String(super::string::UaString::from(&self))],
-                array_dimension:vec![],
-            }
+        Variant::scalar(super::prelude::Value::String(super::string::UaString::from(self)))
+    }
+}
+
+//recursive structures hold their nested value in a Box, encoded as the value itself
+impl<T> Serialize for Box<T>
+where
+    T: Serialize,
+{
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        (**self).serialize_into(buf);
+    }
+}
+
+impl<T> Deserialize for Box<T>
+where
+    T: Deserialize,
+{
+    fn deserialize(data: &[u8]) -> MapperResult<(&[u8], Self)> {
+        let (data, value) = T::deserialize(data)?;
+        Ok((data, Box::new(value)))
     }
 }
 
@@ -307,12 +303,11 @@ impl<T> Serialize for Vec<T>
 where
     T: Serialize,
 {
-    fn serialize(&self) -> Vec<u8> {
-        let mut result = (self.len() as i32).serialize();
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        (self.len() as i32).serialize_into(buf);
         for i in self {
-            result.extend_from_slice(&i.serialize());
+            i.serialize_into(buf);
         }
-        result
     }
 }
 
@@ -325,10 +320,12 @@ where
         if len==-1{
             return Ok((data, Vec::new()))
         }
-        let mut vector = Vec::with_capacity(len as usize);
+        DecodeLimits::check_array_length(len.max(0) as usize)?;
+        //each element is encoded with at least one byte, the remaining data bounds the allocation
+        let mut vector = Vec::with_capacity((len.max(0) as usize).min(data.len()));
         let mut el: T;
-        for _i in 0..len {
-            (data, el) = T::deserialize(data)?;
+        for i in 0..len.max(0) as usize {
+            (data, el) = T::deserialize(data).map_err(|e| e.in_element(i, data.len()))?;
             vector.push(el);
         }
         Ok((data, vector))
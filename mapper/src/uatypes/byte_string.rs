@@ -1,7 +1,8 @@
 use crate::Deserialize;
 
-use crate::Serialize;
-use crate::MapperResult;
+use crate::{BufMut, Serialize};
+use crate::result_prelude::*;
+use super::decode_limits::DecodeLimits;
 
 #[derive(PartialEq, Debug, Clone)]
 pub struct ByteString {
@@ -9,17 +10,13 @@ pub struct ByteString {
 }
 
 impl Serialize for ByteString {
-    fn serialize(&self) -> Vec<u8> {
+    fn serialize_into(&self, buf: &mut impl BufMut) {
         match &self.value {
             Some(s) => {
-                let mut serialization = (s.len() as i32).to_le_bytes().to_vec();
-                serialization.extend_from_slice(s);
-                serialization
-            }
-            _ => {
-                let serialization = (-1 as i32).serialize();
-                serialization
+                (s.len() as i32).serialize_into(buf);
+                buf.put_slice(s);
             }
+            _ => (-1_i32).serialize_into(buf),
         }
     }
 }
@@ -30,6 +27,7 @@ impl Deserialize for ByteString {
         if value < 0 {
             Ok((data, ByteString::new()))
         } else {
+            DecodeLimits::check_string_length(value as usize)?;
             let (data, value) = ByteString::take_count(data, value as usize)?;
             let value = ByteString::from(value);
             Ok((data, value))
@@ -46,6 +44,18 @@ impl ByteString {
             value: Some(Vec::from(s)),
         }
     }
+    ///ByteString from its text: hexadecimal with a `0x` prefix, base64 otherwise
+    pub fn parse(s: &str) -> MapperResult<ByteString> {
+        let s = s.trim();
+        let value = match s.strip_prefix("0x") {
+            Some(hex_value) => hex::decode(hex_value).ok(),
+            None if s.is_empty() => Some(vec![]),
+            None => openssl::base64::decode_block(s).ok(),
+        };
+        value
+            .map(ByteString::from)
+            .ok_or_else(|| MapperError::new(MapperErrorKind::UnexpectedValue, "invalid bytestring text"))
+    }
     pub fn from(vec: Vec<u8>) -> ByteString {
         ByteString { value: Some(vec) }
     }
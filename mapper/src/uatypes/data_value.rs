@@ -1,15 +1,23 @@
-use crate::{Deserialize, Serialize, MapperResult};
+use crate::encoding_prelude::*;
 
 use super::date_time::DateTime;
+use super::status_code::StatusCode;
 use super::variant::Variant;
 
-#[derive(Debug,Clone)]
+#[derive(Debug,Clone,Serialize,Deserialize)]
+#[ua(depth_guard)]
 pub struct DataValue {
+    #[ua(mask_bit = EncodingValue::HAS_VALUE)]
     pub(crate) value: Option<Variant>,
+    #[ua(mask_bit = EncodingValue::STATUS_CODE)]
     pub(crate) status: Option<u32>,
+    #[ua(mask_bit = EncodingValue::SOURCE_TIME_STAMP)]
     pub(crate) source_time_stamp: Option<DateTime>,
+    #[ua(mask_bit = EncodingValue::SOURCE_PICO_SECONDS)]
     pub(crate) source_pico_seconds: Option<u16>,
+    #[ua(mask_bit = EncodingValue::SERVER_TIME_STAMP)]
     pub(crate) server_time_stamp: Option<DateTime>,
+    #[ua(mask_bit = EncodingValue::SERVER_PICO_SECONDS)]
     pub(crate) server_pico_seconds: Option<u16>,
 }
 pub struct EncodingValue;
@@ -22,91 +30,6 @@ impl EncodingValue {
     pub const SERVER_PICO_SECONDS: u8 = 0x20;
 }
 
-impl Serialize for DataValue
-{
-    fn serialize(&self) -> Vec<u8> {
-        let mut result = vec![0x00];
-        if let Some(v)=&self.value {
-            result[0]=result[0]| EncodingValue::HAS_VALUE;
-            result.extend_from_slice(&v.serialize());
-        }
-        if let Some(v)=&self.status {
-            result[0]=result[0]| EncodingValue::STATUS_CODE;
-            result.extend_from_slice(&v.serialize());
-        }
-        if let Some(v)=&self.source_time_stamp {
-            result[0]=result[0]| EncodingValue::SOURCE_TIME_STAMP;
-            result.extend_from_slice(&v.serialize());
-        }
-        if let Some(v)=&self.source_pico_seconds {
-            result[0]=result[0]| EncodingValue::SOURCE_PICO_SECONDS;
-            result.extend_from_slice(&v.serialize());
-        }
-        if let Some(v)=&self.server_time_stamp {
-            result[0]=result[0]| EncodingValue::SERVER_TIME_STAMP;
-            result.extend_from_slice(&v.serialize());
-        }
-        if let Some(v)=&self.server_pico_seconds {
-            result[0]=result[0]| EncodingValue::SERVER_PICO_SECONDS;
-            result.extend_from_slice(&v.serialize());
-        }
-        result
-    }
-}
-
-impl Deserialize for DataValue
-{
-    fn deserialize(data: &[u8]) -> crate::MapperResult<(&[u8], Self)> where Self: Sized {
-        let mut value= None;
-        let mut status= None;
-        let mut source_time_stamp= None;
-        let mut source_pico_seconds= None;
-        let mut server_time_stamp= None;
-        let mut server_pico_seconds= None;
-        let (mut data,encoding_value)=u8::deserialize(data)?;
-        if encoding_value & EncodingValue::HAS_VALUE == EncodingValue::HAS_VALUE  {
-
-            let val: Variant;
-            (data,val)=Variant::deserialize(data)?;
-            value=Some(val);
-        }
-        if encoding_value & EncodingValue::STATUS_CODE == EncodingValue::STATUS_CODE  {
-            let val: u32;
-            (data,val)=u32::deserialize(data)?;
-            status=Some(val);
-        }
-        if encoding_value & EncodingValue::SOURCE_TIME_STAMP == EncodingValue::SOURCE_TIME_STAMP  {
-            let val: DateTime;
-            (data,val)=DateTime::deserialize(data)?;
-            source_time_stamp=Some(val);
-        }
-        if encoding_value & EncodingValue::SOURCE_PICO_SECONDS == EncodingValue::SOURCE_PICO_SECONDS  {
-            let val: u16;
-            (data,val)=u16::deserialize(data)?;
-            source_pico_seconds=Some(val);
-        }
-        if encoding_value & EncodingValue::SERVER_TIME_STAMP == EncodingValue::SERVER_TIME_STAMP  {
-            let val: DateTime;
-            (data,val)=DateTime::deserialize(data)?;
-            server_time_stamp=Some(val);
-        }
-        if encoding_value & EncodingValue::SERVER_PICO_SECONDS == EncodingValue::SERVER_PICO_SECONDS  {
-            let val: u16;
-            (data,val)=u16::deserialize(data)?;
-            server_pico_seconds=Some(val);
-        }
-        Ok((data,DataValue {
-                            value,
-                            status,
-                            source_time_stamp,
-                            source_pico_seconds,
-                            server_time_stamp,
-                            server_pico_seconds,
-        }))
-
-    }
-}
-
 impl DataValue{
     pub(crate) fn empty()->Self{
         DataValue {
@@ -128,4 +51,18 @@ impl DataValue{
             server_pico_seconds: None,
         })
     }
-}
\ No newline at end of file
+
+    ///Same value with only the optional fields in `fields` (`EncodingValue::STATUS_CODE`, `SOURCE_TIME_STAMP`, `SERVER_TIME_STAMP`).
+    ///A selected field that is not set is Good for the status code and the current time for the timestamps.
+    pub(crate) fn with_fields(&self,fields:u8)->DataValue{
+        let selected=|field:u8| fields & field == field;
+        DataValue {
+            value: self.value.clone(),
+            status: if selected(EncodingValue::STATUS_CODE) { Some(self.status.unwrap_or(StatusCode::Good)) } else { None },
+            source_time_stamp: if selected(EncodingValue::SOURCE_TIME_STAMP) { Some(self.source_time_stamp.clone().unwrap_or_else(DateTime::new_now)) } else { None },
+            source_pico_seconds: if selected(EncodingValue::SOURCE_TIME_STAMP) { self.source_pico_seconds } else { None },
+            server_time_stamp: if selected(EncodingValue::SERVER_TIME_STAMP) { Some(self.server_time_stamp.clone().unwrap_or_else(DateTime::new_now)) } else { None },
+            server_pico_seconds: if selected(EncodingValue::SERVER_TIME_STAMP) { self.server_pico_seconds } else { None },
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::Deserialize;
 use crate::Serialize;
-use crate::MapperResult;
+use crate::BufMut;
+use crate::result_prelude::*;
 use std::time::SystemTime;
 //Number of 100ns from 01/01/1601 
 //this code works for unix machine only since it applies a conversion to win32 SystemTime (different epoch)
@@ -9,6 +10,9 @@ use std::time::SystemTime;
 #[derive(Debug,Clone)]
 pub struct DateTime {
     value: i64,
+    //value read on the wire. The unix timestamp drops the sub-second part, so a decoded
+    //date is encoded again from this value to be byte identical.
+    encoded: Option<i64>,
 }
 
 //wintime=(unixtime*TO_NANOSECOND)+EPOCH_DIFFERENCE: unixtime in second
@@ -16,9 +20,13 @@ const TO_NANOSECOND: i64 = 10000000;
 const EPOCH_DIFFERENCE: i64 = 116444736000000000;
 
 impl Serialize for DateTime {
-    fn serialize(&self) -> Vec<u8> {
-        let windows_time = (self.value * TO_NANOSECOND) + EPOCH_DIFFERENCE;
-        windows_time.to_le_bytes().to_vec()
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        if let Some(encoded) = self.encoded {
+            return encoded.serialize_into(buf);
+        }
+        //out of range dates are clamped, as the specification asks for the min and max values
+        let windows_time = self.value.saturating_mul(TO_NANOSECOND).saturating_add(EPOCH_DIFFERENCE);
+        windows_time.serialize_into(buf);
     }
 }
 
@@ -29,6 +37,7 @@ impl Deserialize for DateTime {
             data,
             DateTime {
                 value: DateTime::from_win_to_unix(value),
+                encoded: Some(value),
             },
         ))
     }
@@ -36,28 +45,30 @@ impl Deserialize for DateTime {
 
 impl DateTime {
     pub fn new() -> DateTime {
-        DateTime { value: 0 }
+        DateTime { value: 0, encoded: None }
     }
     pub fn from(date: i64) -> DateTime {
-        DateTime { value: date }
+        DateTime { value: date, encoded: None }
     }
     pub fn set(&mut self, date: i64) {
         self.value = date;
+        self.encoded = None;
     }
     pub fn get(&self) -> &i64 {
         &self.value
     }
     fn from_unix_to_win(unix: i64) -> i64 {
-        (unix * TO_NANOSECOND) + EPOCH_DIFFERENCE
+        unix.saturating_mul(TO_NANOSECOND).saturating_add(EPOCH_DIFFERENCE)
     }
     pub fn from_win_to_unix(wind: i64) -> i64 {
-        (wind - EPOCH_DIFFERENCE) / TO_NANOSECOND
+        wind.saturating_sub(EPOCH_DIFFERENCE) / TO_NANOSECOND
     }
     pub fn now(&mut self) {
         self.value = SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)
             .unwrap()
             .as_secs() as i64;
+        self.encoded = None;
     }
 
     pub fn new_now() -> DateTime {
@@ -65,10 +76,89 @@ impl DateTime {
             .duration_since(SystemTime::UNIX_EPOCH)
             .unwrap()
             .as_secs() as i64;
-        DateTime { value: value }
+        DateTime { value, encoded: None }
+    }
+
+    /// Date from its ISO 8601 text in UTC or with an offset: `2024-03-01T12:30:00.5Z`, `2024-03-01T14:30:00+02:00` or `2024-03-01`.
+    /// The fractional seconds are kept up to the 100ns of the encoding.
+    pub fn parse(text: &str) -> MapperResult<DateTime> {
+        let error = || MapperError::new(MapperErrorKind::UnexpectedValue, "invalid ISO 8601 date");
+        let number = |s: &str| -> MapperResult<i64> {
+            if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(error());
+            }
+            s.parse::<i64>().map_err(|_| error())
+        };
+        let text = text.trim();
+        let (date, time) = text.split_once(['T', ' ']).unwrap_or((text, "00:00:00Z"));
+        let mut date_fields = date.splitn(3, '-');
+        let (year, month, day) = match (date_fields.next(), date_fields.next(), date_fields.next()) {
+            (Some(y), Some(m), Some(d)) => (number(y)?, number(m)?, number(d)?),
+            _ => return Err(error()),
+        };
+        //years of 4 digits, as in ISO 8601 without extension: the seconds of the date cannot overflow
+        if !(1..=9999).contains(&year) || !(1..=12).contains(&month) || !(1..=DateTime::days_in_month(year, month)).contains(&day) {
+            return Err(error());
+        }
+        let (time, offset) = if let Some(time) = time.strip_suffix('Z') {
+            (time, 0)
+        } else if let Some(position) = time.rfind(['+', '-']) {
+            let (hours, minutes) = time[position + 1..].split_once(':').ok_or_else(error)?;
+            let (hours, minutes) = (number(hours)?, number(minutes)?);
+            if hours > 23 || minutes > 59 {
+                return Err(error());
+            }
+            let offset = hours * 3600 + minutes * 60;
+            (&time[..position], if &time[position..position + 1] == "-" { -offset } else { offset })
+        } else {
+            (time, 0)
+        };
+        let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+        let mut time_fields = time.splitn(3, ':');
+        let (hours, minutes, seconds) = match (time_fields.next(), time_fields.next(), time_fields.next()) {
+            (Some(h), Some(m), Some(s)) => (number(h)?, number(m)?, number(s)?),
+            _ => return Err(error()),
+        };
+        if hours > 23 || minutes > 59 || seconds > 60 {
+            return Err(error());
+        }
+        //fraction of second in 100ns, the digits beyond are dropped
+        let mut ticks = 0;
+        if !fraction.is_empty() {
+            //only ascii digits, so that the fraction can be cut at any byte
+            number(fraction)?;
+            let digits = &fraction[..fraction.len().min(7)];
+            ticks = number(digits)? * 10_i64.pow(7 - digits.len() as u32);
+        }
+        let unix = DateTime::days_from_civil(year, month, day) * 86400 + hours * 3600 + minutes * 60 + seconds - offset;
+        Ok(DateTime {
+            value: unix,
+            encoded: Some(DateTime::from_unix_to_win(unix).saturating_add(ticks)),
+        })
+    }
+
+    //number of days of a month of the proleptic gregorian calendar
+    fn days_in_month(year: i64, month: i64) -> i64 {
+        match month {
+            2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    //days since 1970-01-01 of a date of the proleptic gregorian calendar
+    fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146097 + day_of_era - 719468
     }
 
     pub fn read(&mut self, time: i64) {
-        self.value = (time - EPOCH_DIFFERENCE) / TO_NANOSECOND;
+        self.value = time.saturating_sub(EPOCH_DIFFERENCE) / TO_NANOSECOND;
+        self.encoded = Some(time);
     }
 }
\ No newline at end of file
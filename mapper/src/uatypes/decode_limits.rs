@@ -0,0 +1,104 @@
+//! Limits applied when decoding data coming from the network.
+//! Lengths read on the wire are checked against these limits before any allocation,
+//! and the nesting of recursive types (Variant, DataValue, DiagnosticInfo) is bounded.
+//! Limits are set per thread: the [`Handle`](crate::handle::Handle) applies its own limits
+//! before decoding responses.
+
+use std::cell::Cell;
+
+use crate::result_prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecodeLimits {
+    ///maximum length in bytes of String, ByteString and XmlElement
+    pub max_string_length: usize,
+    ///maximum number of elements of an array
+    pub max_array_length: usize,
+    ///maximum nesting depth of Variant, DataValue and DiagnosticInfo
+    pub max_depth: usize,
+    ///maximum size of a message received
+    pub max_message_size: usize,
+}
+
+impl DecodeLimits {
+    pub const DEFAULT_MAX_STRING_LENGTH: usize = 16777216;
+    pub const DEFAULT_MAX_ARRAY_LENGTH: usize = 65536;
+    pub const DEFAULT_MAX_DEPTH: usize = 100;
+    pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 16777216;
+
+    pub fn new(max_string_length: usize, max_array_length: usize, max_depth: usize, max_message_size: usize) -> DecodeLimits {
+        DecodeLimits {
+            max_string_length,
+            max_array_length,
+            max_depth,
+            max_message_size,
+        }
+    }
+
+    /// Limits of the current thread.
+    pub fn current() -> DecodeLimits {
+        LIMITS.with(|l| l.get())
+    }
+
+    /// Use these limits for the following decoding in the current thread.
+    pub fn apply(&self) {
+        LIMITS.with(|l| l.set(*self));
+    }
+
+    pub(crate) fn check_string_length(length: usize) -> MapperResult<()> {
+        if length > DecodeLimits::current().max_string_length {
+            return Err(MapperError::new(MapperErrorKind::ParsingError, "string length exceeds the decoding limit"));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn check_array_length(length: usize) -> MapperResult<()> {
+        if length > DecodeLimits::current().max_array_length {
+            return Err(MapperError::new(MapperErrorKind::ParsingError, "array length exceeds the decoding limit"));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn check_message_size(size: usize) -> MapperResult<()> {
+        if size > DecodeLimits::current().max_message_size {
+            return Err(MapperError::new(MapperErrorKind::ParsingError, "message size exceeds the decoding limit"));
+        }
+        Ok(())
+    }
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        DecodeLimits {
+            max_string_length: DecodeLimits::DEFAULT_MAX_STRING_LENGTH,
+            max_array_length: DecodeLimits::DEFAULT_MAX_ARRAY_LENGTH,
+            max_depth: DecodeLimits::DEFAULT_MAX_DEPTH,
+            max_message_size: DecodeLimits::DEFAULT_MAX_MESSAGE_SIZE,
+        }
+    }
+}
+
+thread_local! {
+    static LIMITS: Cell<DecodeLimits> = Cell::new(DecodeLimits::default());
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Nesting level of a recursive type being decoded. The level is released when the guard is dropped.
+pub(crate) struct DepthGuard;
+
+impl DepthGuard {
+    pub(crate) fn enter() -> MapperResult<DepthGuard> {
+        let depth = DEPTH.with(|d| d.get()) + 1;
+        if depth > DecodeLimits::current().max_depth {
+            return Err(MapperError::new(MapperErrorKind::ParsingError, "nesting depth exceeds the decoding limit"));
+        }
+        DEPTH.with(|d| d.set(depth));
+        Ok(DepthGuard)
+    }
+}
+
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        DEPTH.with(|d| d.set(d.get() - 1));
+    }
+}
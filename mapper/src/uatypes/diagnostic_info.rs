@@ -1,15 +1,27 @@
-use super::{status_code::StatusCode, string::UaString};
-use crate::{Deserialize, Serialize, MapperResult};
+use super::status_code::StatusCode;
+use super::string::UaString;
+use crate::encoding_prelude::*;
 
-#[derive(Debug, PartialEq,Clone)]
+//fields are in the order of the encoding, which is not the order of the mask bits
+//(locale is encoded before localized_text)
+#[derive(Debug, PartialEq,Clone,Serialize,Deserialize)]
+#[ua(depth_guard)]
 pub struct DiagnosticInfo {
+    #[ua(mask)]
     pub(crate) encoding_mask: u8,
-    pub(crate) symbolic_id: i32,
-    pub(crate) namespace_uri: i32,
-    pub(crate) locale: i32,
-    pub(crate) localized_text: i32,
-    pub(crate) additional_info: UaString,
-    pub(crate) inner_status_code: u32,
+    #[ua(mask_bit = EncodingValue::SYMBOLIC_ID)]
+    pub(crate) symbolic_id: Option<i32>,
+    #[ua(mask_bit = EncodingValue::NAMESPACE)]
+    pub(crate) namespace_uri: Option<i32>,
+    #[ua(mask_bit = EncodingValue::LOCALE)]
+    pub(crate) locale: Option<i32>,
+    #[ua(mask_bit = EncodingValue::LOCALIZED_TEXT)]
+    pub(crate) localized_text: Option<i32>,
+    #[ua(mask_bit = EncodingValue::ADDITIONAL_INFO)]
+    pub(crate) additional_info: Option<UaString>,
+    #[ua(mask_bit = EncodingValue::INNER_STATUS_CODE)]
+    pub(crate) inner_status_code: Option<u32>,
+    #[ua(mask_bit = EncodingValue::INNER_DIAGNOSTIC_INFO)]
     pub(crate) inner_diagnostic_info: Option<Box<DiagnosticInfo>>,
 }
 
@@ -24,103 +36,49 @@ impl EncodingValue {
     pub const INNER_DIAGNOSTIC_INFO: u8 = 0x40;
 }
 
-impl Serialize for DiagnosticInfo {
-    fn serialize(&self) -> Vec<u8> {
-        let mut result = vec![0x00]; //temporary null encoding mask
-        if self.symbolic_id != -1 {
-            result[0] = result[0] | EncodingValue::SYMBOLIC_ID;
-            result.extend_from_slice(&self.symbolic_id.serialize());
-        }
-        if self.namespace_uri != -1 {
-            result[0] = result[0] | EncodingValue::NAMESPACE;
-            result.extend_from_slice(&self.namespace_uri.serialize());
-        }
-        if self.locale != -1 {
-            result[0] = result[0] | EncodingValue::LOCALIZED_TEXT;
-            result.extend_from_slice(&self.locale.serialize());
-        }
-        if self.localized_text != -1 {
-            result[0] = result[0] | EncodingValue::LOCALE;
-            result.extend_from_slice(&self.localized_text.serialize());
-        }
-        if !self.additional_info.isnull() {
-            result[0] = result[0] | EncodingValue::ADDITIONAL_INFO;
-            result.extend_from_slice(&self.additional_info.serialize());
-        }
-        if StatusCode::is_status_code(&self.inner_status_code) {
-            result[0] = result[0] | EncodingValue::INNER_STATUS_CODE;
-            result.extend_from_slice(&self.additional_info.serialize());
-        }
-        if let Some(inner_diagnostic_info) = &self.inner_diagnostic_info {
-            result[0] = result[0] | EncodingValue::INNER_DIAGNOSTIC_INFO;
-            result.extend_from_slice(&inner_diagnostic_info.serialize());
+impl DiagnosticInfo {
+    pub fn new() -> DiagnosticInfo {
+        DiagnosticInfo {
+            encoding_mask: 0,
+            symbolic_id: None,
+            namespace_uri: None,
+            locale: None,
+            localized_text: None,
+            additional_info: None,
+            inner_status_code: None,
+            inner_diagnostic_info: None,
         }
-        result
     }
-}
 
-impl Deserialize for DiagnosticInfo {
-    fn deserialize(data: &[u8]) -> MapperResult<(&[u8], Self)> {
-        let (mut data, encoding_mask) = u8::deserialize(data)?;
-        let mut symbolic_id: i32 = -1;
-        let mut namespace_uri: i32 = -1;
-        let mut locale: i32 = -1;
-        let mut localized_text: i32 = -1;
-        let mut additional_info = UaString::new();
-        let mut inner_status_code: u32 = 0xFFFFFFFF;
-        let mut inner_diagnostic_info: Option<Box<DiagnosticInfo>> = None;
-        if encoding_mask & EncodingValue::SYMBOLIC_ID == EncodingValue::SYMBOLIC_ID {
-            (data, symbolic_id) = i32::deserialize(data)?;
-        }
-        if encoding_mask & EncodingValue::NAMESPACE == EncodingValue::NAMESPACE {
-            (data, namespace_uri) = i32::deserialize(data)?;
+    ///Readable form of the diagnostic: `symbolic_id=BadNodeIdUnknown; additional_info=...; inner_status=BadX; inner=(...)`.
+    ///The symbolic id, namespace, locale and localized text are indexes in the string table of the response header.
+    pub fn describe(&self, string_table: &[UaString]) -> String {
+        let string = |index: i32| match string_table.get(index as usize).and_then(|s| s.value.as_deref()) {
+            Some(s) if index >= 0 => s.to_string(),
+            _ => format!("#{}", index),
+        };
+        let mut fields = Vec::new();
+        if let Some(i) = self.symbolic_id {
+            fields.push(format!("symbolic_id={}", string(i)));
         }
-        if encoding_mask & EncodingValue::LOCALE == EncodingValue::LOCALE {
-            (data, localized_text) = i32::deserialize(data)?;
+        if let Some(i) = self.namespace_uri {
+            fields.push(format!("namespace={}", string(i)));
         }
-        if encoding_mask & EncodingValue::LOCALIZED_TEXT == EncodingValue::LOCALIZED_TEXT {
-            (data, locale) = i32::deserialize(data)?;
+        if let Some(i) = self.locale {
+            fields.push(format!("locale={}", string(i)));
         }
-        if encoding_mask & EncodingValue::ADDITIONAL_INFO == EncodingValue::ADDITIONAL_INFO {
-            (data, additional_info) = UaString::deserialize(data)?;
+        if let Some(i) = self.localized_text {
+            fields.push(format!("localized_text={}", string(i)));
         }
-        if encoding_mask & EncodingValue::INNER_STATUS_CODE == EncodingValue::INNER_STATUS_CODE {
-            (data, inner_status_code) = u32::deserialize(data)?;
+        if let Some(info) = self.additional_info.as_ref().and_then(|s| s.value.as_deref()) {
+            fields.push(format!("additional_info={}", info));
         }
-        if encoding_mask & EncodingValue::INNER_DIAGNOSTIC_INFO
-            == EncodingValue::INNER_DIAGNOSTIC_INFO
-        {
-            let (data_, inner_diagnostic) = DiagnosticInfo::deserialize(data)?;
-            data = data_;
-            inner_diagnostic_info = Some(Box::new(inner_diagnostic));
+        if let Some(status) = self.inner_status_code {
+            fields.push(format!("inner_status={}", StatusCode::new(status)));
         }
-        Ok((
-            data,
-            DiagnosticInfo {
-                encoding_mask,
-                symbolic_id,
-                namespace_uri,
-                locale,
-                localized_text,
-                additional_info,
-                inner_status_code,
-                inner_diagnostic_info,
-            },
-        ))
-    }
-}
-
-impl DiagnosticInfo {
-    pub fn new() -> DiagnosticInfo {
-        DiagnosticInfo {
-            encoding_mask: 0,
-            symbolic_id: -1,
-            namespace_uri: -1,
-            locale: -1,
-            localized_text: -1,
-            additional_info: UaString::new(),
-            inner_status_code: 0,
-            inner_diagnostic_info: None,
+        if let Some(inner) = &self.inner_diagnostic_info {
+            fields.push(format!("inner=({})", inner.describe(string_table)));
         }
+        fields.join("; ")
     }
 }
@@ -1,7 +1,7 @@
 use crate::encoding_prelude::*;
 
 use super::{
-    application_description::ApplicationDescription, byte_string::ByteString, string::UaString,
+    application_description::ApplicationDescription, array::UaArray, byte_string::ByteString, string::UaString,
     user_identity_token::UserTokenPolicy,
 };
 
@@ -12,7 +12,7 @@ pub struct EndpointDescription {
     pub (crate) server_certificate: ByteString,
     pub (crate) security_mode: u32,
     pub (crate) security_policy_uri: UaString,
-    pub (crate) user_identity_tokens: Vec::<UserTokenPolicy>,
+    pub (crate) user_identity_tokens: UaArray<UserTokenPolicy>,
     pub (crate) transport_profile_uri: UaString,
     pub (crate) security_level: u8,
 }
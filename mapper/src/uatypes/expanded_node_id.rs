@@ -1,6 +1,9 @@
 use super::node_id::{EncodingValue, NodeId};
 use super::string::UaString;
 use crate::encoding_prelude::*;
+use crate::result_prelude::*;
+use std::fmt::{self, Display};
+use std::str::FromStr;
 
 #[derive(Debug,Clone)]
 pub struct ExpandedNodeId {
@@ -10,43 +13,37 @@ pub struct ExpandedNodeId {
 }
 
 impl Serialize for ExpandedNodeId {
-    fn serialize(&self) -> Vec<u8> {
+    fn serialize_into(&self, buf: &mut impl BufMut) {
         match (self.namespace_uri.value(), self.server_index) {
             (Some(s), x) if x > 0 => {
-                let mut result = self.nodeid.serialize();
-                result[0] = result[0] | EncodingValue::NAMSPACE_URI | EncodingValue::SERVER_INDEX;
-                result.extend_from_slice(&s.serialize());
-                result.extend_from_slice(&x.serialize());
-                result
+                self.nodeid.serialize_with_flags(EncodingValue::NAMSPACE_URI | EncodingValue::SERVER_INDEX, buf);
+                s.serialize_into(buf);
+                x.serialize_into(buf);
             }
             (Some(s), 0) => {
-                let mut result = self.nodeid.serialize();
-                result[0] = result[0] | EncodingValue::NAMSPACE_URI;
-                result.extend_from_slice(&s.serialize());
-                result
+                self.nodeid.serialize_with_flags(EncodingValue::NAMSPACE_URI, buf);
+                s.serialize_into(buf);
             }
             (None, x) if x > 0 => {
-                let mut result = self.nodeid.serialize();
-                result[0] = result[0] | EncodingValue::SERVER_INDEX;
-                result.extend_from_slice(&x.serialize());
-                result
+                self.nodeid.serialize_with_flags(EncodingValue::SERVER_INDEX, buf);
+                x.serialize_into(buf);
             }
-            _ => Vec::new(),
+            _ => self.nodeid.serialize_into(buf),
         }
     }
 }
 
 impl Deserialize for ExpandedNodeId {
     fn deserialize(data: &[u8]) -> MapperResult<(&[u8], Self)>  {
-        let (_, encoding)=u8::deserialize(data)?;
-        let (mut data, node_id)=NodeId::deserialize(data)?;
+        let (_, encoding)=u8::deserialize(data).map_err(|e| e.in_field("ExpandedNodeId","node_id",data.len()))?;
+        let (mut data, node_id)=NodeId::deserialize(data).map_err(|e| e.in_field("ExpandedNodeId","node_id",data.len()))?;
         let mut namespace_uri=UaString::new();
         let mut server_index=0;
         if encoding & EncodingValue::NAMSPACE_URI == EncodingValue::NAMSPACE_URI{
-            (data, namespace_uri)=UaString::deserialize(data)?;
+            (data, namespace_uri)=UaString::deserialize(data).map_err(|e| e.in_field("ExpandedNodeId","namespace_uri",data.len()))?;
         }
         if encoding & EncodingValue::SERVER_INDEX == EncodingValue::SERVER_INDEX{
-            (data, server_index)=u32::deserialize(data)?;
+            (data, server_index)=u32::deserialize(data).map_err(|e| e.in_field("ExpandedNodeId","server_index",data.len()))?;
         }
         Ok((data,ExpandedNodeId{
             nodeid:node_id,
@@ -55,3 +52,95 @@ impl Deserialize for ExpandedNodeId {
         }))
     }
 }
+
+impl ExpandedNodeId {
+    pub fn new(nodeid: NodeId, namespace_uri: UaString, server_index: u32) -> ExpandedNodeId {
+        ExpandedNodeId { nodeid, namespace_uri, server_index }
+    }
+
+    pub fn node_id(&self) -> &NodeId {
+        &self.nodeid
+    }
+
+    pub fn namespace_uri(&self) -> Option<&String> {
+        self.namespace_uri.value().as_ref()
+    }
+
+    /// Node id of the local server, the namespace uri is replaced by its index in the namespace array of the server
+    pub fn resolve(&self, namespace_array: &[UaString]) -> MapperResult<NodeId> {
+        if self.server_index != 0 {
+            return Err(MapperError::new(MapperErrorKind::UnexpectedValue, "node of another server"));
+        }
+        let uri = match self.namespace_uri() {
+            Some(uri) => uri,
+            None => return Ok(self.nodeid.clone()),
+        };
+        let index = namespace_array
+            .iter()
+            .position(|n| n.value().as_ref() == Some(uri))
+            .ok_or_else(|| MapperError::new(MapperErrorKind::UnexpectedValue, "namespace uri not in the namespace array of the server"))?;
+        let mut node_id = self.nodeid.clone();
+        node_id.namespace = index as u16;
+        Ok(node_id)
+    }
+}
+
+/// Expanded node id from its standard text: a node id preceded by the optional `svr=<index>;` and `nsu=<uri>;`,
+/// e.g. `svr=1;nsu=http://opcfoundation.org/UA/;i=85`. The `;` and `%` of the uri are percent-encoded (`%3B`, `%25`).
+impl FromStr for ExpandedNodeId {
+    type Err = MapperError;
+
+    fn from_str(text: &str) -> MapperResult<ExpandedNodeId> {
+        let mut text = text.trim();
+        let mut server_index = 0;
+        let mut namespace_uri = UaString::new();
+        if let Some(rest) = text.strip_prefix("svr=") {
+            let (index, rest) = rest
+                .split_once(';')
+                .ok_or_else(|| MapperError::new(MapperErrorKind::UnexpectedValue, "invalid expanded node id text"))?;
+            server_index = index
+                .parse::<u32>()
+                .map_err(|_| MapperError::new(MapperErrorKind::UnexpectedValue, "invalid server index of expanded node id"))?;
+            text = rest;
+        }
+        if let Some(rest) = text.strip_prefix("nsu=") {
+            let (uri, rest) = rest
+                .split_once(';')
+                .ok_or_else(|| MapperError::new(MapperErrorKind::UnexpectedValue, "invalid expanded node id text"))?;
+            namespace_uri = UaString::from(unescape_uri(uri)?.as_str());
+            text = rest;
+        }
+        Ok(ExpandedNodeId::new(text.parse::<NodeId>()?, namespace_uri, server_index))
+    }
+}
+
+impl Display for ExpandedNodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.server_index != 0 {
+            write!(f, "svr={};", self.server_index)?;
+        }
+        match self.namespace_uri() {
+            //the namespace index is replaced by the uri
+            Some(uri) => write!(f, "nsu={};{}", uri.replace('%', "%25").replace(';', "%3B"), NodeId::new(0, self.nodeid.identifier.clone())),
+            None => write!(f, "{}", self.nodeid),
+        }
+    }
+}
+
+//decode the percent-encoded characters of a namespace uri
+fn unescape_uri(uri: &str) -> MapperResult<String> {
+    let error = || MapperError::new(MapperErrorKind::UnexpectedValue, "invalid percent-encoding in the namespace uri");
+    let mut bytes = Vec::with_capacity(uri.len());
+    let mut rest = uri.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = tail.get(..2).and_then(|h| std::str::from_utf8(h).ok()).ok_or_else(error)?;
+            bytes.push(u8::from_str_radix(hex, 16).map_err(|_| error())?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| error())
+}
@@ -1,20 +1,26 @@
 use crate::{crypto::{security_policy::{SecurityPolicyUri, SecurityPolicy}, pkey::PrivateKey}};
+use std::any::TypeId;
+
 use crate::encoding_prelude::*;
+use crate::result_prelude::*;
 use crate::crypto::pkey::PublicKey;
 use super::prelude::*;
 
-#[derive(Debug,Clone)]
+#[derive(Debug,Clone,Serialize,Deserialize)]
 pub struct ExtensionObject {
     pub(crate) type_id: NodeId,
-    pub(crate) encoding: u8,
     pub(crate) body: ExtensionObjectBody,
 }
 
-//Directly encoded as ByteString
-#[derive(Debug,Clone)]
+//the encoding byte gives the kind of body
+#[derive(Debug,Clone,Serialize,Deserialize)]
 pub enum ExtensionObjectBody {
+    #[ua(tag = EncodingValue::NO_BODY)]
     None,
+    //Directly encoded as ByteString
+    #[ua(tag = EncodingValue::BYTE_STRING)]
     ByteString(ByteString),
+    #[ua(tag = EncodingValue::XML_ELEMENT)]
     XmlElement(XmlElement),
 }
 
@@ -25,6 +31,49 @@ impl EncodingValue {
     pub const XML_ELEMENT: u8 = 0x02;
 }
 impl ExtensionObject{
+    ///ExtensionObject without type nor body
+    pub fn null() -> Self {
+        ExtensionObject {
+            type_id: NodeId::new_numeric(0, 0),
+            body: ExtensionObjectBody::None,
+        }
+    }
+
+    ///ExtensionObject with the binary encoding of `body`
+    pub(crate) fn from_body<T: Serialize + BinaryEncodingId>(body: &T) -> Self {
+        ExtensionObject {
+            type_id: NodeId::new_numeric(0, T::ENCODING_ID),
+            body: ExtensionObjectBody::ByteString(ByteString::from(body.serialize())),
+        }
+    }
+
+    //encoded body, None when there is no body or an xml body
+    fn binary_body(&self) -> Option<&[u8]> {
+        match &self.body {
+            ExtensionObjectBody::ByteString(ByteString { value: Some(body) }) => Some(body),
+            _ => None,
+        }
+    }
+
+    /// Decode the body as `T`. Fails if the type of the body is registered as another structure.
+    pub fn decode<T: Deserialize + 'static>(&self) -> MapperResult<T> {
+        if let Some(registration) = TypeRegistry::lookup(&self.type_id) {
+            if (registration.type_id)() != TypeId::of::<T>() {
+                return Err(MapperError::new(MapperErrorKind::UnexpectedValue, "ExtensionObject body is not of the requested type"));
+            }
+        }
+        let body = self.binary_body().ok_or_else(|| MapperError::new(MapperErrorKind::UnexpectedValue, "ExtensionObject has no binary body"))?;
+        Ok(T::deserialize(body)?.1)
+    }
+
+    /// Decode the body with the structure registered for its type. `None` if the type is unknown or there is no binary body.
+    pub fn decode_known(&self) -> MapperResult<Option<Box<dyn DecodedBody>>> {
+        match (TypeRegistry::lookup(&self.type_id), self.binary_body()) {
+            (Some(registration), Some(body)) => Ok(Some((registration.decoder)(body)?)),
+            _ => Ok(None),
+        }
+    }
+
     pub(crate) fn new_user(user:&str,passwd:&str,security_policy_uri:&str,policy_id:&UaString, server_public_key:&PublicKey,private_key:&PrivateKey,server_nonce:&ByteString )-> Self{
         
         let mut body=UserNameIdentityToken{
@@ -45,11 +94,7 @@ impl ExtensionObject{
         }else{
             
         }
-        ExtensionObject {
-            type_id: NodeId::new_numeric(0, 324),
-            encoding: EncodingValue::BYTE_STRING,
-            body:ExtensionObjectBody::ByteString(ByteString::from(body.serialize())),
-        }
+        ExtensionObject::from_body(&body)
     }
     pub(crate) fn anon(anonymous_policy_id:&UaString)-> Self{
         
@@ -58,11 +103,7 @@ impl ExtensionObject{
 
         };
 
-        ExtensionObject {
-            type_id: NodeId::new_numeric(0, 321),
-            encoding: EncodingValue::BYTE_STRING,
-            body:ExtensionObjectBody::ByteString(ByteString::from(body.serialize())),
-        }
+        ExtensionObject::from_body(&body)
     }
     pub(crate) fn new_user_cert(policy_id:&UaString,user_certificate:&ByteString )-> Self{
         
@@ -71,77 +112,7 @@ impl ExtensionObject{
             certificate: user_certificate.clone(),
 
         };
-        ExtensionObject {
-            type_id: NodeId::new_numeric(0, 327),
-            encoding: EncodingValue::BYTE_STRING,
-            body:ExtensionObjectBody::ByteString(ByteString::from(body.serialize())),
-        }
-    }
-
-}
-impl Serialize for ExtensionObject {
-    fn serialize(&self) -> Vec<u8> {
-        let mut result = self.type_id.serialize();
-        match &self.body {
-            ExtensionObjectBody::None => result.push(EncodingValue::NO_BODY),
-
-            ExtensionObjectBody::ByteString(b) => {
-                result.push(EncodingValue::BYTE_STRING);
-                result.extend_from_slice(&b.serialize());
-            }
-
-            ExtensionObjectBody::XmlElement(x) => {
-                result.push(EncodingValue::BYTE_STRING);
-                result.extend_from_slice(&x.serialize());
-            }
-        }
-        result
+        ExtensionObject::from_body(&body)
     }
-}
 
-impl Deserialize for ExtensionObject {
-    fn deserialize(data: &[u8]) -> MapperResult<(&[u8], Self)> {
-        let (data, type_id) = NodeId::deserialize(data)?;
-        let (data, encoding) = u8::deserialize(data)?;
-        match encoding {
-            EncodingValue::NO_BODY => Ok((
-                data,
-                ExtensionObject {
-                    type_id,
-                    encoding,
-                    body: ExtensionObjectBody::None,
-                },
-            )),
-            EncodingValue::BYTE_STRING => {
-                let (data, body) = ByteString::deserialize(data)?;
-                Ok((
-                    data,
-                    ExtensionObject {
-                        type_id,
-                        encoding,
-                        body: ExtensionObjectBody::ByteString(ByteString::from(body.serialize())),
-                    },
-                ))
-            }
-            EncodingValue::XML_ELEMENT => {
-                let (data, body) = XmlElement::deserialize(data)?;
-                Ok((
-                    data,
-                    ExtensionObject {
-                        type_id,
-                        encoding,
-                        body: ExtensionObjectBody::XmlElement(body),
-                    },
-                ))
-            }
-            _ => Ok((
-                data,
-                ExtensionObject {
-                    type_id,
-                    encoding,
-                    body: ExtensionObjectBody::None,
-                },
-            )),
-        }
-    }
 }
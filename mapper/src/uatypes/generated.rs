@@ -0,0 +1,10 @@
+//! Structures and enumerations generated by build.rs from schema/Opc.Ua.Types.bsd.
+//! Types already written by hand (headers, endpoints, identity tokens...) are not generated
+//! and are used as is by the generated fields. Enumerations are generated as holders of u32 constants
+//! and arrays as `UaArray` (the `NoOf` length fields of the schema are part of the array encoding).
+//! The service messages are generated in `msg::services`.
+
+use crate::encoding_prelude::*;
+use super::prelude::*;
+
+include!(concat!(env!("OUT_DIR"), "/types.rs"));
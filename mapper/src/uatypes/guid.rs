@@ -1,6 +1,7 @@
 use std::str::FromStr;
 
 use crate::encoding_prelude::*;
+use crate::result_prelude::*;
 use uuid::Uuid;
 
 #[derive(Debug, PartialEq,Clone)]
@@ -22,26 +23,36 @@ impl Guid {
             uuid: Uuid::from_str(guid).unwrap(),
         }
     }
+    ///Guid from its text, with or without braces: `72962b91-fa75-4ae6-8d28-b404dc7daf63`
+    pub fn parse(guid: &str) -> MapperResult<Guid> {
+        Uuid::from_str(guid.trim())
+            .map(Guid::from_uuid)
+            .map_err(|_| MapperError::new(MapperErrorKind::UnexpectedValue, "invalid guid text"))
+    }
+}
+
+impl std::fmt::Display for Guid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.uuid.hyphenated())
+    }
 }
 
 impl Serialize for Guid {
-    fn serialize(&self) -> Vec<u8> {
-        let mut result = Vec::new();
+    fn serialize_into(&self, buf: &mut impl BufMut) {
         let (field1, field2, field3, field4) = self.uuid.as_fields();
-        result.extend_from_slice(&field1.serialize());
-        result.extend_from_slice(&field2.serialize());
-        result.extend_from_slice(&field3.serialize());
-        result.extend_from_slice(field4);
-        result
+        field1.serialize_into(buf);
+        field2.serialize_into(buf);
+        field3.serialize_into(buf);
+        buf.put_slice(field4);
     }
 }
 
 impl Deserialize for Guid {
     fn deserialize(data: &[u8]) -> MapperResult<(&[u8], Self)> {
-        let (data, field1) = u32::deserialize(data)?;
-        let (data, field2) = u16::deserialize(data)?;
-        let (data, field3) = u16::deserialize(data)?;
-        let (data, field4) = Guid::take_count(data, 8)?;
+        let (data, field1) = u32::deserialize(data).map_err(|e| e.in_field("Guid", "data1", data.len()))?;
+        let (data, field2) = u16::deserialize(data).map_err(|e| e.in_field("Guid", "data2", data.len()))?;
+        let (data, field3) = u16::deserialize(data).map_err(|e| e.in_field("Guid", "data3", data.len()))?;
+        let (data, field4) = Guid::take_count(data, 8).map_err(|e| e.in_field("Guid", "data4", data.len()))?;
         let field4 = arrayref::array_ref!(field4, 0, 8);
         let uuid = uuid::Builder::from_fields(field1, field2, field3, &field4).into_uuid();
         Ok((data, Guid { uuid }))
@@ -33,10 +33,15 @@ pub mod xml_element;
 pub mod attribute_id;
 pub mod write_value;
 pub mod data_type_id;
+pub mod decode_limits;
+pub mod node_ids;
+pub mod generated;
+pub mod type_registry;
 
 
 pub mod prelude {
     pub(crate) use crate::uatypes::application_description::*;
+    pub(crate) use crate::uatypes::array::*;
     pub(crate) use crate::uatypes::diagnostic_info::*;
     pub(crate) use crate::uatypes::extension_object::*;
     pub(crate) use crate::uatypes::byte_string::*;
@@ -59,6 +64,9 @@ pub mod prelude {
     pub(crate) use crate::uatypes::write_value::*;
     pub(crate) use crate::uatypes::variant::*;
     pub(crate) use crate::uatypes::data_type_id::*;
+    pub(crate) use crate::uatypes::decode_limits::*;
+    pub(crate) use crate::uatypes::node_ids::*;
+    pub(crate) use crate::uatypes::type_registry::*;
     pub(crate) use super::ToVariant;
 }
 
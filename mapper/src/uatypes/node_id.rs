@@ -3,6 +3,8 @@ use super::guid::Guid;
 use super::string::UaString;
 use crate::encoding_prelude::*;
 use crate::result_prelude::{*};
+use std::fmt::{self, Display};
+use std::str::FromStr;
 
 #[derive(Debug, PartialEq,Clone)]
 pub struct NodeId {
@@ -18,43 +20,45 @@ pub enum Identifier {
     ByteString(ByteString),
 }
 
+//encoded by hand: the encoding byte also selects the size of the namespace and of a numeric identifier
 impl Serialize for NodeId {
-    fn serialize(&self) -> Vec<u8> {
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        self.serialize_with_flags(0x00, buf);
+    }
+}
+
+impl NodeId {
+    //encoding with extra flags in the encoding byte, used by ExpandedNodeId
+    pub(crate) fn serialize_with_flags(&self, flags: u8, buf: &mut impl BufMut) {
         match &self.identifier {
             Identifier::Numeric(i) => {
-                if *i < 255 && self.namespace == 0 {
-                    let mut result = vec![EncodingValue::TWO_BYTE];
-                    result.extend_from_slice(&(*i as u8).serialize());
-                    result
-                } else if *i < 65535 {
-                    let mut result = vec![EncodingValue::FOUR_BYTE];
-                    result.extend_from_slice(&(self.namespace as u8).serialize());
-                    result.extend_from_slice(&(*i as u16).serialize());
-                    result
+                if *i <= u8::MAX as u32 && self.namespace == 0 {
+                    buf.put_u8(EncodingValue::TWO_BYTE | flags);
+                    (*i as u8).serialize_into(buf);
+                } else if *i <= u16::MAX as u32 && self.namespace <= u8::MAX as u16 {
+                    buf.put_u8(EncodingValue::FOUR_BYTE | flags);
+                    (self.namespace as u8).serialize_into(buf);
+                    (*i as u16).serialize_into(buf);
                 } else {
-                    let mut result = vec![EncodingValue::NUMERIC];
-                    result.extend_from_slice(&self.namespace.serialize());
-                    result.extend_from_slice(&i.serialize());
-                    result
+                    buf.put_u8(EncodingValue::NUMERIC | flags);
+                    self.namespace.serialize_into(buf);
+                    i.serialize_into(buf);
                 }
             }
             Identifier::String(s) => {
-                let mut result = vec![EncodingValue::STRING];
-                result.extend_from_slice(&self.namespace.serialize());
-                result.extend_from_slice(&s.serialize());
-                result
+                buf.put_u8(EncodingValue::STRING | flags);
+                self.namespace.serialize_into(buf);
+                s.serialize_into(buf);
             }
             Identifier::Guid(guid) => {
-                let mut result = vec![EncodingValue::GUID];
-                result.extend_from_slice(&self.namespace.serialize());
-                result.extend_from_slice(&guid.serialize());
-                result
+                buf.put_u8(EncodingValue::GUID | flags);
+                self.namespace.serialize_into(buf);
+                guid.serialize_into(buf);
             }
             Identifier::ByteString(b) => {
-                let mut result = vec![EncodingValue::BYTE_STRING];
-                result.extend_from_slice(&self.namespace.serialize());
-                result.extend_from_slice(&b.serialize());
-                result
+                buf.put_u8(EncodingValue::BYTE_STRING | flags);
+                self.namespace.serialize_into(buf);
+                b.serialize_into(buf);
             }
         }
     }
@@ -62,10 +66,11 @@ impl Serialize for NodeId {
 
 impl Deserialize for NodeId {
     fn deserialize(data: &[u8]) -> MapperResult<(&[u8], Self)> {
-        let (data, encoding) = u8::deserialize(data)?;
+        let in_field = |field: &'static str, remaining: usize| move |e: MapperError| e.in_field("NodeId", field, remaining);
+        let (data, encoding) = u8::deserialize(data).map_err(in_field("encoding", data.len()))?;
         match encoding {
             EncodingValue::TWO_BYTE => {
-                let (data, numeric) = u8::deserialize(data)?;
+                let (data, numeric) = u8::deserialize(data).map_err(in_field("identifier", data.len()))?;
                 Ok((
                     data,
                     NodeId {
@@ -75,8 +80,8 @@ impl Deserialize for NodeId {
                 ))
             }
             EncodingValue::FOUR_BYTE => {
-                let (data, namespace) = u8::deserialize(data)?;
-                let (data, numeric) = u16::deserialize(data)?;
+                let (data, namespace) = u8::deserialize(data).map_err(in_field("namespace", data.len()))?;
+                let (data, numeric) = u16::deserialize(data).map_err(in_field("identifier", data.len()))?;
                 Ok((
                     data,
                     NodeId {
@@ -86,8 +91,8 @@ impl Deserialize for NodeId {
                 ))
             }
             EncodingValue::NUMERIC => {
-                let (data, namespace) = u16::deserialize(data)?;
-                let (data, numeric) = u32::deserialize(data)?;
+                let (data, namespace) = u16::deserialize(data).map_err(in_field("namespace", data.len()))?;
+                let (data, numeric) = u32::deserialize(data).map_err(in_field("identifier", data.len()))?;
                 Ok((
                     data,
                     NodeId {
@@ -97,8 +102,8 @@ impl Deserialize for NodeId {
                 ))
             }
             EncodingValue::GUID => {
-                let (data, namespace) = u16::deserialize(data)?;
-                let (data, g) = Guid::deserialize(data)?;
+                let (data, namespace) = u16::deserialize(data).map_err(in_field("namespace", data.len()))?;
+                let (data, g) = Guid::deserialize(data).map_err(in_field("identifier", data.len()))?;
                 Ok((
                     data,
                     NodeId {
@@ -108,8 +113,8 @@ impl Deserialize for NodeId {
                 ))
             }
             EncodingValue::BYTE_STRING => {
-                let (data, namespace) = u16::deserialize(data)?;
-                let (data, s) = ByteString::deserialize(data)?;
+                let (data, namespace) = u16::deserialize(data).map_err(in_field("namespace", data.len()))?;
+                let (data, s) = ByteString::deserialize(data).map_err(in_field("identifier", data.len()))?;
                 Ok((
                     data,
                     NodeId {
@@ -119,8 +124,8 @@ impl Deserialize for NodeId {
                 ))
             }
             EncodingValue::STRING => {
-                let (data, namespace) = u16::deserialize(data)?;
-                let (data, s) = UaString::deserialize(data)?;
+                let (data, namespace) = u16::deserialize(data).map_err(in_field("namespace", data.len()))?;
+                let (data, s) = UaString::deserialize(data).map_err(in_field("identifier", data.len()))?;
                 Ok((
                     data,
                     NodeId {
@@ -130,7 +135,7 @@ impl Deserialize for NodeId {
                 ))
             }
 
-            _ => Err(MapperError::new(MapperErrorKind::ParsingError,"node id parsing failed")),
+            _ => Err(MapperError::new(MapperErrorKind::ParsingError,"node id parsing failed").in_field("NodeId", "encoding", data.len() + 1)),
         }
     }
 }
@@ -197,11 +202,51 @@ impl NodeId {
         }
     }
 
-    
     pub fn empty() -> NodeId {
         NodeId::new_numeric(0, 0)
     }
 }
+/// Node id from its standard text: `ns=2;s=Foo`, `i=85`, `g=<guid>` or `b=<base64>`. The namespace is 0 when `ns=` is missing.
+impl FromStr for NodeId {
+    type Err = MapperError;
+
+    fn from_str(text: &str) -> MapperResult<NodeId> {
+        let error = || MapperError::new(MapperErrorKind::UnexpectedValue, "invalid node id text");
+        let text = text.trim();
+        let (namespace, id) = match text.strip_prefix("ns=") {
+            Some(rest) => {
+                let (namespace, id) = rest.split_once(';').ok_or_else(error)?;
+                (namespace.parse::<u16>().map_err(|_| error())?, id)
+            }
+            None => (0, text),
+        };
+        let (kind, value) = id.split_once('=').ok_or_else(error)?;
+        let identifier = match kind {
+            "i" => Identifier::Numeric(value.parse::<u32>().map_err(|_| error())?),
+            "s" => Identifier::String(UaString::from(value)),
+            "g" => Identifier::Guid(Guid::parse(value)?),
+            //always base64 as in the text written by Display: a base64 value may start with `0x`
+            "b" if value.is_empty() => Identifier::ByteString(ByteString::from(vec![])),
+            "b" => Identifier::ByteString(ByteString::from(openssl::base64::decode_block(value).map_err(|_| error())?)),
+            _ => return Err(error()),
+        };
+        Ok(NodeId::new(namespace, identifier))
+    }
+}
+
+impl Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.namespace != 0 {
+            write!(f, "ns={};", self.namespace)?;
+        }
+        match &self.identifier {
+            Identifier::Numeric(id) => write!(f, "i={}", id),
+            Identifier::String(id) => write!(f, "s={}", id.value().as_deref().unwrap_or("")),
+            Identifier::Guid(id) => write!(f, "g={}", id),
+            Identifier::ByteString(id) => write!(f, "b={}", openssl::base64::encode_block(id.value.as_deref().unwrap_or(&[]))),
+        }
+    }
+}
 pub struct EncodingValue;
 impl EncodingValue {
     pub const TWO_BYTE: u8 = 0x00;
@@ -0,0 +1,5 @@
+//! Numeric identifiers of the nodes of namespace 0 (`ObjectIds`, `DataTypeIds`, `VariableIds`),
+//! generated by build.rs from schema/NodeIds.csv with the names of the specification.
+//! The binary encoding ids of the messages are the `X_Encoding_DefaultBinary` objects.
+
+include!(concat!(env!("OUT_DIR"), "/node_ids.rs"));
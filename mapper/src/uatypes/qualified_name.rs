@@ -3,7 +3,7 @@ use crate::encoding_prelude::*;
 use super::string::UaString;
 use crate::MapperResult;
 
-#[derive(Debug, Serialize, Deserialize,Clone)]
+#[derive(Debug, Serialize, Deserialize,Clone, Mutate)]
 pub struct QualifiedName {
     pub(crate) namespace_index: u16,
     pub(crate) name: UaString,
@@ -16,6 +16,14 @@ impl QualifiedName {
             name,
         }
     }
+    ///Qualified name from its text `<namespace>:<name>`, the namespace is 0 without prefix
+    pub fn parse(text: &str) -> QualifiedName {
+        let namespace = text.split_once(':').and_then(|(namespace, name)| Some((namespace.parse::<u16>().ok()?, name)));
+        match namespace {
+            Some((namespace, name)) => QualifiedName::new(namespace, UaString::from(name)),
+            None => QualifiedName::new(0, UaString::from(text)),
+        }
+    }
     pub fn empty() -> QualifiedName {
         QualifiedName {
             namespace_index:0,
@@ -2,12 +2,14 @@ use derive_macro::{Serialize, Deserialize};
 
 // use super::qualified_name::QualifiedName;
 use super::node_id::*;
+use super::expanded_node_id::ExpandedNodeId;
 use super::string::*;
 use super::qualified_name::*;
+use super::attribute_id::*;
 use crate::encoding_prelude::*;
 
 
-#[derive(Serialize,Deserialize,Debug)]
+#[derive(Serialize,Deserialize,Debug,Clone, Mutate)]
 pub(crate) struct ReadValueId {
     pub(crate) node_id:NodeId,
     pub(crate) attribute_id: u32,
@@ -16,5 +18,26 @@ pub(crate) struct ReadValueId {
 
 }
 
+impl ReadValueId {
+    pub(crate) fn new(node_id: &NodeId, attribute_id: u32) -> ReadValueId {
+        ReadValueId {
+            node_id: node_id.clone(),
+            attribute_id,
+            index_range: UaString::new(),
+            data_encoding: QualifiedName::empty(),
+        }
+    }
 
-
+    ///item of a read from its text `<node id>@<attribute>`: `ns=1;s=the.answer@UserAccessLevel`, the attribute is Value when omitted.
+    ///The node id is also returned as given since a namespace uri (`nsu=`) has to be resolved for each server.
+    ///A string node id may contain `@`: the item ends with an attribute only when the text after the last `@` is an attribute name.
+    pub(crate) fn parse(item: &str) -> MapperResult<(ExpandedNodeId, ReadValueId)> {
+        let (node_id, attribute_id) = item
+            .rsplit_once('@')
+            .and_then(|(node_id, attribute)| Some((node_id, AttributeId::from_name(attribute)?)))
+            .unwrap_or((item, AttributeId::VALUE));
+        let node_id = node_id.parse::<ExpandedNodeId>()?;
+        let item = ReadValueId::new(node_id.node_id(), attribute_id);
+        Ok((node_id, item))
+    }
+}
@@ -2,7 +2,7 @@ use crate::{encoding_prelude::*, crypto::{security_policy::SecurityPolicy, }};
 
 use super::{string::UaString, byte_string::ByteString};
 use crate::crypto::policy_prelude::*;
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Mutate)]
 pub (crate) struct SignatureData{
     pub (crate)algorithm: UaString,
     pub (crate) signature: ByteString,
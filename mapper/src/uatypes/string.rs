@@ -1,4 +1,6 @@
 use crate::encoding_prelude::*;
+use crate::result_prelude::*;
+use super::decode_limits::DecodeLimits;
 
 //String need to be able to deal with null. So we need to wrap the String into an Option.
 #[derive(PartialEq, Debug,Clone)]
@@ -7,22 +9,13 @@ pub struct UaString {
 }
 
 impl Serialize for UaString {
-    fn serialize(&self) -> Vec<u8> {
+    fn serialize_into(&self, buf: &mut impl BufMut) {
         match &self.value {
-            Some(s) if s.len()==0 => {
-                let mut serialization = (s.len() as i32).to_le_bytes().to_vec();
-                serialization.extend_from_slice(s.as_bytes());
-                serialization
-            }
             Some(s) => {
-                let mut serialization = (s.len() as i32).to_le_bytes().to_vec();
-                serialization.extend_from_slice(s.as_bytes());
-                serialization
-            }
-            _ => {
-                let serialization = (-1 as i32).serialize();
-                serialization
+                (s.len() as i32).serialize_into(buf);
+                buf.put_slice(s.as_bytes());
             }
+            _ => (-1_i32).serialize_into(buf),
         }
     }
 }
@@ -33,9 +26,10 @@ impl Deserialize for UaString {
         if value < 0 {
             Ok((data, UaString::new()))
         } else {
+            DecodeLimits::check_string_length(value as usize)?;
             let (data, value) = UaString::take_count(data, value as usize)?;
-            let value = UaString::from_utf8(value);
-            Ok((data, value))
+            let value = String::from_utf8(value).map_err(|_| MapperError::new(MapperErrorKind::ParsingError, "invalid utf8 string"))?;
+            Ok((data, UaString { value: Some(value) }))
         }
     }
 }
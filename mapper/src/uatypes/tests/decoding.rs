@@ -5,7 +5,7 @@ mod tests {
 
     use crate::{
         uatypes::{
-            application_description::ApplicationDescription, byte_string::ByteString,
+            application_description::ApplicationDescription, array::UaArray, byte_string::ByteString,
             diagnostic_info::DiagnosticInfo, localized_text::LocalizedText, string::UaString,
             user_identity_token::UserTokenPolicy,
         },
@@ -126,12 +126,12 @@ mod tests {
     fn diagnostic_info_deserialize() {
         let diag1 = DiagnosticInfo {
             encoding_mask: crate::uatypes::diagnostic_info::EncodingValue::SYMBOLIC_ID,
-            symbolic_id: 1,
-            namespace_uri: -1,
-            locale: -1,
-            localized_text: -1,
-            additional_info: UaString::new(),
-            inner_status_code: 0xFFFFFFFF,
+            symbolic_id: Some(1),
+            namespace_uri: None,
+            locale: None,
+            localized_text: None,
+            additional_info: None,
+            inner_status_code: None,
             inner_diagnostic_info: None,
         };
         let (_, result) = DiagnosticInfo::deserialize(&diag1.serialize()).unwrap();
@@ -139,12 +139,12 @@ mod tests {
         let diag2 = DiagnosticInfo {
             encoding_mask: crate::uatypes::diagnostic_info::EncodingValue::SYMBOLIC_ID
                 | crate::uatypes::diagnostic_info::EncodingValue::INNER_DIAGNOSTIC_INFO,
-            symbolic_id: 1,
-            namespace_uri: -1,
-            locale: -1,
-            localized_text: -1,
-            additional_info: UaString::new(),
-            inner_status_code: 0xFFFFFFFF,
+            symbolic_id: Some(1),
+            namespace_uri: None,
+            locale: None,
+            localized_text: None,
+            additional_info: None,
+            inner_status_code: None,
             inner_diagnostic_info: Some(Box::new(diag1)),
         };
         let (_, result) = DiagnosticInfo::deserialize(&diag2.serialize()).unwrap();
@@ -152,12 +152,12 @@ mod tests {
         let diag3 = DiagnosticInfo {
             encoding_mask: crate::uatypes::diagnostic_info::EncodingValue::SYMBOLIC_ID
                 | crate::uatypes::diagnostic_info::EncodingValue::INNER_DIAGNOSTIC_INFO,
-            symbolic_id: 1,
-            namespace_uri: -1,
-            locale: -1,
-            localized_text: -1,
-            additional_info: UaString::new(),
-            inner_status_code: 0xFFFFFFFF,
+            symbolic_id: Some(1),
+            namespace_uri: None,
+            locale: None,
+            localized_text: None,
+            additional_info: None,
+            inner_status_code: None,
             inner_diagnostic_info: Some(Box::new(diag2)),
         };
         let (_, result) = DiagnosticInfo::deserialize(&diag3.serialize()).unwrap();
@@ -168,8 +168,8 @@ mod tests {
     fn localized_text_deserialize() {
         let ltext = LocalizedText {
             encoding_mask: 03 as u8,
-            locale: UaString::from("CN"),
-            text: UaString::from("Hot水"),
+            locale: Some(UaString::from("CN")),
+            text: Some(UaString::from("Hot水")),
         };
         let result: Vec<u8> = vec![
             0x03, 0x02, 0x00, 0x00, 0x00, 0x43, 0x4e, 0x06, 0x00, 0x00, 0x00, 0x48, 0x6F, 0x74,
@@ -197,6 +197,51 @@ mod tests {
         assert_eq!(derive_test, deserialized);
     }
 
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct DeriveMaskTest {
+        a: u16,
+        #[ua(mask)]
+        mask: u8,
+        #[ua(mask_bit = 0x01)]
+        b: Option<i32>,
+        #[ua(mask_bit = 0x04)]
+        c: Option<UaString>,
+    }
+    #[test]
+    fn derive_mask() {
+        let derive_test = DeriveMaskTest { a: 2, mask: 0, b: None, c: Some(UaString::from("c")) };
+        let serialized_struct = derive_test.serialize();
+        let result: Vec<u8> = vec![0x02, 0x00, 0x04, 0x01, 0x00, 0x00, 0x00, b'c'];
+        assert_eq!(result, serialized_struct);
+        let (_, deserialized) = DeriveMaskTest::deserialize(&serialized_struct).unwrap();
+        assert_eq!(DeriveMaskTest { mask: 0x04, ..derive_test }, deserialized);
+        let derive_test = DeriveMaskTest { a: 2, mask: 0x05, b: Some(-1), c: None };
+        let result: Vec<u8> = vec![0x02, 0x00, 0x01, 0xff, 0xff, 0xff, 0xff];
+        assert_eq!(result, derive_test.serialize());
+        let (rest, deserialized) = DeriveMaskTest::deserialize(&result).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(DeriveMaskTest { mask: 0x01, ..derive_test }, deserialized);
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum DeriveTagTest {
+        #[ua(tag = 0x00)]
+        Empty,
+        #[ua(tag = 0x07)]
+        Value(u16),
+    }
+    #[test]
+    fn derive_tag() {
+        assert_eq!(vec![0x00], DeriveTagTest::Empty.serialize());
+        assert_eq!(vec![0x07, 0x01, 0x00], DeriveTagTest::Value(1).serialize());
+        let (_, deserialized) = DeriveTagTest::deserialize(&[0x07, 0x01, 0x00]).unwrap();
+        assert_eq!(DeriveTagTest::Value(1), deserialized);
+        let (_, deserialized) = DeriveTagTest::deserialize(&[0x00]).unwrap();
+        assert_eq!(DeriveTagTest::Empty, deserialized);
+        assert!(DeriveTagTest::deserialize(&[0x01]).is_err());
+        let error = DeriveTagTest::deserialize(&[0x07, 0x01]).unwrap_err();
+        assert_eq!(error.path().unwrap(), "DeriveTagTest.Value");
+    }
 
     #[test]
     fn derive_deserialize_vec() {
@@ -223,13 +268,13 @@ mod tests {
             product_uri: UaString::from("http://open62541.org"),
             application_name: LocalizedText {
                 encoding_mask: 03 as u8,
-                locale: UaString::from("en"),
-                text: UaString::from("open62541-based OPC UA Application"),
+                locale: Some(UaString::from("en")),
+                text: Some(UaString::from("open62541-based OPC UA Application")),
             },
             application_type: 0,
             gateway_server_uri: UaString::new(),
             discovery_policy_uri: UaString::new(),
-            discovery_urls: vec![UaString::from("opc.tcp://localhost:4840")],
+            discovery_urls: UaArray::from(vec![UaString::from("opc.tcp://localhost:4840")]),
         };
         assert_eq!(aim_application_description, application_description);
     }
@@ -247,4 +292,35 @@ mod tests {
         let (_,result) = UserTokenPolicy::deserialize(&encoded_data).unwrap();
         assert_eq!(user_identity_token, result);
     }
+
+    use crate::uatypes::data_type_id::DataTypeId;
+    use crate::uatypes::variant::{Value, Variant};
+    #[test]
+    fn deserialize_variant_array() {
+        //Read of a Double[3] node
+        let encoded_data = hex::decode("8b03000000000000000000f03f00000000000000400000000000000840").unwrap();
+        let (rest, variant) = Variant::deserialize(&encoded_data).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(variant.dimensions(), vec![3]);
+        assert!(matches!(variant.value[2], Value::Double(v) if v == 3.0));
+        assert_eq!(variant.serialize(), encoded_data);
+    }
+
+    #[test]
+    fn deserialize_variant_matrix() {
+        let encoded_data = hex::decode("c60400000001000000020000000300000004000000020000000200000002000000").unwrap();
+        let (rest, variant) = Variant::deserialize(&encoded_data).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(variant.dimensions(), vec![2, 2]);
+        assert_eq!(variant.serialize(), encoded_data);
+        assert_eq!(variant.serialize(), Variant::from_datatype(DataTypeId::INT_32, "[[1, 2], [3, 4]]").unwrap().serialize());
+        //the dimensions do not match the number of values
+        let encoded_data = hex::decode("c60400000001000000020000000300000004000000020000000300000002000000").unwrap();
+        assert!(Variant::deserialize(&encoded_data).is_err());
+        let encoded_data = hex::decode("c6040000000100000002000000030000000400000002000000ffffffff04000000").unwrap();
+        assert!(Variant::deserialize(&encoded_data).is_err());
+        //dimensions of a scalar
+        let encoded_data = hex::decode("46010000000100000001000000").unwrap();
+        assert!(Variant::deserialize(&encoded_data).is_err());
+    }
 }
@@ -101,24 +101,24 @@ mod tests {
     fn diagnostic_info_serialize() {
         let diag1 = DiagnosticInfo {
             encoding_mask: 0,
-            symbolic_id: 01,
-            namespace_uri: -1,
-            locale: -1,
-            localized_text: -1,
-            additional_info: UaString::new(),
-            inner_status_code: 0xffff,
+            symbolic_id: Some(1),
+            namespace_uri: None,
+            locale: None,
+            localized_text: None,
+            additional_info: None,
+            inner_status_code: None,
             inner_diagnostic_info: None,
         };
         let result: Vec<u8> = vec![0x01, 0x01, 0x00, 0x00, 0x00];
         assert_eq!(result, diag1.serialize());
         let diag2 = DiagnosticInfo {
             encoding_mask: 0,
-            symbolic_id: 1,
-            namespace_uri: -1,
-            locale: -1,
-            localized_text: -1,
-            additional_info: UaString::new(),
-            inner_status_code: 0xffff,
+            symbolic_id: Some(1),
+            namespace_uri: None,
+            locale: None,
+            localized_text: None,
+            additional_info: None,
+            inner_status_code: None,
             inner_diagnostic_info: Some(Box::new(diag1)),
         };
         let result: Vec<u8> = vec![
@@ -136,12 +136,12 @@ mod tests {
         assert_eq!(result, diag2.serialize());
         let diag3 = DiagnosticInfo {
             encoding_mask: 0,
-            symbolic_id: 1,
-            namespace_uri: -1,
-            locale: -1,
-            localized_text: -1,
-            additional_info: UaString::new(),
-            inner_status_code: 0xffff,
+            symbolic_id: Some(1),
+            namespace_uri: None,
+            locale: None,
+            localized_text: None,
+            additional_info: None,
+            inner_status_code: None,
             inner_diagnostic_info: Some(Box::new(diag2)),
         };
         let result: Vec<u8> = vec![
@@ -191,7 +191,6 @@ mod tests {
                 namespace: 0,
                 identifier: Identifier::Numeric(631),
             },
-            encoding: extension_object::EncodingValue::NO_BODY,
             body: extension_object::ExtensionObjectBody::None,
         };
         let result: Vec<u8> = vec![0x01, 0x00, 0x77, 0x02, 0x00];
@@ -231,8 +230,8 @@ mod tests {
     }
 
     impl<'a> Serialize for Stime<'a> {
-        fn serialize(&self) -> Vec<u8> {
-            self.c.serialize()
+        fn serialize_into(&self, buf: &mut impl BufMut) {
+            self.c.serialize_into(buf);
         }
     }
 
@@ -246,4 +245,117 @@ mod tests {
         let result: Vec<u8> = vec![0x00, 0xca, 0x9a, 0x3b, 0x00, 0xca, 0x9a, 0x3b];
         assert_eq!(result, serialized_struct);
     }
+
+    use crate::uatypes::data_type_id::DataTypeId;
+    use crate::uatypes::variant::{Value, Variant};
+    #[test]
+    fn variant_array_serialize() {
+        let array = Variant::from_datatype(DataTypeId::INT_32, "[1, 2, -3]").unwrap();
+        assert!(array.is_array());
+        assert_eq!(array.dimensions(), vec![3]);
+        let result: Vec<u8> = vec![
+            0x86, 0x03, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0xfd, 0xff, 0xff, 0xff,
+        ];
+        assert_eq!(result, array.serialize());
+        //an array of one element is not a scalar
+        assert_eq!(vec![0x81, 0x01, 0x00, 0x00, 0x00, 0x01], Variant::from_datatype(DataTypeId::BOOLEAN, "[true]").unwrap().serialize());
+        assert_eq!(vec![0x01, 0x01], Variant::from_datatype(DataTypeId::BOOLEAN, "true").unwrap().serialize());
+        //an empty array keeps its type
+        assert_eq!(vec![0x8b, 0x00, 0x00, 0x00, 0x00], Variant::from_datatype(DataTypeId::DOUBLE, "[]").unwrap().serialize());
+        //quoted strings
+        let strings = Variant::from_datatype(DataTypeId::STRING, r#"["a,b", c , "[\"d\"]"]"#).unwrap();
+        let values: Vec<String> = strings.value.iter().map(|v| match v {
+            Value::String(s) => s.value.clone().unwrap(),
+            v => panic!("unexpected value {:?}", v),
+        }).collect();
+        assert_eq!(values, vec!["a,b", "c", "[\"d\"]"]);
+        //a leading backslash gives a scalar
+        let scalar = |text| {
+            let variant = Variant::from_datatype(DataTypeId::STRING, text).unwrap();
+            assert!(!variant.is_array());
+            match &variant.value[..] {
+                [Value::String(s)] => s.value.clone().unwrap(),
+                v => panic!("unexpected value {:?}", v),
+            }
+        };
+        assert_eq!(scalar(r"\[not an array]"), "[not an array]");
+        assert_eq!(scalar(r"\\a"), "\\a");
+    }
+
+    #[test]
+    fn variant_matrix_serialize() {
+        let matrix = Variant::from_datatype(DataTypeId::BYTE, "[[1, 2, 3], [4, 5, 6]]").unwrap();
+        assert_eq!(matrix.dimensions(), vec![2, 3]);
+        let result: Vec<u8> = vec![
+            0xc3, 0x06, 0x00, 0x00, 0x00, 1, 2, 3, 4, 5, 6, 0x02, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00,
+        ];
+        assert_eq!(result, matrix.serialize());
+        let cube = Variant::from_datatype(DataTypeId::UINT_16, "[[[1], [2]], [[3], [4]]]").unwrap();
+        assert_eq!(cube.dimensions(), vec![2, 2, 1]);
+        assert_eq!(cube.value.len(), 4);
+        //invalid text
+        assert!(Variant::from_datatype(DataTypeId::BYTE, "[[1, 2], [3]]").is_err());
+        assert!(Variant::from_datatype(DataTypeId::BYTE, "[[1, 2], 3]").is_err());
+        assert!(Variant::from_datatype(DataTypeId::BYTE, "[1, 2").is_err());
+        assert!(Variant::from_datatype(DataTypeId::BYTE, "[1, 2] 3").is_err());
+        assert!(Variant::from_datatype(DataTypeId::BYTE, "[1, 256]").is_err());
+    }
+
+    #[test]
+    fn variant_builtin_types_from_text() {
+        let serialize = |type_, text| Variant::from_datatype(type_, text).unwrap().serialize();
+        //2024-03-01T12:30:00.5Z: 133537698000000000 + 5000000 ticks
+        let date: Vec<u8> = [vec![0x0d], 133537698005000000_i64.to_le_bytes().to_vec()].concat();
+        assert_eq!(date, serialize(DataTypeId::DATETIME, "2024-03-01T12:30:00.5Z"));
+        assert_eq!(date, serialize(DataTypeId::DATETIME, "2024-03-01T14:30:00.5+02:00"));
+        assert_eq!([vec![0x0d], 116444736000000000_i64.to_le_bytes().to_vec()].concat(), serialize(DataTypeId::DATETIME, "1970-01-01"));
+        assert_eq!(
+            vec![0x0e, 0x91, 0x2B, 0x96, 0x72, 0x75, 0xFA, 0xE6, 0x4A, 0x8D, 0x28, 0xB4, 0x04, 0xDC, 0x7D, 0xAF, 0x63],
+            serialize(DataTypeId::GUID, "{72962B91-FA75-4AE6-8D28-B404DC7DAF63}")
+        );
+        assert_eq!(vec![0x0f, 0x03, 0x00, 0x00, 0x00, 0x01, 0x02, 0xff], serialize(DataTypeId::BYTESTRING, "0x0102ff"));
+        assert_eq!(vec![0x0f, 0x03, 0x00, 0x00, 0x00, 0x01, 0x02, 0xff], serialize(DataTypeId::BYTESTRING, "AQL/"));
+        assert_eq!(vec![0x10, 0x03, 0x00, 0x00, 0x00, b'<', b'a', b'>'], serialize(DataTypeId::XMLELEMENT, "<a>"));
+        assert_eq!(vec![0x11, 0x01, 0x02, 0x05, 0x00], serialize(DataTypeId::NODEID, "ns=2;i=5"));
+        assert_eq!(vec![0x11, 0x03, 0x02, 0x00, 0x03, 0x00, 0x00, 0x00, b'F', b'o', b'o'], serialize(DataTypeId::NODEID, "ns=2;s=Foo"));
+        assert_eq!(vec![0x11, 0x00, 0x55], serialize(DataTypeId::NODEID, "i=85"));
+        assert_eq!(
+            vec![0x12, 0xc0, 0x55, 0x01, 0x00, 0x00, 0x00, b'u', 0x01, 0x00, 0x00, 0x00],
+            serialize(DataTypeId::EXPANDED_NODEID, "svr=1;nsu=u;i=85")
+        );
+        assert_eq!(vec![0x12, 0x00, 0x55], serialize(DataTypeId::EXPANDED_NODEID, "i=85"));
+        assert_eq!(vec![0x13, 0x00, 0x00, 0x34, 0x80], serialize(DataTypeId::STATUS_CODE, "BadNodeIdUnknown"));
+        assert_eq!(vec![0x13, 0x00, 0x00, 0x34, 0x80], serialize(DataTypeId::STATUS_CODE, "0x80340000"));
+        assert_eq!(vec![0x14, 0x02, 0x00, 0x01, 0x00, 0x00, 0x00, b'N'], serialize(DataTypeId::QUALIFIE_NAME, "2:N"));
+        assert_eq!(vec![0x14, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, b'a', b':', b'b'], serialize(DataTypeId::QUALIFIE_NAME, "a:b"));
+        assert_eq!(
+            vec![0x15, 0x03, 0x02, 0x00, 0x00, 0x00, b'e', b'n', 0x01, 0x00, 0x00, 0x00, b'T'],
+            serialize(DataTypeId::LOCALIZED_TEXT, "en|T")
+        );
+        assert_eq!(vec![0x15, 0x02, 0x01, 0x00, 0x00, 0x00, b'T'], serialize(DataTypeId::LOCALIZED_TEXT, "T"));
+        //arrays of builtin types
+        let ids = Variant::from_datatype(DataTypeId::NODEID, r#"["ns=1;s=a;b", "i=1"]"#).unwrap();
+        assert_eq!(ids.dimensions(), vec![2]);
+        //invalid text
+        assert!(Variant::from_datatype(DataTypeId::DATETIME, "2024-13-01T00:00:00Z").is_err());
+        //the day must exist in the month
+        assert!(Variant::from_datatype(DataTypeId::DATETIME, "2023-02-29").is_err());
+        assert!(Variant::from_datatype(DataTypeId::DATETIME, "2024-02-29").is_ok());
+        assert!(Variant::from_datatype(DataTypeId::DATETIME, "1900-02-29").is_err());
+        assert!(Variant::from_datatype(DataTypeId::DATETIME, "2000-02-29").is_ok());
+        assert!(Variant::from_datatype(DataTypeId::DATETIME, "2024-04-31").is_err());
+        assert!(Variant::from_datatype(DataTypeId::DATETIME, "yesterday").is_err());
+        //no panic on a fraction that is not made of digits, or on a year or an offset out of range
+        assert!(Variant::from_datatype(DataTypeId::DATETIME, "2024-01-01T00:00:00.123456é").is_err());
+        assert!(Variant::from_datatype(DataTypeId::DATETIME, "2024-01-01T00:00:00.12345678é").is_err());
+        assert!(Variant::from_datatype(DataTypeId::DATETIME, "999999999999-01-01").is_err());
+        assert!(Variant::from_datatype(DataTypeId::DATETIME, "0000-01-01").is_err());
+        assert!(Variant::from_datatype(DataTypeId::DATETIME, "2024-01-01T00:00:00+99999999999999:00").is_err());
+        assert!(Variant::from_datatype(DataTypeId::DATETIME, "9999-12-31T23:59:59.9999999Z").is_ok());
+        assert!(Variant::from_datatype(DataTypeId::GUID, "72962B91").is_err());
+        assert!(Variant::from_datatype(DataTypeId::NODEID, "ns=x;i=5").is_err());
+        assert!(Variant::from_datatype(DataTypeId::NODEID, "q=5").is_err());
+        assert!(Variant::from_datatype(DataTypeId::STATUS_CODE, "NotAStatus").is_err());
+        assert!(Variant::from_datatype(DataTypeId::BYTESTRING, "0xzz").is_err());
+    }
 }
@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use crate::msg::header::prelude::*;
+    use crate::msg::message_type::MessageType;
+    use crate::msg::services::BrowseRequest;
+    use crate::msg::{self, parser, Msg};
+    use crate::uatypes::generated::*;
+    use crate::uatypes::prelude::*;
+    use crate::{Deserialize, Serialize};
+
+    fn browse_request() -> BrowseRequest {
+        BrowseRequest {
+            message_header: MessageHeader::build(MessageType::MSG, b'F', 1),
+            security_header: SymmetricSecurityHeader { token_id: 1 },
+            sequence_header: SequenceHeader { sequence_number: 2, request_id: 2 },
+            node_id: NodeId::new_numeric(0, ObjectIds::BrowseRequest_Encoding_DefaultBinary),
+            request_header: RequestHeader {
+                authentication_token: NodeId::new_numeric(0, 0),
+                timestamp: DateTime::from(132_000_000_000_000_000),
+                request_handle: 3,
+                return_diagnostic: 0,
+                audit_entry: UaString::new(),
+                timout_hint: 1000,
+                additional_header: ExtensionObject::null(),
+            },
+            view: ViewDescription { view_id: NodeId::new_numeric(0, 0), timestamp: DateTime::new(), view_version: 0 },
+            requested_max_references_per_node: 100,
+            nodes_to_browse: UaArray::from(vec![BrowseDescription {
+                node_id: NodeId::new_numeric(0, 85),
+                browse_direction: BrowseDirection::FORWARD,
+                reference_type_id: NodeId::new_numeric(0, 33),
+                include_subtypes: true,
+                node_class_mask: NodeClass::OBJECT | NodeClass::VARIABLE,
+                result_mask: 0x3f,
+            }]),
+        }
+    }
+
+    #[test]
+    fn browse_request_round_trip() {
+        let request = browse_request();
+        let encoded = request.serialize();
+        assert_eq!(encoded.len(), request.encoded_len());
+        let (rest, decoded) = BrowseRequest::deserialize(&encoded).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded.nodes_to_browse.len(), 1);
+        assert_eq!(decoded.nodes_to_browse[0].node_class_mask, 3);
+        assert_eq!(decoded.serialize(), encoded);
+        //the array is preceded by its length as the NoOfNodesToBrowse field of the schema
+        let array = request.nodes_to_browse.serialize();
+        assert_eq!(&array[..4], &[1, 0, 0, 0]);
+        assert!(encoded.ends_with(&array));
+    }
+
+    #[test]
+    fn services_are_parsed() {
+        let mut encoded = browse_request().serialize();
+        msg::set_size(&mut encoded);
+        match parser::parse(&encoded).unwrap() {
+            Msg::BrowseRequest(m) => assert_eq!(m.requested_max_references_per_node, 100),
+            m => panic!("unexpected message {}", m.name()),
+        }
+        assert!(Msg::BrowseRequest(browse_request()).request_header().is_some());
+    }
+
+    #[test]
+    fn generated_errors_have_a_path() {
+        let encoded = browse_request().serialize();
+        let error = BrowseRequest::deserialize(&encoded[..encoded.len() - 1]).unwrap_err();
+        assert_eq!(error.path().unwrap(), "BrowseRequest.nodes_to_browse[0].result_mask");
+    }
+
+    #[test]
+    fn node_ids() {
+        assert_eq!(ObjectIds::OpenSecureChannelRequest_Encoding_DefaultBinary, 446);
+        assert_eq!(ObjectIds::BrowseRequest_Encoding_DefaultBinary, 527);
+        assert_eq!(DataTypeIds::BrowseRequest, 525);
+        assert_eq!(DataTypeIds::DiagnosticInfo, 25);
+    }
+
+    #[test]
+    fn status_code_names() {
+        assert_eq!(StatusCode::name(StatusCode::BadDecodingError), Some("BadDecodingError"));
+        assert_eq!(StatusCode::new(0x806F0000).to_str(), "BadNoMatch");
+        assert_eq!(StatusCode::new(0x12345678).to_str(), "not a status code");
+        assert!(StatusCode::is_status_code(&StatusCode::GoodCascade));
+    }
+}
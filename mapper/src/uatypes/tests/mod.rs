@@ -1,2 +1,6 @@
 mod decoding;
 mod encoding;
+mod generated;
+mod node_id;
+mod status_code;
+mod type_registry;
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests {
+    use crate::uatypes::prelude::*;
+    use crate::Serialize;
+
+    #[test]
+    fn node_id_text_round_trip() {
+        for text in [
+            "i=2258",
+            "ns=1;i=42",
+            "ns=2;s=the.answer",
+            "s=a;b=c",
+            "ns=3;g=72962b91-fa75-4ae6-8d28-b404dc7daf63",
+            "ns=4;b=AQL/",
+        ] {
+            assert_eq!(text.parse::<NodeId>().unwrap().to_string(), text);
+        }
+        //namespace 0 is implicit and the guid is formatted in lowercase
+        assert_eq!("ns=0;i=85".parse::<NodeId>().unwrap().to_string(), "i=85");
+        assert_eq!("g={72962B91-FA75-4AE6-8D28-B404DC7DAF63}".parse::<NodeId>().unwrap().to_string(), "g=72962b91-fa75-4ae6-8d28-b404dc7daf63");
+        assert_eq!("ns=1;i=42".parse::<NodeId>().unwrap(), NodeId::new_numeric(1, 42));
+        assert_eq!("ns=2;s=the.answer".parse::<NodeId>().unwrap(), NodeId::new_string(2, UaString::from("the.answer")));
+        for text in ["", "42", "ns=1", "ns=70000;i=1", "x=1", "i=-1", "g=1234", "b=!"] {
+            assert!(text.parse::<NodeId>().is_err(), "{}", text);
+        }
+        //the base64 of these bytes starts with 0x, it is not read as hexadecimal
+        let node_id = NodeId::new_bytestring(1, ByteString::from(vec![0xd3, 0x1a, 0x0f]));
+        assert_eq!(node_id.to_string(), "ns=1;b=0xoP");
+        assert_eq!(node_id.to_string().parse::<NodeId>().unwrap(), node_id);
+        assert_eq!("b=".parse::<NodeId>().unwrap(), NodeId::new_bytestring(0, ByteString::from(vec![])));
+    }
+
+    #[test]
+    fn expanded_node_id_text_round_trip() {
+        for text in ["i=85", "ns=1;s=a", "nsu=http://example.org/UA/;s=the.answer", "svr=2;nsu=urn:a;i=5", "svr=1;ns=3;i=5"] {
+            assert_eq!(text.parse::<ExpandedNodeId>().unwrap().to_string(), text);
+        }
+        let node_id = "nsu=urn:a;i=5".parse::<ExpandedNodeId>().unwrap();
+        assert_eq!(node_id.namespace_uri().map(String::as_str), Some("urn:a"));
+        //encoded with the namespace uri flag
+        assert_eq!(node_id.serialize(), vec![0x80, 0x05, 0x05, 0x00, 0x00, 0x00, b'u', b'r', b'n', b':', b'a']);
+        assert!("svr=x;i=5".parse::<ExpandedNodeId>().is_err());
+        assert!("nsu=urn:a".parse::<ExpandedNodeId>().is_err());
+        //the ; and % of the uri are percent-encoded
+        let node_id = "nsu=urn:a%3Bb%25c;i=5".parse::<ExpandedNodeId>().unwrap();
+        assert_eq!(node_id.namespace_uri().map(String::as_str), Some("urn:a;b%c"));
+        assert_eq!(node_id.to_string(), "nsu=urn:a%3Bb%25c;i=5");
+        assert_eq!(node_id.to_string().parse::<ExpandedNodeId>().unwrap().namespace_uri().map(String::as_str), Some("urn:a;b%c"));
+        assert!("nsu=urn:a%3;i=5".parse::<ExpandedNodeId>().is_err());
+        assert!("nsu=urn:a%zz;i=5".parse::<ExpandedNodeId>().is_err());
+    }
+
+    #[test]
+    fn resolve_namespace_uri() {
+        let namespace_array = vec![UaString::from("http://opcfoundation.org/UA/"), UaString::from("urn:server"), UaString::from("urn:a")];
+        let node_id = "nsu=urn:a;s=the.answer".parse::<ExpandedNodeId>().unwrap();
+        assert_eq!(node_id.resolve(&namespace_array).unwrap(), NodeId::new_string(2, UaString::from("the.answer")));
+        //a server that renumbers its namespaces
+        let renumbered = vec![UaString::from("http://opcfoundation.org/UA/"), UaString::from("urn:a")];
+        assert_eq!(node_id.resolve(&renumbered).unwrap(), NodeId::new_string(1, UaString::from("the.answer")));
+        assert!(node_id.resolve(&namespace_array[..2]).is_err());
+        //without namespace uri the node id is kept
+        assert_eq!("ns=5;i=1".parse::<ExpandedNodeId>().unwrap().resolve(&[]).unwrap(), NodeId::new_numeric(5, 1));
+        assert!("svr=1;i=1".parse::<ExpandedNodeId>().unwrap().resolve(&namespace_array).is_err());
+        assert_eq!(VariableIds::Server_NamespaceArray, 2255);
+    }
+}
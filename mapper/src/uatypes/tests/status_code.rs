@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests {
+    use crate::uatypes::status_code::{Severity, StatusCode, StatusCodeBits};
+    use crate::{Deserialize, Serialize};
+
+    #[test]
+    fn severity_and_sub_code() {
+        assert_eq!(StatusCode::new(StatusCode::Good).severity(), Severity::Good);
+        assert_eq!(StatusCode::new(StatusCode::GoodCompletesAsynchronously).severity(), Severity::Good);
+        assert_eq!(StatusCode::new(StatusCode::UncertainInitialValue).severity(), Severity::Uncertain);
+        assert_eq!(StatusCode::new(StatusCode::BadWriteNotSupported).severity(), Severity::Bad);
+        assert!(StatusCode::new(0xC000_0000).is_bad());
+        assert!(StatusCode::new(StatusCode::UncertainInitialValue).is_uncertain());
+        assert_eq!(StatusCode::new(StatusCode::BadWriteNotSupported).sub_code(), 0x073);
+        assert_eq!(StatusCode::new(StatusCode::BadNodeIdUnknown | StatusCodeBits::STRUCTURE_CHANGED).code(), StatusCode::new(StatusCode::BadNodeIdUnknown));
+    }
+
+    #[test]
+    fn info_bits() {
+        let status = StatusCode::new(StatusCode::Good | StatusCodeBits::SEMANTICS_CHANGED | StatusCodeBits::INFO_TYPE_DATA_VALUE | StatusCodeBits::LIMIT_HIGH | StatusCodeBits::OVERFLOW);
+        assert!(status.semantics_changed());
+        assert!(!status.structure_changed());
+        assert!(status.overflow());
+        assert_eq!(status.limit(), StatusCodeBits::LIMIT_HIGH);
+        assert_eq!(status.to_string(), "Good|SemanticsChanged|LimitHigh|Overflow");
+        //without the DataValue info type the low bits are not limit nor overflow bits
+        let status = StatusCode::new(StatusCode::Good | StatusCodeBits::OVERFLOW);
+        assert!(!status.overflow());
+        assert_eq!(status.to_string(), "Good");
+        let status = StatusCode::new(StatusCode::UncertainInitialValue | StatusCodeBits::INFO_TYPE_DATA_VALUE | StatusCodeBits::HISTORIAN_INTERPOLATED | StatusCodeBits::HISTORIAN_PARTIAL);
+        assert_eq!(status.to_string(), "UncertainInitialValue|HistorianInterpolated|HistorianPartial");
+    }
+
+    #[test]
+    fn display_and_names() {
+        assert_eq!(StatusCode::new(StatusCode::BadNodeIdUnknown).to_string(), "BadNodeIdUnknown");
+        assert_eq!(StatusCode::new(StatusCode::BadNodeIdUnknown | StatusCodeBits::STRUCTURE_CHANGED).to_string(), "BadNodeIdUnknown|StructureChanged");
+        assert_eq!(StatusCode::new(0x80FF_0000).to_string(), "0x80FF0000");
+        assert_eq!(StatusCode::from_name("BadWriteNotSupported"), Some(StatusCode::BadWriteNotSupported));
+        assert_eq!(StatusCode::from_name("NotAStatus"), None);
+        assert_eq!("BadUserAccessDenied".parse::<StatusCode>().unwrap(), StatusCode::new(StatusCode::BadUserAccessDenied));
+        assert_eq!("0x801F0000".parse::<StatusCode>().unwrap(), StatusCode::new(StatusCode::BadUserAccessDenied));
+        assert!("Bad Status".parse::<StatusCode>().is_err());
+    }
+
+    #[test]
+    fn decode_with_info_bits() {
+        //the info bits of a known code are kept
+        let encoded = (StatusCode::Good | StatusCodeBits::STRUCTURE_CHANGED).serialize();
+        let (_, status) = StatusCode::deserialize(&encoded).unwrap();
+        assert_eq!(status.serialize(), encoded);
+        //an unknown code is kept with its severity and given in hexadecimal
+        let (_, status) = StatusCode::deserialize(&0x80FF_0000_u32.serialize()).unwrap();
+        assert_eq!(status, StatusCode::new(0x80FF_0000));
+        assert!(status.is_bad());
+        assert_eq!(status.to_string(), "0x80FF0000");
+        assert_eq!(status.serialize(), 0x80FF_0000_u32.serialize());
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use crate::encoding_prelude::*;
+    use crate::uatypes::generated::*;
+    use crate::uatypes::prelude::*;
+
+    #[test]
+    fn decode_identity_token() {
+        let token = ExtensionObject::anon(&UaString::from("anonymous"));
+        let decoded = token.decode::<AnonymousIdentityToken>().unwrap();
+        assert_eq!(decoded.policy_id, UaString::from("anonymous"));
+        //the body is registered as another structure
+        assert!(token.decode::<UserNameIdentityToken>().is_err());
+
+        let token = ExtensionObject::new_user_cert(&UaString::from("certificate"), &ByteString::from(vec![1, 2, 3]));
+        let known = token.decode_known().unwrap().unwrap();
+        assert_eq!(known.type_name(), "X509IdentityToken");
+        assert_eq!(known.downcast_ref::<X509IdentityToken>().unwrap().certificate, ByteString::from(vec![1, 2, 3]));
+        assert!(format!("{:?}", known).starts_with("X509IdentityToken"));
+    }
+
+    #[test]
+    fn decode_generated_structure() {
+        let filter = DataChangeFilter { trigger: DataChangeTrigger::STATUS_VALUE, deadband_type: 1, deadband_value: 0.5 };
+        let object = ExtensionObject::from_body(&filter);
+        assert_eq!(object.type_id, NodeId::new_numeric(0, ObjectIds::DataChangeFilter_Encoding_DefaultBinary));
+        let known = object.decode_known().unwrap().unwrap();
+        assert_eq!(known.downcast_ref::<DataChangeFilter>().unwrap().deadband_value, 0.5);
+    }
+
+    #[test]
+    fn unknown_types_stay_raw() {
+        let object = ExtensionObject {
+            type_id: NodeId::new_numeric(0, 12345),
+            body: ExtensionObjectBody::ByteString(ByteString::from(vec![1, 2, 3, 4])),
+        };
+        assert!(object.decode_known().unwrap().is_none());
+        assert_eq!(object.decode::<u32>().unwrap(), 0x04030201);
+        let empty = ExtensionObject { type_id: NodeId::new_numeric(0, 0), body: ExtensionObjectBody::None };
+        assert!(empty.decode_known().unwrap().is_none());
+        assert!(empty.decode::<u32>().is_err());
+        //a known type with a truncated body
+        let truncated = ExtensionObject {
+            type_id: NodeId::new_numeric(0, ObjectIds::AnonymousIdentityToken_Encoding_DefaultBinary),
+            body: ExtensionObjectBody::ByteString(ByteString::from(vec![4, 0, 0, 0, b'a'])),
+        };
+        assert!(truncated.decode_known().is_err());
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct VendorStructure {
+        serial_number: UaString,
+        temperature: f32,
+    }
+
+    #[test]
+    fn register_vendor_structure() {
+        let encoding_id = NodeId::new_numeric(2, 5001);
+        let body = VendorStructure { serial_number: UaString::from("SN-42"), temperature: 21.5 };
+        let object = ExtensionObject {
+            type_id: encoding_id.clone(),
+            body: ExtensionObjectBody::ByteString(ByteString::from(body.serialize())),
+        };
+        assert!(object.decode_known().unwrap().is_none());
+
+        TypeRegistry::register::<VendorStructure>(encoding_id.clone());
+        let known = object.decode_known().unwrap().unwrap();
+        let decoded = known.downcast_ref::<VendorStructure>().unwrap();
+        assert_eq!(decoded.serial_number, UaString::from("SN-42"));
+        assert_eq!(decoded.temperature, 21.5);
+        assert!(object.decode::<AnonymousIdentityToken>().is_err());
+
+        TypeRegistry::unregister(&encoding_id);
+        assert!(object.decode_known().unwrap().is_none());
+    }
+}
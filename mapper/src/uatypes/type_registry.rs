@@ -0,0 +1,139 @@
+//! Registry of the structures that can be decoded from the body of an [`ExtensionObject`](super::extension_object::ExtensionObject).
+//! Structures are indexed by the NodeId of their binary encoding (the `X_Encoding_DefaultBinary` objects).
+//! The structures of namespace 0 known by the mapper are registered by default, vendor structures
+//! can be added at runtime with [`TypeRegistry::register`]. Bodies of unknown types stay raw.
+
+use std::any::{Any, TypeId};
+use std::fmt::Debug;
+use std::sync::RwLock;
+
+use crate::encoding_prelude::*;
+use crate::msg::header::prelude::*;
+use super::prelude::*;
+
+/// Structures of namespace 0 with a binary encoding object.
+pub trait BinaryEncodingId {
+    const ENCODING_ID: u32;
+}
+
+/// Structure decoded from the body of an ExtensionObject, whose type is known at runtime.
+pub trait DecodedBody: Debug + Any {
+    fn as_any(&self) -> &dyn Any;
+    ///name of the rust type
+    fn type_name(&self) -> &'static str;
+}
+
+impl<T: Deserialize + Debug + Any> DecodedBody for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<T>().rsplit("::").next().unwrap_or_default()
+    }
+}
+
+impl dyn DecodedBody {
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.as_any().downcast_ref::<T>()
+    }
+}
+
+//decoder of a registered structure
+#[derive(Clone, Copy)]
+pub(crate) struct Registration {
+    pub(crate) type_id: fn() -> TypeId,
+    pub(crate) decoder: fn(&[u8]) -> MapperResult<Box<dyn DecodedBody>>,
+}
+
+impl Registration {
+    pub(crate) fn of<T: Deserialize + Debug + 'static>() -> Registration {
+        Registration {
+            type_id: TypeId::of::<T>,
+            decoder: |data| Ok(Box::new(T::deserialize(data)?.1)),
+        }
+    }
+}
+
+//structures registered at runtime, they take precedence over the built-in ones
+static CUSTOM: RwLock<Vec<(NodeId, Registration)>> = RwLock::new(Vec::new());
+
+pub struct TypeRegistry;
+
+impl TypeRegistry {
+    /// Decode the bodies of ExtensionObjects of type `encoding_id` as `T`. A previous registration of the same id is replaced.
+    pub fn register<T: Deserialize + Debug + 'static>(encoding_id: NodeId) {
+        let mut custom = CUSTOM.write().unwrap_or_else(|e| e.into_inner());
+        custom.retain(|(id, _)| *id != encoding_id);
+        custom.push((encoding_id, Registration::of::<T>()));
+    }
+
+    /// Remove a structure registered at runtime.
+    pub fn unregister(encoding_id: &NodeId) {
+        CUSTOM.write().unwrap_or_else(|e| e.into_inner()).retain(|(id, _)| id != encoding_id);
+    }
+
+    pub(crate) fn lookup(encoding_id: &NodeId) -> Option<Registration> {
+        let custom = CUSTOM.read().unwrap_or_else(|e| e.into_inner());
+        if let Some((_, registration)) = custom.iter().find(|(id, _)| id == encoding_id) {
+            return Some(*registration);
+        }
+        match (encoding_id.namespace, &encoding_id.identifier) {
+            (0, Identifier::Numeric(id)) => TypeRegistry::built_in(*id),
+            _ => None,
+        }
+    }
+
+    fn built_in(encoding_id: u32) -> Option<Registration> {
+        let registration = match encoding_id {
+            AnonymousIdentityToken::ENCODING_ID => Registration::of::<AnonymousIdentityToken>(),
+            UserNameIdentityToken::ENCODING_ID => Registration::of::<UserNameIdentityToken>(),
+            X509IdentityToken::ENCODING_ID => Registration::of::<X509IdentityToken>(),
+            UserTokenPolicy::ENCODING_ID => Registration::of::<UserTokenPolicy>(),
+            ApplicationDescription::ENCODING_ID => Registration::of::<ApplicationDescription>(),
+            EndpointDescription::ENCODING_ID => Registration::of::<EndpointDescription>(),
+            SignatureData::ENCODING_ID => Registration::of::<SignatureData>(),
+            ReadValueId::ENCODING_ID => Registration::of::<ReadValueId>(),
+            WriteValue::ENCODING_ID => Registration::of::<WriteValue>(),
+            ObjectIds::ResponseHeader_Encoding_DefaultBinary => Registration::of::<ResponseHeader>(),
+            _ => return super::generated::registration(encoding_id),
+        };
+        Some(registration)
+    }
+}
+
+impl BinaryEncodingId for AnonymousIdentityToken {
+    const ENCODING_ID: u32 = ObjectIds::AnonymousIdentityToken_Encoding_DefaultBinary;
+}
+
+impl BinaryEncodingId for UserNameIdentityToken {
+    const ENCODING_ID: u32 = ObjectIds::UserNameIdentityToken_Encoding_DefaultBinary;
+}
+
+impl BinaryEncodingId for X509IdentityToken {
+    const ENCODING_ID: u32 = ObjectIds::X509IdentityToken_Encoding_DefaultBinary;
+}
+
+impl BinaryEncodingId for UserTokenPolicy {
+    const ENCODING_ID: u32 = ObjectIds::UserTokenPolicy_Encoding_DefaultBinary;
+}
+
+impl BinaryEncodingId for ApplicationDescription {
+    const ENCODING_ID: u32 = ObjectIds::ApplicationDescription_Encoding_DefaultBinary;
+}
+
+impl BinaryEncodingId for EndpointDescription {
+    const ENCODING_ID: u32 = ObjectIds::EndpointDescription_Encoding_DefaultBinary;
+}
+
+impl BinaryEncodingId for SignatureData {
+    const ENCODING_ID: u32 = ObjectIds::SignatureData_Encoding_DefaultBinary;
+}
+
+impl BinaryEncodingId for ReadValueId {
+    const ENCODING_ID: u32 = ObjectIds::ReadValueId_Encoding_DefaultBinary;
+}
+
+impl BinaryEncodingId for WriteValue {
+    const ENCODING_ID: u32 = ObjectIds::WriteValue_Encoding_DefaultBinary;
+}
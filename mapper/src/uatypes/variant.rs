@@ -4,8 +4,12 @@ use super::prelude::*;
 #[derive(Debug,Clone)]
 //Variant is a structure to contain vector of opc ua datatype
 pub struct Variant {
-    pub(crate) value: Vec<Value>,
+    //values of an array are kept as read, a null array (length -1) is not an empty one
+    pub(crate) value: UaArray<Value>,
+    //dimensions of a matrix, empty for a scalar or an array of one dimension
     pub(crate) array_dimension: Vec<i32>,
+    //type of the elements of an array (an empty array still has a type), None for a scalar
+    pub(crate) array_type: Option<u8>,
 }
 
 #[derive(Serialize,Debug,Clone)]
@@ -70,25 +74,41 @@ impl Value{
     }
 }
 
+//encoded by hand: the encoding byte combines the type of the value with the array flags
 impl Serialize for Variant
 {
-    fn serialize(&self) -> Vec<u8> {
-        let mut result = vec![0x00];
-        if self.value.len()==0 {
-            return result;
-        }
-        else if self.value.len()==1 
-        {
-            result[0]=Value::get_encoding_value(&self.value[0]);
-            result.extend_from_slice(&self.value[0].serialize());
-        }
-        else
-        {
-            result[0]=result[0]|Value::get_encoding_value(&self.value[0])|DataTypeId::ARRAY_VALUE_ENCODED;
-            result.extend_from_slice(&self.value.serialize());
+    fn serialize_into(&self, buf: &mut impl BufMut) {
+        match self.array_type {
+            Some(type_) => {
+                let mut encoding_mask = type_ | DataTypeId::ARRAY_VALUE_ENCODED;
+                if !self.array_dimension.is_empty() {
+                    encoding_mask |= DataTypeId::ARRAY_DIM_ENCODED;
+                }
+                buf.put_u8(encoding_mask);
+                self.value.serialize_into(buf);
+                if !self.array_dimension.is_empty() {
+                    self.array_dimension.serialize_into(buf);
+                }
+            },
+            None => match self.value.first() {
+                Some(value) => {
+                    buf.put_u8(Value::get_encoding_value(value));
+                    value.serialize_into(buf);
+                },
+                None => buf.put_u8(DataTypeId::NULL),
+            },
         }
-        //We currently ignore multidimensionnal array
-        result
+    }
+}
+
+//decode one value or an array of values of type T
+fn decode_values<T: Deserialize>(data: &[u8], is_array: bool, to_value: fn(T) -> Value) -> MapperResult<(&[u8], UaArray<Value>)> {
+    if is_array {
+        let (data, val) = UaArray::<T>::deserialize(data)?;
+        Ok((data, UaArray { values: val.values.map(|v| v.into_iter().map(to_value).collect()) }))
+    } else {
+        let (data, val) = T::deserialize(data)?;
+        Ok((data, UaArray::from(vec![to_value(val)])))
     }
 }
 
@@ -96,323 +116,57 @@ impl Deserialize for Variant
 
 {
     fn deserialize(data: &[u8]) -> MapperResult<(&[u8], Self)> {
-        let (mut data, encoding_mask) = u8::deserialize(data)?;
+        let _depth = DepthGuard::enter()?;
+        let (mut data, encoding_mask) = u8::deserialize(data).map_err(|e| e.in_field("Variant", "encoding_mask", data.len()))?;
         if encoding_mask == DataTypeId::NULL {
             return Ok((
                 data,
-                Variant {
-                    value:vec![],
-                    array_dimension:vec![],
-                },
+                Variant::null(),
             ));
         }
-        let mut value:Vec<Value>=vec![];
         let mut array_dimension : Vec<i32>=vec![];
-        if encoding_mask & 0x3f == DataTypeId::BOOLEAN {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<bool>;
-                (data, val) = Vec::<bool>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::Boolean(el)}).collect();
-            }
-            else {
-                let val : bool;
-                (data, val) = bool::deserialize(data)?;
-                value=vec![Value::Boolean(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::SBYTE {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<i8>;
-                (data, val) = Vec::<i8>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::Sbyte(el)}).collect();
-            }
-            else {
-                let val : i8;
-                (data, val) = i8::deserialize(data)?;
-                value=vec![Value::Sbyte(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::BYTE {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<u8>;
-                (data, val) = Vec::<u8>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::Byte(el)}).collect();
-            }
-            else {
-                let val : u8;
-                (data, val) = u8::deserialize(data)?;
-                value=vec![Value::Byte(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::INT_16 {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<i16>;
-                (data, val) = Vec::<i16>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::Int16(el)}).collect();
-            }
-            else {
-                let val : i16;
-                (data, val) = i16::deserialize(data)?;
-                value=vec![Value::Int16(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::UINT_16 {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<u16>;
-                (data, val) = Vec::<u16>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::Uint16(el)}).collect();
-            }
-            else {
-                let val : u16;
-                (data, val) = u16::deserialize(data)?;
-                value=vec![Value::Uint16(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::INT_32 {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<i32>;
-                (data, val) = Vec::<i32>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::Int32(el)}).collect();
-            }
-            else {
-                let val : i32;
-                (data, val) = i32::deserialize(data)?;
-                value=vec![Value::Int32(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::UINT_32 {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<u32>;
-                (data, val) = Vec::<u32>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::Uint32(el)}).collect();
-            }
-            else {
-                let val : u32;
-                (data, val) = u32::deserialize(data)?;
-                value=vec![Value::Uint32(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::INT_64 {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<i64>;
-                (data, val) = Vec::<i64>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::Int64(el)}).collect();
-            }
-            else {
-                let val : i64;
-                (data, val) = i64::deserialize(data)?;
-                value=vec![Value::Int64(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::UINT_64 {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<u64>;
-                (data, val) = Vec::<u64>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::Uint64(el)}).collect();
-            }
-            else {
-                let val : u64;
-                (data, val) = u64::deserialize(data)?;
-                value=vec![Value::Uint64(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::FLOAT {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<f32>;
-                (data, val) = Vec::<f32>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::Float(el)}).collect();
-            }
-            else {
-                let val : f32;
-                (data, val) = f32::deserialize(data)?;
-                value=vec![Value::Float(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::DOUBLE {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<f64>;
-                (data, val) = Vec::<f64>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::Double(el)}).collect();
-            }
-            else {
-                let val : f64;
-                (data, val) = f64::deserialize(data)?;
-                value=vec![Value::Double(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::STRING {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<UaString>;
-                (data, val) = Vec::<UaString>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::String(el)}).collect();
-            }
-            else {
-                let val : UaString;
-                (data, val) = UaString::deserialize(data)?;
-                value=vec![Value::String(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::DATETIME {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<DateTime>;
-                (data, val) = Vec::<DateTime>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::DateTime(el)}).collect();
-            }
-            else {
-                let val : DateTime;
-                (data, val) = DateTime::deserialize(data)?;
-                value=vec![Value::DateTime(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::GUID {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<Guid>;
-                (data, val) = Vec::<Guid>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::Guid(el)}).collect();
-            }
-            else {
-                let val : Guid;
-                (data, val) = Guid::deserialize(data)?;
-                value=vec![Value::Guid(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::BYTESTRING {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<ByteString>;
-                (data, val) = Vec::<ByteString>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::ByteString(el)}).collect();
-            }
-            else {
-                let val : ByteString;
-                (data, val) = ByteString::deserialize(data)?;
-                value=vec![Value::ByteString(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::XMLELEMENT {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<XmlElement>;
-                (data, val) = Vec::<XmlElement>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::XmlElement(el)}).collect();
-            }
-            else {
-                let val : XmlElement;
-                (data, val) = XmlElement::deserialize(data)?;
-                value=vec![Value::XmlElement(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::NODEID {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<NodeId>;
-                (data, val) = Vec::<NodeId>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::NodeId(el)}).collect();
-            }
-            else {
-                let val : NodeId;
-                (data, val) = NodeId::deserialize(data)?;
-                value=vec![Value::NodeId(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::EXPANDED_NODEID {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<ExpandedNodeId>;
-                (data, val) = Vec::<ExpandedNodeId>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::ExpandedNodeId(el)}).collect();
-            }
-            else {
-                let val : ExpandedNodeId;
-                (data, val) = ExpandedNodeId::deserialize(data)?;
-                value=vec![Value::ExpandedNodeId(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::STATUS_CODE {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<u32>;
-                (data, val) = Vec::<u32>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::StatusCode(el)}).collect();
-            }
-            else {
-                let val : u32;
-                (data, val) = u32::deserialize(data)?;
-                value=vec![Value::StatusCode(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::QUALIFIE_NAME {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<QualifiedName>;
-                (data, val) = Vec::<QualifiedName>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::QualifiedName(el)}).collect();
-            }
-            else {
-                let val : QualifiedName;
-                (data, val) = QualifiedName::deserialize(data)?;
-                value=vec![Value::QualifiedName(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::LOCALIZED_TEXT {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<LocalizedText>;
-                (data, val) = Vec::<LocalizedText>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::LocaizedText(el)}).collect();
-            }
-            else {
-                let val : LocalizedText;
-                (data, val) = LocalizedText::deserialize(data)?;
-                value=vec![Value::LocaizedText(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::EXTENSION_OBJECT {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<ExtensionObject>;
-                (data, val) = Vec::<ExtensionObject>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::ExtensionObject(el)}).collect();
-            }
-            else {
-                let val : ExtensionObject;
-                (data, val) = ExtensionObject::deserialize(data)?;
-                value=vec![Value::ExtensionObject(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::DATAVALUE {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<DataValue>;
-                (data, val) = Vec::<DataValue>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::DataValue(el)}).collect();
-            }
-            else {
-                let val : DataValue;
-                (data, val) = DataValue::deserialize(data)?;
-                value=vec![Value::DataValue(val)];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::VARIANT {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<Variant>;
-                (data, val) = Vec::<Variant>::deserialize(data)?;
-                value =val.into_iter().map(|el| {Value::Variant(Box::new(el))}).collect();
-            }
-            else {
-                let val : Variant;
-                (data, val) = Variant::deserialize(data)?;
-                value=vec![Value::Variant(Box::new(val))];
-            }
-        }
-        if encoding_mask & 0x3f == DataTypeId::DIAGNOSTIC_INFO {
-            if encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED {
-                let val : Vec<DiagnosticInfo>;
-                (data, val) = Vec::<DiagnosticInfo>::deserialize(data)?;
-                value=val.into_iter().map(|el| {Value::DiagnosticInfo(el)}).collect();
-            }
-            else {
-                let val : DiagnosticInfo;
-                (data, val) = DiagnosticInfo::deserialize(data)?;
-                value=vec![Value::DiagnosticInfo(val)];
-            }
-        }
-        
+        let is_array= encoding_mask &DataTypeId::ARRAY_VALUE_ENCODED == DataTypeId::ARRAY_VALUE_ENCODED;
+        //each type is decoded in its own function to keep the frame of this (recursive) function small
+        let value:UaArray<Value>;
+        let remaining = data.len();
+        (data, value) = match encoding_mask & 0x3f {
+            DataTypeId::BOOLEAN => decode_values::<bool>(data, is_array, Value::Boolean),
+            DataTypeId::SBYTE => decode_values::<i8>(data, is_array, Value::Sbyte),
+            DataTypeId::BYTE => decode_values::<u8>(data, is_array, Value::Byte),
+            DataTypeId::INT_16 => decode_values::<i16>(data, is_array, Value::Int16),
+            DataTypeId::UINT_16 => decode_values::<u16>(data, is_array, Value::Uint16),
+            DataTypeId::INT_32 => decode_values::<i32>(data, is_array, Value::Int32),
+            DataTypeId::UINT_32 => decode_values::<u32>(data, is_array, Value::Uint32),
+            DataTypeId::INT_64 => decode_values::<i64>(data, is_array, Value::Int64),
+            DataTypeId::UINT_64 => decode_values::<u64>(data, is_array, Value::Uint64),
+            DataTypeId::FLOAT => decode_values::<f32>(data, is_array, Value::Float),
+            DataTypeId::DOUBLE => decode_values::<f64>(data, is_array, Value::Double),
+            DataTypeId::STRING => decode_values::<UaString>(data, is_array, Value::String),
+            DataTypeId::DATETIME => decode_values::<DateTime>(data, is_array, Value::DateTime),
+            DataTypeId::GUID => decode_values::<Guid>(data, is_array, Value::Guid),
+            DataTypeId::BYTESTRING => decode_values::<ByteString>(data, is_array, Value::ByteString),
+            DataTypeId::XMLELEMENT => decode_values::<XmlElement>(data, is_array, Value::XmlElement),
+            DataTypeId::NODEID => decode_values::<NodeId>(data, is_array, Value::NodeId),
+            DataTypeId::EXPANDED_NODEID => decode_values::<ExpandedNodeId>(data, is_array, Value::ExpandedNodeId),
+            DataTypeId::STATUS_CODE => decode_values::<u32>(data, is_array, Value::StatusCode),
+            DataTypeId::QUALIFIE_NAME => decode_values::<QualifiedName>(data, is_array, Value::QualifiedName),
+            DataTypeId::LOCALIZED_TEXT => decode_values::<LocalizedText>(data, is_array, Value::LocaizedText),
+            DataTypeId::EXTENSION_OBJECT => decode_values::<ExtensionObject>(data, is_array, Value::ExtensionObject),
+            DataTypeId::DATAVALUE => decode_values::<DataValue>(data, is_array, Value::DataValue),
+            DataTypeId::VARIANT => decode_values::<Variant>(data, is_array, |el| Value::Variant(Box::new(el))),
+            DataTypeId::DIAGNOSTIC_INFO => decode_values::<DiagnosticInfo>(data, is_array, Value::DiagnosticInfo),
+            _ => Ok((data, UaArray::new())),
+        }.map_err(|e| e.in_field("Variant", "value", remaining))?;
         
         if encoding_mask & DataTypeId::ARRAY_DIM_ENCODED == DataTypeId::ARRAY_DIM_ENCODED
         {
-            (data, array_dimension) = Vec::<i32>::deserialize(data)?;
+            let remaining = data.len();
+            (data, array_dimension) = Vec::<i32>::deserialize(data).map_err(|e| e.in_field("Variant", "array_dimensions", remaining))?;
+            //the values of a matrix are given in a single array
+            let length = array_dimension.iter().try_fold(1_usize, |length, d| usize::try_from(*d).ok().and_then(|d| length.checked_mul(d)));
+            if !is_array || length != Some(value.len()) {
+                return Err(MapperError::new(MapperErrorKind::ParsingError, "array dimensions do not match the array length").in_field("Variant", "array_dimensions", remaining));
+            }
         }
         
         Ok((
@@ -420,6 +174,7 @@ impl Deserialize for Variant
             Variant {
                 value,
                 array_dimension,
+                array_type: if is_array { Some(encoding_mask & 0x3f) } else { None },
             },
         ))
     }
@@ -427,96 +182,170 @@ impl Deserialize for Variant
 
 
 impl Variant {
+    pub(crate) fn null() -> Variant {
+        Variant {
+            value: UaArray::new(),
+            array_dimension: vec![],
+            array_type: None,
+        }
+    }
+
+    pub(crate) fn scalar(value: Value) -> Variant {
+        Variant {
+            value: UaArray::from(vec![value]),
+            array_dimension: vec![],
+            array_type: None,
+        }
+    }
+
+    ///array of elements of type `type_`, `array_dimension` is empty for an array of one dimension
+    pub(crate) fn array(type_: u8, value: Vec<Value>, array_dimension: Vec<i32>) -> Variant {
+        Variant {
+            value: UaArray::from(value),
+            array_dimension,
+            array_type: Some(type_),
+        }
+    }
+
+    pub fn is_array(&self) -> bool {
+        self.array_type.is_some()
+    }
+
+    ///dimensions of the value: empty for a scalar, the length for an array, the ArrayDimensions of a matrix
+    pub fn dimensions(&self) -> Vec<i32> {
+        match self.array_type {
+            None => vec![],
+            Some(_) if self.array_dimension.is_empty() => vec![self.value.len() as i32],
+            Some(_) => self.array_dimension.clone(),
+        }
+    }
+
     pub(crate) fn from<T:ToVariant>(value:T)->Variant {
         value.to_variant()
     }
 
+    /// Variant of type `type_` from its text. Arrays are given between brackets and matrices as nested arrays,
+    /// in row-major order as encoded: `[1, 2, 3]`, `[[1, 2], [3, 4]]`. Elements may be quoted to contain commas or brackets: `["a,b", "c"]`.
+    /// A leading backslash gives a scalar whatever the text that follows: `\[a]` is the string `[a]`.
     pub(crate) fn from_datatype(type_:u8,value:&str)->MapperResult<Variant>{
+        if let Some(scalar) = value.strip_prefix('\\') {
+            return Ok(Variant::scalar(Value::from_datatype(type_, scalar)?));
+        }
+        if !value.trim_start().starts_with('[') {
+            return Ok(Variant::scalar(Value::from_datatype(type_, value)?));
+        }
+        let mut parser = ArrayParser { chars: value.trim().chars().peekable(), elements: vec![] };
+        let dimensions = parser.parse_array()?;
+        if parser.chars.next().is_some() {
+            return Err(MapperError::new(MapperErrorKind::VariantError, "unexpected text after the array"));
+        }
+        let values = parser.elements.iter().map(|e| Value::from_datatype(type_, e)).collect::<MapperResult<Vec<Value>>>()?;
+        let array_dimension = if dimensions.len() > 1 { dimensions } else { vec![] };
+        Ok(Variant::array(type_, values, array_dimension))
+    }
+}
+
+impl Value {
+    //scalar value of type `type_` from its text
+    fn from_datatype(type_:u8,value:&str)->MapperResult<Value>{
+        fn parse<T: std::str::FromStr>(value: &str, to_value: fn(T) -> Value) -> MapperResult<Value> {
+            value.trim().parse::<T>().map(to_value).map_err(|_| MapperError::new(MapperErrorKind::VariantError, "incompatible type for variant"))
+        }
         match type_{
-            DataTypeId::BOOLEAN=>{
-                if value=="true"{
-                    Ok(true.to_variant())
-                }else{
-                    Ok(false.to_variant())
-                }
-            },
-            DataTypeId::SBYTE=>{
-                let sbyte=value.parse::<i8>();
-                match sbyte{
-                    Ok(value)=>Ok(value.to_variant()),
-                    Err(_)=>Err(MapperError::new(MapperErrorKind::VariantError, "incompatible type for variant"))
-                }
-            },
-            DataTypeId::BYTE=>{
-                let byte=value.parse::<u8>();
-                match byte{
-                    Ok(value)=>Ok(value.to_variant()),
-                    Err(_)=>Err(MapperError::new(MapperErrorKind::VariantError, "incompatible type for variant"))
-                }
-            },
-            DataTypeId::INT_16=>{
-                let int16=value.parse::<i16>();
-                match int16{
-                    Ok(value)=>Ok(value.to_variant()),
-                    Err(_)=>Err(MapperError::new(MapperErrorKind::VariantError, "incompatible type for variant"))
-                }
-            },
-            DataTypeId::UINT_16=>{
-                let uint16=value.parse::<u16>();
-                match uint16{
-                    Ok(value)=>Ok(value.to_variant()),
-                    Err(_)=>Err(MapperError::new(MapperErrorKind::VariantError, "incompatible type for variant"))
-                }
-            },
-            DataTypeId::INT_32=>{
-                let int32=value.parse::<i32>();
-                match int32{
-                    Ok(value)=>Ok(value.to_variant()),
-                    Err(_)=>Err(MapperError::new(MapperErrorKind::VariantError, "incompatible type for variant"))
-                }
-            },
-            DataTypeId::UINT_32=>{
-                let uint32=value.parse::<u32>();
-                match uint32{
-                    Ok(value)=>Ok(value.to_variant()),
-                    Err(_)=>Err(MapperError::new(MapperErrorKind::VariantError, "incompatible type for variant"))
-                }
-            },
-            DataTypeId::INT_64=>{
-                let int64=value.parse::<i64>();
-                match int64{
-                    Ok(value)=>Ok(value.to_variant()),
-                    Err(_)=>Err(MapperError::new(MapperErrorKind::VariantError, "incompatible type for variant"))
-                }
-            },
-            DataTypeId::UINT_64=>{
-                let uint64=value.parse::<u64>();
-                match uint64{
-                    Ok(value)=>Ok(value.to_variant()),
-                    Err(_)=>Err(MapperError::new(MapperErrorKind::VariantError, "incompatible type for variant"))
-                }
-            },
-            DataTypeId::FLOAT=>{
-                let float=value.parse::<f32>();
-                match float{
-                    Ok(value)=>Ok(value.to_variant()),
-                    Err(_)=>Err(MapperError::new(MapperErrorKind::VariantError, "incompatible type for variant"))
-                }
-            },
-            DataTypeId::DOUBLE=>{
-                let double=value.parse::<f64>();
-                match double{
-                    Ok(value)=>Ok(value.to_variant()),
-                    Err(_)=>Err(MapperError::new(MapperErrorKind::VariantError, "incompatible type for variant"))
-                }
-            },
-            DataTypeId::STRING=>{
-                Ok(value.to_variant())
-            },
-            _=> {
-                Err(MapperError::new(MapperErrorKind::VariantError, "unkown type for variant"))
-            },
+            DataTypeId::BOOLEAN=>Ok(Value::Boolean(value.trim()=="true")),
+            DataTypeId::SBYTE=>parse(value, Value::Sbyte),
+            DataTypeId::BYTE=>parse(value, Value::Byte),
+            DataTypeId::INT_16=>parse(value, Value::Int16),
+            DataTypeId::UINT_16=>parse(value, Value::Uint16),
+            DataTypeId::INT_32=>parse(value, Value::Int32),
+            DataTypeId::UINT_32=>parse(value, Value::Uint32),
+            DataTypeId::INT_64=>parse(value, Value::Int64),
+            DataTypeId::UINT_64=>parse(value, Value::Uint64),
+            DataTypeId::FLOAT=>parse(value, Value::Float),
+            DataTypeId::DOUBLE=>parse(value, Value::Double),
+            DataTypeId::STRING=>Ok(Value::String(UaString::from(value))),
+            DataTypeId::DATETIME=>Ok(Value::DateTime(DateTime::parse(value)?)),
+            DataTypeId::GUID=>Ok(Value::Guid(Guid::parse(value)?)),
+            DataTypeId::BYTESTRING=>Ok(Value::ByteString(ByteString::parse(value)?)),
+            DataTypeId::XMLELEMENT=>Ok(Value::XmlElement(XmlElement::from(value))),
+            DataTypeId::NODEID=>Ok(Value::NodeId(value.parse::<NodeId>()?)),
+            DataTypeId::EXPANDED_NODEID=>Ok(Value::ExpandedNodeId(value.parse::<ExpandedNodeId>()?)),
+            DataTypeId::STATUS_CODE=>Ok(Value::StatusCode(StatusCode::parse(value)?.get_value())),
+            DataTypeId::QUALIFIE_NAME=>Ok(Value::QualifiedName(QualifiedName::parse(value))),
+            DataTypeId::LOCALIZED_TEXT=>Ok(Value::LocaizedText(LocalizedText::parse(value))),
+            _=> Err(MapperError::new(MapperErrorKind::VariantError, "unkown type for variant")),
+        }
+    }
+}
+
+//parser of the text of arrays, the elements are collected in row-major order
+struct ArrayParser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    elements: Vec<String>,
+}
+
+impl ArrayParser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    //parse `[...]` and give its dimensions, the sub-arrays must have the same dimensions
+    fn parse_array(&mut self) -> MapperResult<Vec<i32>> {
+        let error = |message| MapperError::new(MapperErrorKind::VariantError, message);
+        self.skip_whitespace();
+        if self.chars.next() != Some('[') {
+            return Err(error("array must start with ["));
+        }
+        self.skip_whitespace();
+        if self.chars.next_if_eq(&']').is_some() {
+            return Ok(vec![0]);
+        }
+        let mut length = 0;
+        let mut inner: Option<Vec<i32>> = None;
+        loop {
+            self.skip_whitespace();
+            let dimensions = if self.chars.peek() == Some(&'[') {
+                self.parse_array()?
+            } else {
+                self.parse_element()?;
+                vec![]
+            };
+            match &inner {
+                Some(d) if *d != dimensions => return Err(error("rows of a matrix must have the same dimensions")),
+                Some(_) => {},
+                None => inner = Some(dimensions),
+            }
+            length += 1;
+            self.skip_whitespace();
+            match self.chars.next() {
+                Some(',') => continue,
+                Some(']') => break,
+                _ => return Err(error("array must end with ]")),
+            }
+        }
+        let mut result = vec![length];
+        result.extend(inner.unwrap_or_default());
+        Ok(result)
+    }
 
+    fn parse_element(&mut self) -> MapperResult<()> {
+        let mut element = String::new();
+        if self.chars.next_if_eq(&'"').is_some() {
+            loop {
+                match self.chars.next() {
+                    Some('\\') => element.extend(self.chars.next()),
+                    Some('"') => break,
+                    Some(c) => element.push(c),
+                    None => return Err(MapperError::new(MapperErrorKind::VariantError, "unterminated quoted element")),
+                }
+            }
+        } else {
+            while let Some(c) = self.chars.next_if(|c| *c != ',' && *c != ']' && *c != '[') {
+                element.push(c);
+            }
+            element = element.trim().to_string();
         }
+        self.elements.push(element);
+        Ok(())
     }
-}
\ No newline at end of file
+}
@@ -1,6 +1,7 @@
 use crate::encoding_prelude::*;
-use super::{node_id::*, string::UaString, data_value::DataValue,attribute_id::*};
-#[derive(Serialize,Deserialize,Debug)]
+use crate::result_prelude::*;
+use super::{node_id::*, expanded_node_id::ExpandedNodeId, string::UaString, data_value::DataValue,attribute_id::*};
+#[derive(Serialize,Deserialize,Debug,Clone, Mutate)]
 pub(crate) struct WriteValue {
     pub(crate) node_id:NodeId,
     pub(crate) attribute_id:u32,
@@ -15,4 +16,27 @@ impl WriteValue{
                      index_range: UaString::new(),
                      value: value.clone() }
     }
-}
\ No newline at end of file
+
+    ///item of a write from its text `<node id>@<attribute>:<data type id>=<value>`: `ns=1;s=the.answer@DisplayName:21=en|Answer`.
+    ///The value is given as for Variant::from_datatype. The node id is also returned as given, see ReadValueId::parse.
+    ///The node id and the value may contain `@`: the attribute follows the first `@` that is followed by `<attribute name>:`.
+    pub (crate) fn parse(item:&str)->MapperResult<(ExpandedNodeId,WriteValue)>{
+        let error=|message| MapperError::new(MapperErrorKind::UnexpectedValue, message);
+        let (node_id,attribute_id,value)=item.match_indices('@')
+            .find_map(|(i,_)| {
+                let (attribute,value)=item[i+1..].split_once(':')?;
+                Some((&item[..i],AttributeId::from_name(attribute)?,value))
+            })
+            .ok_or_else(|| error("write item without attribute"))?;
+        let (type_,value)=value.split_once('=').ok_or_else(|| error("write item without value"))?;
+        let type_=type_.trim().parse::<u8>().map_err(|_| error("invalid data type of write item"))?;
+        let node_id=node_id.parse::<ExpandedNodeId>()?;
+        let item=WriteValue{
+            node_id:node_id.node_id().clone(),
+            attribute_id,
+            index_range:UaString::new(),
+            value:DataValue::from_value(type_,value)?,
+        };
+        Ok((node_id,item))
+    }
+}